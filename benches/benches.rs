@@ -33,6 +33,15 @@ fn lookup<T>(arena: &Arena<T>, idx: Index<T>, n: usize) {
     }
 }
 
+fn branded_lookup<T>(arena: &mut Arena<T>, idx: Index<T>, n: usize) {
+    arena.scope(|arena| {
+        let valid = arena.validate(idx).unwrap();
+        for _ in 0..n {
+            criterion::black_box(&arena[valid]);
+        }
+    })
+}
+
 fn collect<T>(arena: &Arena<T>, n: usize) {
     for _ in 0..n {
         criterion::black_box(arena.iter().collect::<Vec<_>>());
@@ -190,6 +199,21 @@ fn criterion_benchmark(c: &mut Criterion) {
     }
     group.finish();
 
+    let mut group = c.benchmark_group("branded-lookup-small");
+    for n in 1..3 {
+        let n = n * 100;
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |b, n| {
+            let mut small_arena = Arena::<Small>::new();
+            for _ in 0..1024 {
+                small_arena.insert(Default::default());
+            }
+            let small_idx = small_arena.iter().map(|pair| pair.0).next().unwrap();
+            b.iter(|| branded_lookup(&mut small_arena, small_idx, *n))
+        });
+    }
+    group.finish();
+
     let mut group = c.benchmark_group("collect-small");
     for n in 1..3 {
         let n = n * 100;
@@ -0,0 +1,140 @@
+use super::{ExhaustionPolicy, FixedGenerationalIndex, GenerationalIndex};
+use core::fmt::Debug;
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A type which can be used as the id of an arena in an `IdGeneration`.
+///
+/// Its width decides how many arenas get distinct ids: ids are handed out from a
+/// counter shared by the whole process, which wraps around after `2^{bits} - 1`
+/// arenas.
+pub trait ArenaId: Copy + Eq + Debug {
+    /// The id of generations which were not issued by an arena
+    fn unassigned() -> Self;
+    /// Get a fresh id, which is never the unassigned one
+    fn fresh() -> Self;
+    /// Get this id as a `u64`
+    fn to_u64(self) -> u64;
+}
+
+macro_rules! impl_arena_id {
+    ($($id:ty),*) => {
+        $(
+            impl ArenaId for $id {
+                #[inline(always)]
+                fn unassigned() -> Self {
+                    0
+                }
+                fn fresh() -> Self {
+                    static NEXT: AtomicU64 = AtomicU64::new(1);
+                    loop {
+                        let id = NEXT.fetch_add(1, Ordering::Relaxed) as $id;
+                        if id != 0 {
+                            return id;
+                        }
+                    }
+                }
+                #[inline(always)]
+                fn to_u64(self) -> u64 {
+                    self as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_arena_id!(u8, u16, u32, u64);
+
+/// A generation which carries the id of the arena which issued it, so that an index
+/// used with another arena is rejected instead of resolving to an unrelated element.
+///
+/// Every arena constructed with this generation type gets a fresh id of type `A`.
+/// A clone of an arena keeps its id, and so does a deserialized arena.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{AccessError, StandardIdArena};
+///
+/// let mut a = StandardIdArena::new();
+/// let mut b = StandardIdArena::new();
+/// let in_a = a.insert("a");
+/// b.insert("b");
+///
+/// assert_eq!(a.get(in_a), Some(&"a"));
+/// assert_eq!(b.get(in_a), None);
+/// match b.try_get(in_a) {
+///     Err(AccessError::WrongArena { expected, found }) => assert_ne!(expected, found),
+///     other => panic!("unexpected {:?}", other),
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IdGeneration<G, A = u32> {
+    generation: G,
+    arena: A,
+}
+
+impl<G: Copy, A: ArenaId> IdGeneration<G, A> {
+    /// Get the generation, without the id of its arena
+    #[inline(always)]
+    pub fn generation(self) -> G {
+        self.generation
+    }
+
+    /// Get the id of the arena which issued this generation
+    #[inline(always)]
+    pub fn arena(self) -> A {
+        self.arena
+    }
+}
+
+impl<G: FixedGenerationalIndex, A: ArenaId> FixedGenerationalIndex for IdGeneration<G, A> {
+    #[inline(always)]
+    fn first_generation() -> Self {
+        IdGeneration {
+            generation: G::first_generation(),
+            arena: A::unassigned(),
+        }
+    }
+    #[inline(always)]
+    fn generation_lt(&self, other: &Self) -> bool {
+        // Ids only break ties, so that the order stays total
+        self.generation.generation_lt(&other.generation)
+            || (self.generation == other.generation && self.arena.to_u64() < other.arena.to_u64())
+    }
+    #[inline(always)]
+    fn arena_first_generation() -> Self {
+        IdGeneration {
+            generation: G::arena_first_generation(),
+            arena: A::fresh(),
+        }
+    }
+    #[inline(always)]
+    fn reset_generation(&self) -> Self {
+        IdGeneration {
+            generation: self.generation.reset_generation(),
+            arena: self.arena,
+        }
+    }
+    #[inline(always)]
+    fn arena_id(&self) -> Option<u64> {
+        Some(self.arena.to_u64())
+    }
+    #[inline(always)]
+    fn advance_generation(&mut self, policy: ExhaustionPolicy) -> bool {
+        self.generation.advance_generation(policy)
+    }
+}
+
+impl<G: GenerationalIndex, A: ArenaId> GenerationalIndex for IdGeneration<G, A> {
+    #[inline(always)]
+    fn increment_generation(&mut self) {
+        self.generation.increment_generation()
+    }
+    #[inline(always)]
+    fn checked_increment_generation(&mut self) -> bool {
+        self.generation.checked_increment_generation()
+    }
+}
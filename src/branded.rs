@@ -0,0 +1,320 @@
+use super::{
+    AccessError, Arena, ArenaIndex, Entry, FixedGenerationalIndex, GenerationStrategy, Index,
+};
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops;
+
+// A `Valid` token proves that its slot holds an element of the arena of its
+// scope. The brand is an invariant lifetime which is only ever chosen by
+// `Arena::scope`, so tokens cannot leave their scope or be used with another
+// one, and a branded arena cannot remove elements, so the proof holds for as
+// long as the token exists.
+//
+// With the `unchecked` feature, accesses through a token skip the bounds and
+// occupancy checks entirely, which relies on the above.
+
+/// An invariant lifetime, identifying one call to `Arena::scope`
+type Brand<'brand> = PhantomData<fn(&'brand ()) -> &'brand ()>;
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Arena<T, I, G, S> {
+    /// Call `f` with a branded view of this arena, in which indices can be
+    /// validated once into `Valid` tokens that access their element without
+    /// checking its generation again.
+    ///
+    /// Elements can be inserted into a branded arena, but not removed, so that
+    /// every token stays valid until the end of the scope. Tokens cannot be used
+    /// outside of the scope which created them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let indices: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+    ///
+    /// let sum = arena.scope(|mut arena| {
+    ///     let valid: Vec<_> = indices.iter().filter_map(|&i| arena.validate(i)).collect();
+    ///     for _ in 0..100 {
+    ///         for &v in &valid {
+    ///             arena[v] += 1;
+    ///         }
+    ///     }
+    ///     valid.iter().map(|&v| arena[v]).sum::<usize>()
+    /// });
+    /// assert_eq!(sum, 45 + 1000);
+    /// ```
+    ///
+    /// Tokens cannot escape their scope:
+    ///
+    /// ```compile_fail
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let idx = arena.insert(0);
+    /// let valid = arena.scope(|arena| arena.validate(idx).unwrap());
+    /// ```
+    ///
+    /// Nor be used with another scope:
+    ///
+    /// ```compile_fail
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut a = StandardArena::new();
+    /// let mut b = StandardArena::new();
+    /// let idx = a.insert(0);
+    /// b.insert(1);
+    /// a.scope(|a| {
+    ///     let valid = a.validate(idx).unwrap();
+    ///     b.scope(|b| b[valid]);
+    /// });
+    /// ```
+    pub fn scope<F, R>(&mut self, f: F) -> R
+    where
+        F: for<'brand> FnOnce(BrandedArena<'brand, '_, T, I, G, S>) -> R,
+    {
+        f(BrandedArena {
+            arena: self,
+            _brand: PhantomData,
+        })
+    }
+}
+
+/// A view of an `Arena` within `Arena::scope`, which hands out `Valid` tokens
+/// for its elements.
+pub struct BrandedArena<'brand, 'a, T: 'a, I: 'a, G: 'a, S: 'a> {
+    arena: &'a mut Arena<T, I, G, S>,
+    _brand: Brand<'brand>,
+}
+
+impl<'brand, 'a, T, I, G, S> BrandedArena<'brand, 'a, T, I, G, S>
+where
+    I: ArenaIndex,
+    G: FixedGenerationalIndex,
+    S: GenerationStrategy,
+{
+    /// Check that index `i` refers to an element of the arena, returning a token
+    /// to access it without further checks.
+    pub fn validate(&self, i: Index<T, I, G>) -> Option<Valid<'brand, T>> {
+        self.arena.get(i)?;
+        Some(Valid::new(i.index.to_idx()))
+    }
+
+    /// Check that index `i` refers to an element of the arena, returning a token
+    /// to access it without further checks, or the reason it does not.
+    pub fn try_validate(&self, i: Index<T, I, G>) -> Result<Valid<'brand, T>, AccessError<G>> {
+        self.arena.try_get(i)?;
+        Ok(Valid::new(i.index.to_idx()))
+    }
+
+    /// Insert `value` into the arena, returning a token to access it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is full and its index type cannot address any more
+    /// slots, like `Arena::insert`.
+    pub fn insert(&mut self, value: T) -> Valid<'brand, T> {
+        Valid::new(self.arena.insert(value).index.to_idx())
+    }
+
+    /// Get the index of the element a token refers to.
+    pub fn index(&self, valid: Valid<'brand, T>) -> Index<T, I, G> {
+        match *self.entry(valid) {
+            Entry::Occupied { generation, .. } => Index::new(I::from_idx(valid.slot), generation),
+            _ => vacant(),
+        }
+    }
+
+    /// Get a shared reference to the element a token refers to.
+    pub fn get(&self, valid: Valid<'brand, T>) -> &T {
+        match *self.entry(valid) {
+            Entry::Occupied { ref value, .. } => value,
+            _ => vacant(),
+        }
+    }
+
+    /// Get an exclusive reference to the element a token refers to.
+    pub fn get_mut(&mut self, valid: Valid<'brand, T>) -> &mut T {
+        match *self.entry_mut(valid) {
+            Entry::Occupied { ref mut value, .. } => value,
+            _ => vacant(),
+        }
+    }
+
+    /// Get exclusive references to the elements two tokens refer to, or `None` if
+    /// they refer to the same element.
+    pub fn get2_mut(
+        &mut self,
+        a: Valid<'brand, T>,
+        b: Valid<'brand, T>,
+    ) -> Option<(&mut T, &mut T)> {
+        let (low, high, swapped) = match a.slot.cmp(&b.slot) {
+            Ordering::Less => (a.slot, b.slot, false),
+            Ordering::Greater => (b.slot, a.slot, true),
+            Ordering::Equal => return None,
+        };
+        let (head, tail) = self.arena.items.split_at_mut(high);
+        let low = match head[low] {
+            Entry::Occupied { ref mut value, .. } => value,
+            _ => vacant(),
+        };
+        let high = match tail[0] {
+            Entry::Occupied { ref mut value, .. } => value,
+            _ => vacant(),
+        };
+        Some(if swapped { (high, low) } else { (low, high) })
+    }
+
+    /// Get the number of elements in the arena.
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// Is the arena empty?
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty()
+    }
+
+    /// Get the slot of a token
+    #[cfg(not(feature = "unchecked"))]
+    fn entry(&self, valid: Valid<'brand, T>) -> &Entry<T, I, G> {
+        &self.arena.items[valid.slot]
+    }
+
+    /// Get the slot of a token, without bounds checks
+    #[cfg(feature = "unchecked")]
+    #[allow(unsafe_code)]
+    fn entry(&self, valid: Valid<'brand, T>) -> &Entry<T, I, G> {
+        // SAFETY: tokens are only created for occupied slots of this arena, and
+        // slots are never removed within a scope
+        unsafe { self.arena.items.get_unchecked(valid.slot) }
+    }
+
+    /// Get the slot of a token mutably
+    #[cfg(not(feature = "unchecked"))]
+    fn entry_mut(&mut self, valid: Valid<'brand, T>) -> &mut Entry<T, I, G> {
+        &mut self.arena.items[valid.slot]
+    }
+
+    /// Get the slot of a token mutably, without bounds checks
+    #[cfg(feature = "unchecked")]
+    #[allow(unsafe_code)]
+    fn entry_mut(&mut self, valid: Valid<'brand, T>) -> &mut Entry<T, I, G> {
+        // SAFETY: as for `entry`
+        unsafe { self.arena.items.get_unchecked_mut(valid.slot) }
+    }
+}
+
+/// Handle a token whose slot is not occupied, which cannot happen
+#[cfg(not(feature = "unchecked"))]
+#[inline(always)]
+fn vacant() -> ! {
+    unreachable!("a validated slot cannot be vacated within its scope")
+}
+
+/// Handle a token whose slot is not occupied, which cannot happen
+#[cfg(feature = "unchecked")]
+#[allow(unsafe_code)]
+#[inline(always)]
+fn vacant() -> ! {
+    // SAFETY: tokens are only created for occupied slots of this arena, and
+    // slots are never removed within a scope
+    unsafe { core::hint::unreachable_unchecked() }
+}
+
+impl<'brand, 'a, T, I, G, S> ops::Index<Valid<'brand, T>> for BrandedArena<'brand, 'a, T, I, G, S>
+where
+    I: ArenaIndex,
+    G: FixedGenerationalIndex,
+    S: GenerationStrategy,
+{
+    type Output = T;
+
+    fn index(&self, valid: Valid<'brand, T>) -> &T {
+        self.get(valid)
+    }
+}
+
+impl<'brand, 'a, T, I, G, S> ops::IndexMut<Valid<'brand, T>>
+    for BrandedArena<'brand, 'a, T, I, G, S>
+where
+    I: ArenaIndex,
+    G: FixedGenerationalIndex,
+    S: GenerationStrategy,
+{
+    fn index_mut(&mut self, valid: Valid<'brand, T>) -> &mut T {
+        self.get_mut(valid)
+    }
+}
+
+impl<'brand, 'a, T, I, G, S> fmt::Debug for BrandedArena<'brand, 'a, T, I, G, S>
+where
+    T: fmt::Debug,
+    I: fmt::Debug,
+    G: fmt::Debug,
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BrandedArena")
+            .field("arena", &self.arena)
+            .finish()
+    }
+}
+
+/// A token proving that an element of type `T` is in the arena of the scope
+/// `'brand`, obtained from `BrandedArena::validate`.
+///
+/// Accessing the element through the token checks neither its generation nor,
+/// with the `unchecked` feature, its slot.
+pub struct Valid<'brand, T> {
+    slot: usize,
+    _brand: Brand<'brand>,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<'brand, T> Valid<'brand, T> {
+    /// A token for the occupied slot `slot`
+    fn new(slot: usize) -> Valid<'brand, T> {
+        Valid {
+            slot,
+            _brand: PhantomData,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the slot of the element this token refers to
+    pub fn to_idx(self) -> usize {
+        self.slot
+    }
+}
+
+impl<'brand, T> Copy for Valid<'brand, T> {}
+
+impl<'brand, T> Clone for Valid<'brand, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'brand, T> PartialEq for Valid<'brand, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.slot == other.slot
+    }
+}
+
+impl<'brand, T> Eq for Valid<'brand, T> {}
+
+impl<'brand, T> Hash for Valid<'brand, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.slot.hash(state);
+    }
+}
+
+impl<'brand, T> fmt::Debug for Valid<'brand, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Valid").field("slot", &self.slot).finish()
+    }
+}
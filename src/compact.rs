@@ -0,0 +1,181 @@
+use super::{Arena, ArenaIndex, Entry, GenerationStrategy, GenerationalIndex, Index, Vec};
+use core::slice;
+
+/// The indices of the elements an `Arena::compact` call moved, mapping each old
+/// index to its new index.
+///
+/// Elements which were not moved keep their index, and are not in the table.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::StandardArena;
+///
+/// let mut arena = StandardArena::new();
+/// let a = arena.insert('a');
+/// let b = arena.insert('b');
+/// let c = arena.insert('c');
+/// arena.remove(a);
+///
+/// let remap = arena.compact();
+/// assert_eq!(remap.len(), 1);
+/// let new_c = remap.get(c).unwrap();
+/// assert_eq!(arena[new_c], 'c');
+/// assert_eq!(remap.get(b), None);
+/// assert_eq!(remap.remap(b), b);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IndexRemap<T, I = usize, G = usize> {
+    /// Pairs of old and new indices, in ascending order of old slot
+    #[allow(clippy::type_complexity)]
+    moves: Vec<(Index<T, I, G>, Index<T, I, G>)>,
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> IndexRemap<T, I, G> {
+    /// Get the new index of the element which was at `old`, if it was moved.
+    pub fn get(&self, old: Index<T, I, G>) -> Option<Index<T, I, G>> {
+        let slot = old.index.to_idx();
+        match self
+            .moves
+            .binary_search_by_key(&slot, |(moved, _)| moved.index.to_idx())
+        {
+            Ok(position) if self.moves[position].0.generation == old.generation => {
+                Some(self.moves[position].1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Get the index the element which was at `old` now has: its new index if it was
+    /// moved, or `old` itself otherwise.
+    pub fn remap(&self, old: Index<T, I, G>) -> Index<T, I, G> {
+        self.get(old).unwrap_or(old)
+    }
+
+    /// Iterate over the pairs of old and new indices of the moved elements, in
+    /// ascending order of old index.
+    #[allow(clippy::type_complexity)]
+    pub fn iter(&self) -> slice::Iter<'_, (Index<T, I, G>, Index<T, I, G>)> {
+        self.moves.iter()
+    }
+
+    /// The number of moved elements
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// Whether no element was moved
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> Arena<T, I, G, S> {
+    /// Move every element into the lowest free slots, then drop the free slots left at
+    /// the end of the arena, so that iteration no longer walks over them.
+    ///
+    /// Returns the table of the elements which were moved, which invalidates their old
+    /// indices. Stale indices stay invalid, even if the dropped slots are regrown.
+    /// Retired slots are never reused, so elements are not moved into them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::with_capacity(8);
+    /// let indices: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+    /// for &idx in &indices[..6] {
+    ///     arena.remove(idx);
+    /// }
+    ///
+    /// let remap = arena.compact();
+    /// assert_eq!(arena.capacity(), 2);
+    /// assert!(!arena.contains(indices[7]));
+    /// assert_eq!(arena[remap.remap(indices[7])], 7);
+    /// ```
+    pub fn compact(&mut self) -> IndexRemap<T, I, G> {
+        let mut moves = Vec::new();
+        self.compact_with(|old, new| moves.push((old, new)));
+        // Elements are moved from the highest slot down
+        moves.reverse();
+        IndexRemap { moves }
+    }
+
+    /// Compact the arena like `compact`, calling `moved(old, new)` with the old and new
+    /// index of each moved element instead of building a table.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{StandardArena, StandardIndex};
+    ///
+    /// struct Node {
+    ///     next: Option<StandardIndex<Node>>,
+    /// }
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let garbage = arena.insert(Node { next: None });
+    /// let head = arena.insert(Node { next: None });
+    /// let tail = arena.insert(Node { next: None });
+    /// arena[head].next = Some(tail);
+    /// arena.remove(garbage);
+    ///
+    /// // `tail` moves into the slot `garbage` left
+    /// let mut moves = Vec::new();
+    /// arena.compact_with(|old, new| moves.push((old, new)));
+    /// let remap = |idx| moves.iter().find(|&&(old, _)| old == idx).map_or(idx, |&(_, new)| new);
+    /// let indices: Vec<_> = arena.iter().map(|(idx, _)| idx).collect();
+    /// for idx in indices {
+    ///     let next = arena[idx].next.map(remap);
+    ///     arena[idx].next = next;
+    /// }
+    ///
+    /// let tail = remap(tail);
+    /// assert_eq!(arena[head].next, Some(tail));
+    /// assert_eq!(arena[tail].next, None);
+    /// ```
+    pub fn compact_with<F: FnMut(Index<T, I, G>, Index<T, I, G>)>(&mut self, mut moved: F) {
+        let first_free = self
+            .items
+            .iter()
+            .position(|entry| matches!(entry, Entry::Free { .. }));
+        let last_occupied = self
+            .items
+            .iter()
+            .rposition(|entry| matches!(entry, Entry::Occupied { .. }));
+        let must_move = match (first_free, last_occupied) {
+            (Some(lo), Some(hi)) => lo < hi,
+            _ => false,
+        };
+        // Moved elements get a generation newer than any index to their new slot, or
+        // stay put if the shared generation is exhausted
+        if must_move && self.advance_shared_generation(Self::advance) {
+            let (mut lo, mut hi) = (0, self.items.len());
+            loop {
+                while lo < hi && !matches!(self.items[lo], Entry::Free { .. }) {
+                    lo += 1;
+                }
+                while lo < hi && !matches!(self.items[hi - 1], Entry::Occupied { .. }) {
+                    hi -= 1;
+                }
+                if lo + 1 >= hi {
+                    break;
+                }
+                hi -= 1;
+                let (old, value) = self
+                    .vacate_slot(hi, true, Self::advance)
+                    .expect("slot is occupied");
+                let generation = match self.items[lo] {
+                    Entry::Free { generation, .. } if S::PER_SLOT => generation,
+                    _ => self.generation,
+                };
+                self.items[lo] = Entry::Occupied { generation, value };
+                self.len += 1;
+                moved(old, Index::new(I::from_idx(lo), generation));
+            }
+        }
+        self.truncate_free_tail(0);
+        self.rebuild_free_list();
+    }
+}
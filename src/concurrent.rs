@@ -0,0 +1,295 @@
+use super::slots::{checked, Slot, Slots};
+use super::{ArenaIndex, CapacityError, GenerationalIndex, Index, LockError};
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+/// An arena which can be shared between threads, supporting insertion, removal and
+/// access through a shared reference.
+///
+/// Slots are allocated in segments which never move, each slot behind its own lock,
+/// so threads only contend when they access the same element. Free slots are kept in
+/// several free lists, and each thread starts with a different one. Every slot keeps
+/// its own generation, like in an `Arena` with `PerSlotGeneration`, and a slot whose
+/// generation is exhausted is never reused.
+///
+/// Every access locks the element exclusively, even to read it, so threads reading
+/// the same element wait for each other, and a thread which already holds a guard
+/// to an element deadlocks or panics getting another one. `LockingArena`, whose
+/// elements are behind reader-writer locks, is the arena for workloads which mostly
+/// read.
+///
+/// A thread panicking while it holds a guard poisons the element, which may be
+/// left half updated: the element can then only be removed, and the methods
+/// locking it report `LockError::Poisoned`, or `None`.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use typed_generational_arena::{ConcurrentArena, NonzeroGeneration};
+///
+/// let arena = ConcurrentArena::<u32, usize, NonzeroGeneration<usize>>::new();
+/// thread::scope(|s| {
+///     for t in 0..4 {
+///         let arena = &arena;
+///         s.spawn(move || {
+///             for i in 0..100 {
+///                 let idx = arena.insert(t * 100 + i);
+///                 *arena.get(idx).unwrap() += 1;
+///                 if i % 2 == 0 {
+///                     assert_eq!(arena.remove(idx), Some(t * 100 + i + 1));
+///                     assert!(arena.get(idx).is_none());
+///                 }
+///             }
+///         });
+///     }
+/// });
+/// assert_eq!(arena.len(), 200);
+/// ```
+pub struct ConcurrentArena<T, I = usize, G = usize> {
+    slots: Slots<Mutex<Slot<T, G>>, G>,
+    _phantom: core::marker::PhantomData<fn() -> I>,
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> ConcurrentArena<T, I, G> {
+    /// Constructs a new, empty `ConcurrentArena`, with a free list per available
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::ConcurrentArena;
+    ///
+    /// let arena = ConcurrentArena::<usize>::new();
+    /// # let _ = arena;
+    /// ```
+    pub fn new() -> ConcurrentArena<T, I, G> {
+        let shards = thread::available_parallelism().map_or(4, usize::from);
+        ConcurrentArena::with_free_lists(0, shards)
+    }
+
+    /// Constructs a new, empty `ConcurrentArena` able to hold `n` elements without
+    /// further allocation.
+    pub fn with_capacity(n: usize) -> ConcurrentArena<T, I, G> {
+        let shards = thread::available_parallelism().map_or(4, usize::from);
+        ConcurrentArena::with_free_lists(n, shards)
+    }
+
+    /// Constructs a new, empty `ConcurrentArena` able to hold `n` elements without
+    /// further allocation, with `free_lists` free lists to spread the threads
+    /// inserting and removing elements over.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `free_lists` is zero.
+    pub fn with_free_lists(n: usize, free_lists: usize) -> ConcurrentArena<T, I, G> {
+        assert!(
+            free_lists > 0,
+            "a concurrent arena needs at least one free list"
+        );
+        ConcurrentArena {
+            slots: Slots::new(n, free_lists, I::max_idx().saturating_add(1)),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Get the number of elements in the arena.
+    ///
+    /// Other threads may insert or remove elements at any time, so this is only a
+    /// snapshot.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Is the arena empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of slots handed out so far, whether they hold an element or
+    /// not.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Get the number of slots the index type of the arena can address.
+    pub fn max_capacity(&self) -> usize {
+        self.slots.max_capacity()
+    }
+
+    /// Insert `value` into the arena, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index type cannot address any more slots.
+    pub fn insert(&self, value: T) -> Index<T, I, G> {
+        match self.try_insert(value) {
+            Ok(index) => index,
+            Err((_, error)) => panic!("{}", error),
+        }
+    }
+
+    /// Insert `value` into the arena, unless the index type cannot address any more
+    /// slots, in which case `value` is returned along with the reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{CapacityError, ConcurrentArena};
+    ///
+    /// let arena = ConcurrentArena::<u32, u8, u8>::new();
+    /// for i in 0..256 {
+    ///     assert!(arena.try_insert(i).is_ok());
+    /// }
+    /// assert_eq!(
+    ///     arena.try_insert(256),
+    ///     Err((256, CapacityError::IndexSpaceExhausted { max_capacity: 256 }))
+    /// );
+    /// ```
+    pub fn try_insert(&self, value: T) -> Result<Index<T, I, G>, (T, CapacityError)> {
+        match self.slots.insert(value) {
+            Ok((slot, generation)) => Ok(Index::new(I::from_idx(slot), generation)),
+            Err(value) => Err((
+                value,
+                CapacityError::IndexSpaceExhausted {
+                    max_capacity: self.max_capacity(),
+                },
+            )),
+        }
+    }
+
+    /// Remove the element at index `i` from the arena, returning it, or `None` if
+    /// `i` does not refer to an element of the arena.
+    ///
+    /// A poisoned element is removed too, and returned as the panicking thread
+    /// left it.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread holds a guard to the element.
+    pub fn remove(&self, i: Index<T, I, G>) -> Option<T> {
+        self.slots.remove(i.index.to_idx(), i.generation)
+    }
+
+    /// Is the element at index `i` in the arena, poisoned or not?
+    ///
+    /// Waits while another thread holds a guard to the element.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread holds a guard to the element.
+    pub fn contains(&self, i: Index<T, I, G>) -> bool {
+        match self.slots.lookup(i.index.to_idx(), &i.generation) {
+            Ok(lock) => lock
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .check(i.index.to_idx(), i.generation)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Lock the element at index `i` and get a guard to it, or `None` if `i` does
+    /// not refer to an element of the arena or the element is poisoned.
+    ///
+    /// The guard gives exclusive access to the element, and blocks other threads
+    /// accessing or removing it until it is dropped, even if they only read it.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread already holds a guard to the
+    /// element, even one it only reads through: a guard is never shared, so a
+    /// thread reading an element must drop its guard before getting another one
+    /// for the same index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::ConcurrentArena;
+    ///
+    /// let arena = ConcurrentArena::<String>::new();
+    /// let idx = arena.insert("hello".to_string());
+    /// arena.get(idx).unwrap().push_str(", world");
+    /// assert_eq!(*arena.get(idx).unwrap(), "hello, world");
+    ///
+    /// arena.remove(idx);
+    /// assert!(arena.get(idx).is_none());
+    /// ```
+    pub fn get(&self, i: Index<T, I, G>) -> Option<ConcurrentGuard<'_, T, G>> {
+        self.try_get(i).ok()
+    }
+
+    /// Lock the element at index `i` and get a guard to it, or the reason it
+    /// cannot be locked: `i` does not refer to an element of the arena, or the
+    /// element is poisoned.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread already holds a guard to the
+    /// element.
+    pub fn try_get(&self, i: Index<T, I, G>) -> Result<ConcurrentGuard<'_, T, G>, LockError<G>> {
+        let slot = i.index.to_idx();
+        let lock = self.slots.lookup(slot, &i.generation)?;
+        let entry = checked(lock.lock(), slot, i.generation)?;
+        Ok(ConcurrentGuard { entry })
+    }
+
+    /// Get an exclusive reference to the element at index `i`, without locking
+    /// since the arena is borrowed exclusively, or `None` if `i` does not refer to
+    /// an element of the arena or the element is poisoned.
+    pub fn get_mut(&mut self, i: Index<T, I, G>) -> Option<&mut T> {
+        self.slots.get_mut(i.index.to_idx(), i.generation)
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> Default for ConcurrentArena<T, I, G> {
+    fn default() -> Self {
+        ConcurrentArena::new()
+    }
+}
+
+impl<T, I, G> fmt::Debug for ConcurrentArena<T, I, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ConcurrentArena")
+            .field("len", &self.slots.len())
+            .field("capacity", &self.slots.capacity())
+            .finish()
+    }
+}
+
+/// A locked element of a `ConcurrentArena`, obtained from `ConcurrentArena::get`.
+///
+/// The element cannot be accessed or removed by other threads until the guard is
+/// dropped.
+pub struct ConcurrentGuard<'a, T: 'a, G: 'a> {
+    /// The locked slot, which holds an element
+    entry: MutexGuard<'a, Slot<T, G>>,
+}
+
+impl<'a, T, G> Deref for ConcurrentGuard<'a, T, G> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.entry.value {
+            Some(ref value) => value,
+            None => unreachable!("a locked element cannot be removed"),
+        }
+    }
+}
+
+impl<'a, T, G> DerefMut for ConcurrentGuard<'a, T, G> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self.entry.value {
+            Some(ref mut value) => value,
+            None => unreachable!("a locked element cannot be removed"),
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug, G> fmt::Debug for ConcurrentGuard<'a, T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,158 @@
+use super::{
+    Arena, ArenaIndex, Entry, FixedGenerationalIndex, GenerationStrategy, GenerationalIndex, Index,
+    SharedGeneration,
+};
+use core::mem;
+
+/// A vacant slot in an `Arena`, obtained from `Arena::vacant_entry`.
+///
+/// The index a value will occupy is known before the value is inserted,
+/// which makes it possible to build values which store their own index.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{StandardArena, StandardIndex};
+///
+/// struct Node {
+///     this: StandardIndex<Node>,
+/// }
+///
+/// let mut arena = StandardArena::new();
+/// let entry = arena.vacant_entry();
+/// let this = entry.index();
+/// entry.insert(Node { this });
+/// assert_eq!(arena[this].this, this);
+/// ```
+#[derive(Debug)]
+pub struct VacantEntry<
+    'a,
+    T: 'a,
+    I: 'a + ArenaIndex,
+    G: 'a + FixedGenerationalIndex,
+    S: 'a = SharedGeneration,
+> {
+    arena: &'a mut Arena<T, I, G, S>,
+    index: Index<T, I, G>,
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy>
+    VacantEntry<'a, T, I, G, S>
+{
+    #[inline]
+    pub(crate) fn new(arena: &'a mut Arena<T, I, G, S>, index: Index<T, I, G>) -> Self {
+        VacantEntry { arena, index }
+    }
+
+    /// Get the index a value inserted into this entry will have
+    #[inline]
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Insert `value` into this entry, returning its index
+    #[inline]
+    pub fn insert(self, value: T) -> Index<T, I, G> {
+        self.arena.fill_vacant(self.index, value);
+        self.index
+    }
+
+    /// Insert the value returned by `create`, which is passed the index the
+    /// value will have, into this entry, returning that index
+    #[inline]
+    pub fn insert_with<F: FnOnce(Index<T, I, G>) -> T>(self, create: F) -> Index<T, I, G> {
+        let value = create(self.index);
+        self.insert(value)
+    }
+}
+
+/// An occupied slot in an `Arena`, obtained from `Arena::entry`.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::StandardArena;
+///
+/// let mut arena = StandardArena::new();
+/// let idx = arena.insert(1);
+///
+/// {
+///     let mut entry = arena.entry(idx).unwrap();
+///     *entry.get_mut() += 1;
+///     assert_eq!(*entry.get(), 2);
+/// }
+/// assert_eq!(arena[idx], 2);
+/// ```
+#[derive(Debug)]
+pub struct OccupiedEntry<
+    'a,
+    T: 'a,
+    I: 'a + ArenaIndex,
+    G: 'a + FixedGenerationalIndex,
+    S: 'a = SharedGeneration,
+> {
+    arena: &'a mut Arena<T, I, G, S>,
+    index: Index<T, I, G>,
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy>
+    OccupiedEntry<'a, T, I, G, S>
+{
+    #[inline]
+    pub(crate) fn new(arena: &'a mut Arena<T, I, G, S>, index: Index<T, I, G>) -> Self {
+        OccupiedEntry { arena, index }
+    }
+
+    /// Get the index of this entry
+    #[inline]
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Get a shared reference to the value in this entry
+    #[inline]
+    pub fn get(&self) -> &T {
+        match self.arena.items[self.index.index.to_idx()] {
+            Entry::Occupied { ref value, .. } => value,
+            _ => unreachable!("occupied entry is not occupied"),
+        }
+    }
+
+    /// Get an exclusive reference to the value in this entry
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        match self.arena.items[self.index.index.to_idx()] {
+            Entry::Occupied { ref mut value, .. } => value,
+            _ => unreachable!("occupied entry is not occupied"),
+        }
+    }
+
+    /// Convert this entry into an exclusive reference to its value, bound to
+    /// the lifetime of the arena borrow
+    #[inline]
+    pub fn into_mut(self) -> &'a mut T {
+        match self.arena.items[self.index.index.to_idx()] {
+            Entry::Occupied { ref mut value, .. } => value,
+            _ => unreachable!("occupied entry is not occupied"),
+        }
+    }
+
+    /// Replace the value in this entry, returning the old value. The index of
+    /// the entry is unchanged.
+    #[inline]
+    pub fn replace(&mut self, value: T) -> T {
+        mem::replace(self.get_mut(), value)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy>
+    OccupiedEntry<'a, T, I, G, S>
+{
+    /// Remove this entry from the arena, returning its value
+    #[inline]
+    pub fn remove(self) -> T {
+        self.arena
+            .remove(self.index)
+            .expect("occupied entry is not occupied")
+    }
+}
@@ -0,0 +1,333 @@
+use super::FixedGenerationalIndex;
+use core::fmt::{self, Debug, Display};
+
+/// Why an index does not refer to an element of an `Arena`, returned by
+/// `Arena::try_get`, `Arena::try_get_mut` and `Arena::try_remove`.
+///
+/// `G` is the generation type of the arena.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{AccessError, StandardArena};
+///
+/// let mut arena = StandardArena::new();
+/// let idx = arena.insert(42);
+/// arena.remove(idx);
+///
+/// match arena.try_get(idx) {
+///     Err(AccessError::Stale { slot, .. }) => assert_eq!(slot, 0),
+///     other => panic!("unexpected {:?}", other),
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AccessError<G> {
+    /// The slot of the index is past the end of the arena
+    OutOfBounds {
+        /// The slot of the index
+        slot: usize,
+        /// The number of slots in the arena
+        capacity: usize,
+    },
+    /// The slot of the index holds no element, and the generation of the
+    /// index does not tell why, for example because the slot was retired
+    Vacant {
+        /// The slot of the index
+        slot: usize,
+    },
+    /// The element the index referred to was removed, and its slot is now
+    /// free or holds a newer element
+    Stale {
+        /// The slot of the index
+        slot: usize,
+        /// The generation of the element in the slot, or of the next element
+        /// to occupy it if it is free
+        current: G,
+        /// The generation of the index
+        requested: G,
+    },
+    /// The index has a newer generation than its slot, so it was not issued
+    /// by this arena
+    Newer {
+        /// The slot of the index
+        slot: usize,
+        /// The generation of the element in the slot, or of the next element
+        /// to occupy it if it is free
+        current: G,
+        /// The generation of the index
+        requested: G,
+    },
+    /// The index was issued by another arena, according to the arena id its
+    /// generation carries
+    WrongArena {
+        /// The id of the arena
+        expected: u64,
+        /// The id of the arena which issued the index
+        found: u64,
+    },
+}
+
+impl<G: FixedGenerationalIndex> AccessError<G> {
+    /// Check that an index with generation `index` was issued by the arena whose
+    /// generations look like `arena`
+    pub(crate) fn check_arena(arena: &G, index: &G) -> Result<(), AccessError<G>> {
+        match (arena.arena_id(), index.arena_id()) {
+            (Some(expected), Some(found)) if expected != found => {
+                Err(AccessError::WrongArena { expected, found })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Describe this error without its generations, which may not implement
+    /// `Debug`
+    pub(crate) fn summary(&self) -> &'static str {
+        match self {
+            AccessError::OutOfBounds { .. } => "no element at index: slot is out of bounds",
+            AccessError::Vacant { .. } => "no element at index: slot is vacant",
+            AccessError::Stale { .. } => "no element at index: generation is stale",
+            AccessError::Newer { .. } => {
+                "no element at index: generation is newer than the slot's, \
+                 the index may come from another arena"
+            }
+            AccessError::WrongArena { .. } => {
+                "no element at index: the index comes from another arena"
+            }
+        }
+    }
+}
+
+impl<G: Debug> Display for AccessError<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccessError::OutOfBounds { slot, capacity } => write!(
+                f,
+                "no element at index: slot {} is out of bounds for an arena with {} slots",
+                slot, capacity
+            ),
+            AccessError::Vacant { slot } => {
+                write!(f, "no element at index: slot {} is vacant", slot)
+            }
+            AccessError::Stale {
+                slot,
+                current,
+                requested,
+            } => write!(
+                f,
+                "no element at index: generation {:?} is stale, slot {} holds generation {:?}",
+                requested, slot, current
+            ),
+            AccessError::Newer {
+                slot,
+                current,
+                requested,
+            } => write!(
+                f,
+                "no element at index: generation {:?} is newer than generation {:?} in slot {}, \
+                 the index may come from another arena",
+                requested, current, slot
+            ),
+            AccessError::WrongArena { expected, found } => write!(
+                f,
+                "no element at index: the index comes from arena {}, not arena {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G: Debug> std::error::Error for AccessError<G> {}
+
+/// Why `Arena::get_many_mut` or `Arena::get_many_mut_slice` could not return
+/// references to the elements at the given indices.
+///
+/// `G` is the generation type of the arena.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{GetManyError, StandardArena};
+///
+/// let mut arena = StandardArena::new();
+/// let idx = arena.insert(42);
+///
+/// assert_eq!(
+///     arena.get_many_mut([idx, idx]),
+///     Err(GetManyError::Duplicate { first: 0, second: 1 })
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GetManyError<G> {
+    /// An index does not refer to an element of the arena
+    Invalid {
+        /// The position of the index in the requested indices
+        position: usize,
+        /// Why the index does not refer to an element
+        error: AccessError<G>,
+    },
+    /// Two indices refer to the same element
+    Duplicate {
+        /// The position of the first of the two indices
+        first: usize,
+        /// The position of the second of the two indices
+        second: usize,
+    },
+}
+
+impl<G: Debug> Display for GetManyError<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GetManyError::Invalid { position, error } => {
+                write!(f, "invalid index at position {}: {}", position, error)
+            }
+            GetManyError::Duplicate { first, second } => write!(
+                f,
+                "indices at positions {} and {} refer to the same element",
+                first, second
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G: Debug> std::error::Error for GetManyError<G> {}
+
+/// Why an `Arena` could not grow, returned by `Arena::try_reserve` and
+/// `Arena::try_insert_grow`.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{CapacityError, NanoArena};
+///
+/// let mut arena = NanoArena::<()>::new();
+/// assert_eq!(
+///     arena.try_reserve(1000),
+///     Err(CapacityError::IndexSpaceExhausted { max_capacity: 256 })
+/// );
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CapacityError {
+    /// The index type of the arena cannot address the requested number of slots
+    IndexSpaceExhausted {
+        /// The number of slots the index type can address
+        max_capacity: usize,
+    },
+    /// The allocator could not provide the requested memory
+    AllocationFailed,
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CapacityError::IndexSpaceExhausted { max_capacity } => write!(
+                f,
+                "arena is full: its index type cannot address more than {} slots",
+                max_capacity
+            ),
+            CapacityError::AllocationFailed => write!(f, "arena allocation failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
+
+/// Why `LockingArena::try_get_read`, `LockingArena::try_get_write` or
+/// `ConcurrentArena::try_get` could not lock an element.
+///
+/// `G` is the generation type of the arena.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{LockError, LockingArena};
+///
+/// let arena = LockingArena::<u32>::new();
+/// let idx = arena.insert(42);
+///
+/// let reader = arena.get_read(idx).unwrap();
+/// assert_eq!(arena.try_get_write(idx).unwrap_err(), LockError::WouldBlock);
+/// drop(reader);
+/// assert!(arena.try_get_write(idx).is_ok());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LockError<G> {
+    /// The index does not refer to an element of the arena
+    Invalid(AccessError<G>),
+    /// The element is locked by another guard, and locking it would block
+    WouldBlock,
+    /// A thread panicked while holding a guard to the element, which may have
+    /// left it half updated. Removing the element is the way to discard it.
+    Poisoned,
+}
+
+impl<G> From<AccessError<G>> for LockError<G> {
+    fn from(error: AccessError<G>) -> LockError<G> {
+        LockError::Invalid(error)
+    }
+}
+
+impl<G: Debug> Display for LockError<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LockError::Invalid(error) => Display::fmt(error, f),
+            LockError::WouldBlock => write!(f, "element is locked by another guard"),
+            LockError::Poisoned => write!(
+                f,
+                "element is poisoned: a thread panicked while holding a guard to it"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G: Debug> std::error::Error for LockError<G> {}
+
+/// Why `ArenaTree::append_child` or `ArenaTree::insert_before` could not move a
+/// node.
+///
+/// `G` is the generation type of the tree.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{ArenaTree, TreeError};
+///
+/// let mut tree = ArenaTree::<u32>::new();
+/// let parent = tree.insert(1);
+/// let child = tree.insert(2);
+/// tree.append_child(parent, child).unwrap();
+///
+/// assert_eq!(tree.append_child(child, parent), Err(TreeError::Cycle));
+/// assert_eq!(tree.insert_before(parent, child), Err(TreeError::Root));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TreeError<G> {
+    /// One of the nodes is not in the tree
+    Invalid(AccessError<G>),
+    /// The node to move is the target node or one of its ancestors
+    Cycle,
+    /// The node to insert a sibling next to is a root
+    Root,
+}
+
+impl<G> From<AccessError<G>> for TreeError<G> {
+    fn from(error: AccessError<G>) -> TreeError<G> {
+        TreeError::Invalid(error)
+    }
+}
+
+impl<G: Debug> Display for TreeError<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TreeError::Invalid(error) => Display::fmt(error, f),
+            TreeError::Cycle => write!(f, "node cannot be moved next to or below itself"),
+            TreeError::Root => write!(f, "roots have no siblings"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<G: Debug> std::error::Error for TreeError<G> {}
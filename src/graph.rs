@@ -0,0 +1,676 @@
+//! A directed graph whose nodes and edges are the elements of two `Arena`s.
+
+use super::{
+    AccessError, Arena, ArenaIndex, FixedGenerationalIndex, GenerationalIndex, Index, IndexSet,
+    SecondaryMap, Vec,
+};
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ops::Add;
+use core::slice;
+
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        use std::collections::{BinaryHeap, VecDeque};
+    } else {
+        use alloc::collections::{BinaryHeap, VecDeque};
+    }
+}
+
+/// A node of the graph: its value, and its incident edges in insertion order
+#[derive(Clone, Debug)]
+struct Node<N, E, I, G> {
+    value: N,
+    outgoing: Vec<Index<E, I, G>>,
+    incoming: Vec<Index<E, I, G>>,
+}
+
+/// An edge of the graph: its value, and its endpoints
+#[derive(Clone, Debug)]
+struct Edge<N, E, I, G> {
+    value: E,
+    source: Index<N, I, G>,
+    target: Index<N, I, G>,
+}
+
+/// A directed graph with values of type `N` on its nodes and values of type `E`
+/// on its edges, stored in two `Arena`s.
+///
+/// Nodes are identified by an `Index<N, I, G>` and edges by an `Index<E, I, G>`,
+/// so the two cannot be mixed up unless `N` and `E` are the same type. Like any
+/// other index, they stay valid when other nodes and edges are removed, and are
+/// checked against the generation of their slot, so an index to a removed node or
+/// edge is never confused with a later one in the same slot. Removing a node
+/// removes its incident edges. Several edges may join the same nodes, and an edge
+/// may join a node to itself.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::ArenaGraph;
+///
+/// let mut roads = ArenaGraph::<&str, u32>::new();
+/// let paris = roads.insert_node("Paris");
+/// let lyon = roads.insert_node("Lyon");
+/// let nice = roads.insert_node("Nice");
+/// roads.insert_edge(paris, lyon, 465).unwrap();
+/// roads.insert_edge(lyon, nice, 470).unwrap();
+/// let direct = roads.insert_edge(paris, nice, 950).unwrap();
+///
+/// let distances = roads.dijkstra(paris, |_, &km| km);
+/// assert_eq!(distances[nice], 935);
+///
+/// assert_eq!(roads.remove_node(lyon), Some("Lyon"));
+/// assert_eq!(roads.edge_count(), 1);
+/// assert_eq!(roads.successors(paris).collect::<Vec<_>>(), [nice]);
+/// assert_eq!(roads.target(direct), Some(nice));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArenaGraph<N, E, I = usize, G = usize> {
+    nodes: Arena<Node<N, E, I, G>, I, G>,
+    edges: Arena<Edge<N, E, I, G>, I, G>,
+}
+
+/// Convert an index into one of the arenas of the graph to or from the index of
+/// the value it holds
+#[inline]
+fn cast<A, B, I: ArenaIndex, G: FixedGenerationalIndex>(i: Index<A, I, G>) -> Index<B, I, G> {
+    Index::new(i.index, i.generation)
+}
+
+impl<N, E, I: ArenaIndex, G: GenerationalIndex> ArenaGraph<N, E, I, G> {
+    /// Constructs a new, empty `ArenaGraph`.
+    pub fn new() -> ArenaGraph<N, E, I, G> {
+        ArenaGraph {
+            nodes: Arena::new(),
+            edges: Arena::new(),
+        }
+    }
+
+    /// Constructs a new, empty `ArenaGraph` able to hold `nodes` nodes and `edges`
+    /// edges without further allocation.
+    pub fn with_capacity(nodes: usize, edges: usize) -> ArenaGraph<N, E, I, G> {
+        ArenaGraph {
+            nodes: Arena::with_capacity(nodes),
+            edges: Arena::with_capacity(edges),
+        }
+    }
+
+    /// Get the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Get the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Does the graph have no nodes?
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Insert a node with the value `value`, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena of nodes is full and its index type cannot address
+    /// any more slots, like `Arena::insert`.
+    pub fn insert_node(&mut self, value: N) -> Index<N, I, G> {
+        cast(self.nodes.insert(Node {
+            value,
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }))
+    }
+
+    /// Insert an edge from the node `source` to the node `target` with the value
+    /// `value`, returning its index, or why one of the nodes is not in the graph.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena of edges is full and its index type cannot address
+    /// any more slots, like `Arena::insert`.
+    pub fn insert_edge(
+        &mut self,
+        source: Index<N, I, G>,
+        target: Index<N, I, G>,
+        value: E,
+    ) -> Result<Index<E, I, G>, AccessError<G>> {
+        self.nodes.try_get(cast(source))?;
+        self.nodes.try_get(cast(target))?;
+        let edge = cast(self.edges.insert(Edge {
+            value,
+            source,
+            target,
+        }));
+        self.node_data_mut(source).outgoing.push(edge);
+        self.node_data_mut(target).incoming.push(edge);
+        Ok(edge)
+    }
+
+    /// Is the node `node` in the graph?
+    pub fn contains_node(&self, node: Index<N, I, G>) -> bool {
+        self.nodes.contains(cast(node))
+    }
+
+    /// Is the edge `edge` in the graph?
+    pub fn contains_edge(&self, edge: Index<E, I, G>) -> bool {
+        self.edges.contains(cast(edge))
+    }
+
+    /// Get a shared reference to the value of the node `node`, or `None` if it is
+    /// not in the graph.
+    pub fn node(&self, node: Index<N, I, G>) -> Option<&N> {
+        self.nodes.get(cast(node)).map(|node| &node.value)
+    }
+
+    /// Get an exclusive reference to the value of the node `node`, or `None` if it
+    /// is not in the graph.
+    pub fn node_mut(&mut self, node: Index<N, I, G>) -> Option<&mut N> {
+        self.nodes.get_mut(cast(node)).map(|node| &mut node.value)
+    }
+
+    /// Get a shared reference to the value of the edge `edge`, or `None` if it is
+    /// not in the graph.
+    pub fn edge(&self, edge: Index<E, I, G>) -> Option<&E> {
+        self.edges.get(cast(edge)).map(|edge| &edge.value)
+    }
+
+    /// Get an exclusive reference to the value of the edge `edge`, or `None` if it
+    /// is not in the graph.
+    pub fn edge_mut(&mut self, edge: Index<E, I, G>) -> Option<&mut E> {
+        self.edges.get_mut(cast(edge)).map(|edge| &mut edge.value)
+    }
+
+    /// Get the node the edge `edge` goes from, or `None` if it is not in the
+    /// graph.
+    pub fn source(&self, edge: Index<E, I, G>) -> Option<Index<N, I, G>> {
+        self.edges.get(cast(edge)).map(|edge| edge.source)
+    }
+
+    /// Get the node the edge `edge` goes to, or `None` if it is not in the graph.
+    pub fn target(&self, edge: Index<E, I, G>) -> Option<Index<N, I, G>> {
+        self.edges.get(cast(edge)).map(|edge| edge.target)
+    }
+
+    /// Remove the node `node` and all of its incident edges, returning its value,
+    /// or `None` if it is not in the graph.
+    pub fn remove_node(&mut self, node: Index<N, I, G>) -> Option<N> {
+        let removed = self.nodes.remove(cast(node))?;
+        // The other endpoint of a loop is the removed node itself, and a loop is
+        // both outgoing and incoming, so it is only removed the first time
+        for edge in removed.outgoing {
+            if let Some(edge_data) = self.edges.remove(cast(edge)) {
+                if let Some(target) = self.nodes.get_mut(cast(edge_data.target)) {
+                    unlink(&mut target.incoming, edge);
+                }
+            }
+        }
+        for edge in removed.incoming {
+            if let Some(edge_data) = self.edges.remove(cast(edge)) {
+                if let Some(source) = self.nodes.get_mut(cast(edge_data.source)) {
+                    unlink(&mut source.outgoing, edge);
+                }
+            }
+        }
+        Some(removed.value)
+    }
+
+    /// Remove the edge `edge`, returning its value, or `None` if it is not in the
+    /// graph.
+    pub fn remove_edge(&mut self, edge: Index<E, I, G>) -> Option<E> {
+        let removed = self.edges.remove(cast(edge))?;
+        unlink(&mut self.node_data_mut(removed.source).outgoing, edge);
+        unlink(&mut self.node_data_mut(removed.target).incoming, edge);
+        Some(removed.value)
+    }
+
+    /// Iterate over the edges from the node `node`, in insertion order, which is
+    /// empty if it is not in the graph.
+    pub fn outgoing(&self, node: Index<N, I, G>) -> Edges<'_, E, I, G> {
+        Edges {
+            edges: self.adjacent(node, true).iter(),
+        }
+    }
+
+    /// Iterate over the edges to the node `node`, in insertion order, which is
+    /// empty if it is not in the graph.
+    pub fn incoming(&self, node: Index<N, I, G>) -> Edges<'_, E, I, G> {
+        Edges {
+            edges: self.adjacent(node, false).iter(),
+        }
+    }
+
+    /// Iterate over the targets of the edges from the node `node`, in the order of
+    /// `outgoing`, so a node is seen once per edge to it.
+    pub fn successors(&self, node: Index<N, I, G>) -> Neighbors<'_, N, E, I, G> {
+        Neighbors {
+            graph: self,
+            edges: self.adjacent(node, true).iter(),
+            outgoing: true,
+        }
+    }
+
+    /// Iterate over the sources of the edges to the node `node`, in the order of
+    /// `incoming`, so a node is seen once per edge from it.
+    pub fn predecessors(&self, node: Index<N, I, G>) -> Neighbors<'_, N, E, I, G> {
+        Neighbors {
+            graph: self,
+            edges: self.adjacent(node, false).iter(),
+            outgoing: false,
+        }
+    }
+
+    /// Iterate over the nodes reachable from the node `start` in breadth-first
+    /// order, starting with `start` itself, which is empty if it is not in the
+    /// graph.
+    pub fn bfs(&self, start: Index<N, I, G>) -> Bfs<'_, N, E, I, G> {
+        let mut bfs = Bfs {
+            graph: self,
+            queue: VecDeque::new(),
+            discovered: IndexSet::new(),
+        };
+        if self.contains_node(start) {
+            bfs.discovered.insert(start);
+            bfs.queue.push_back(start);
+        }
+        bfs
+    }
+
+    /// Iterate over the nodes reachable from the node `start` in depth-first
+    /// preorder, starting with `start` itself, which is empty if it is not in the
+    /// graph.
+    ///
+    /// Successors are visited in the order of `outgoing`.
+    pub fn dfs(&self, start: Index<N, I, G>) -> Dfs<'_, N, E, I, G> {
+        let mut dfs = Dfs {
+            graph: self,
+            stack: Vec::new(),
+            visited: IndexSet::new(),
+        };
+        if self.contains_node(start) {
+            dfs.stack.push(start);
+        }
+        dfs
+    }
+
+    /// Sort the nodes of the graph so that every edge goes from a node to a later
+    /// one, or find a node on a cycle if there is no such order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::ArenaGraph;
+    ///
+    /// let mut tasks = ArenaGraph::<&str, ()>::new();
+    /// let test = tasks.insert_node("test");
+    /// let build = tasks.insert_node("build");
+    /// let fetch = tasks.insert_node("fetch");
+    /// tasks.insert_edge(build, test, ()).unwrap();
+    /// tasks.insert_edge(fetch, build, ()).unwrap();
+    /// assert_eq!(tasks.topological_sort(), Ok(vec![fetch, build, test]));
+    ///
+    /// tasks.insert_edge(test, fetch, ()).unwrap();
+    /// let cycle = tasks.topological_sort().unwrap_err();
+    /// assert!([test, build, fetch].contains(&cycle.node()));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn topological_sort(&self) -> Result<Vec<Index<N, I, G>>, Cycle<N, I, G>> {
+        // Kahn's algorithm, counting the edges from nodes which are not sorted yet
+        let mut remaining = SecondaryMap::with_capacity(self.nodes.capacity());
+        let mut ready = Vec::new();
+        for (node, data) in self.nodes.iter() {
+            let node = cast(node);
+            remaining.insert(node, data.incoming.len());
+            if data.incoming.is_empty() {
+                ready.push(node);
+            }
+        }
+        // Pop the sources in slot order
+        ready.reverse();
+
+        let mut sorted = Vec::with_capacity(self.nodes.len());
+        while let Some(node) = ready.pop() {
+            sorted.push(node);
+            for target in self.successors(node) {
+                let count = &mut remaining[target];
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(target);
+                }
+            }
+        }
+        if sorted.len() == self.nodes.len() {
+            return Ok(sorted);
+        }
+
+        // Every unsorted node has an unsorted predecessor, so following them from
+        // any unsorted node ends up going around a cycle
+        let mut node = match remaining.iter().find(|&(_, &count)| count > 0) {
+            Some((node, _)) => node,
+            None => unreachable!("unsorted nodes have unsorted predecessors"),
+        };
+        let mut visited = IndexSet::new();
+        while visited.insert(node) {
+            node = match self.predecessors(node).find(|&p| remaining[p] > 0) {
+                Some(predecessor) => predecessor,
+                None => unreachable!("unsorted nodes have unsorted predecessors"),
+            };
+        }
+        Err(Cycle { node })
+    }
+
+    /// Get the length of the shortest path from the node `start` to every node
+    /// reachable from it, where `cost` gives the length of each edge.
+    ///
+    /// The lengths must not be negative, that is, adding one must never give a
+    /// shorter length. `C::default()` is the length of the empty path.
+    pub fn dijkstra<C, F>(&self, start: Index<N, I, G>, mut cost: F) -> SecondaryMap<N, C, I, G>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+        F: FnMut(Index<E, I, G>, &E) -> C,
+    {
+        let mut distances = SecondaryMap::new();
+        let mut done = IndexSet::new();
+        let mut heap = BinaryHeap::new();
+        if self.contains_node(start) {
+            distances.insert(start, C::default());
+            heap.push(Candidate {
+                distance: C::default(),
+                node: start,
+            });
+        }
+        while let Some(Candidate { distance, node }) = heap.pop() {
+            if !done.insert(node) {
+                continue;
+            }
+            for &edge in &self.node_data(node).outgoing {
+                let data = self.edge_data(edge);
+                let next = distance + cost(edge, &data.value);
+                let shorter = match distances.get(data.target) {
+                    Some(&known) => next < known,
+                    None => true,
+                };
+                if shorter {
+                    distances.insert(data.target, next);
+                    heap.push(Candidate {
+                        distance: next,
+                        node: data.target,
+                    });
+                }
+            }
+        }
+        distances
+    }
+
+    /// Get the outgoing or incoming edges of a node, or none if it is not in the
+    /// graph
+    fn adjacent(&self, node: Index<N, I, G>, outgoing: bool) -> &[Index<E, I, G>] {
+        match self.nodes.get(cast(node)) {
+            Some(node) if outgoing => &node.outgoing,
+            Some(node) => &node.incoming,
+            None => &[],
+        }
+    }
+
+    /// Get a node which is linked to from the graph, and so is in it
+    fn node_data(&self, node: Index<N, I, G>) -> &Node<N, E, I, G> {
+        match self.nodes.get(cast(node)) {
+            Some(node) => node,
+            None => unreachable!("nodes linked from the graph are in it"),
+        }
+    }
+
+    /// Get a node which is linked to from the graph, and so is in it, mutably
+    fn node_data_mut(&mut self, node: Index<N, I, G>) -> &mut Node<N, E, I, G> {
+        match self.nodes.get_mut(cast(node)) {
+            Some(node) => node,
+            None => unreachable!("nodes linked from the graph are in it"),
+        }
+    }
+
+    /// Get an edge which is linked to from the graph, and so is in it
+    fn edge_data(&self, edge: Index<E, I, G>) -> &Edge<N, E, I, G> {
+        match self.edges.get(cast(edge)) {
+            Some(edge) => edge,
+            None => unreachable!("edges linked from the graph are in it"),
+        }
+    }
+}
+
+/// Remove `edge` from a list of incident edges, keeping the order of the others.
+/// Live edges have distinct slots, so comparing slots is enough.
+fn unlink<E, I: ArenaIndex, G>(edges: &mut Vec<Index<E, I, G>>, edge: Index<E, I, G>) {
+    let slot = edge.index.to_idx();
+    if let Some(position) = edges.iter().position(|e| e.index.to_idx() == slot) {
+        edges.remove(position);
+    }
+}
+
+impl<N, E, I: ArenaIndex, G: GenerationalIndex> Default for ArenaGraph<N, E, I, G> {
+    fn default() -> ArenaGraph<N, E, I, G> {
+        ArenaGraph::new()
+    }
+}
+
+/// The error of `ArenaGraph::topological_sort` for a graph with a cycle.
+pub struct Cycle<N, I = usize, G = usize> {
+    node: Index<N, I, G>,
+}
+
+impl<N, I: Copy, G: Copy> Cycle<N, I, G> {
+    /// Get a node on the cycle.
+    pub fn node(&self) -> Index<N, I, G> {
+        self.node
+    }
+}
+
+impl<N, I: Copy, G: Copy> Copy for Cycle<N, I, G> {}
+
+impl<N, I: Copy, G: Copy> Clone for Cycle<N, I, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<N, I: PartialEq, G: PartialEq> PartialEq for Cycle<N, I, G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<N, I: Eq, G: Eq> Eq for Cycle<N, I, G> {}
+
+impl<N, I: fmt::Debug, G: fmt::Debug> fmt::Debug for Cycle<N, I, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Cycle").field("node", &self.node).finish()
+    }
+}
+
+impl<N, I, G> fmt::Display for Cycle<N, I, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "graph has a cycle")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N, I: fmt::Debug, G: fmt::Debug> std::error::Error for Cycle<N, I, G> {}
+
+/// A node to visit in `ArenaGraph::dijkstra`, ordered so that the closest one is
+/// the greatest
+struct Candidate<C, N, I, G> {
+    distance: C,
+    node: Index<N, I, G>,
+}
+
+impl<C: Ord, N, I, G> PartialEq for Candidate<C, N, I, G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl<C: Ord, N, I, G> Eq for Candidate<C, N, I, G> {}
+
+impl<C: Ord, N, I, G> PartialOrd for Candidate<C, N, I, G> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord, N, I, G> Ord for Candidate<C, N, I, G> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.cmp(&self.distance)
+    }
+}
+
+/// An iterator over the outgoing or incoming edges of a node of an
+/// `ArenaGraph`, created by `ArenaGraph::outgoing` or `ArenaGraph::incoming`.
+#[derive(Clone, Debug)]
+pub struct Edges<'a, E: 'a, I: 'a, G: 'a> {
+    edges: slice::Iter<'a, Index<E, I, G>>,
+}
+
+impl<'a, E, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for Edges<'a, E, I, G> {
+    type Item = Index<E, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.edges.next().cloned()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl<'a, E, I: ArenaIndex, G: FixedGenerationalIndex> DoubleEndedIterator for Edges<'a, E, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.edges.next_back().cloned()
+    }
+}
+
+impl<'a, E, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator for Edges<'a, E, I, G> {
+    fn len(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<'a, E, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for Edges<'a, E, I, G> {}
+
+/// An iterator over the successors or predecessors of a node of an
+/// `ArenaGraph`, created by `ArenaGraph::successors` or
+/// `ArenaGraph::predecessors`.
+#[derive(Clone, Debug)]
+pub struct Neighbors<'a, N: 'a, E: 'a, I: 'a, G: 'a> {
+    graph: &'a ArenaGraph<N, E, I, G>,
+    edges: slice::Iter<'a, Index<E, I, G>>,
+    outgoing: bool,
+}
+
+impl<'a, N, E, I, G> Neighbors<'a, N, E, I, G>
+where
+    I: ArenaIndex,
+    G: GenerationalIndex,
+{
+    /// Get the node at the other end of `edge`
+    fn other(&self, edge: &Index<E, I, G>) -> Index<N, I, G> {
+        let edge = self.graph.edge_data(*edge);
+        if self.outgoing {
+            edge.target
+        } else {
+            edge.source
+        }
+    }
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> Iterator for Neighbors<'a, N, E, I, G> {
+    type Item = Index<N, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge = self.edges.next()?;
+        Some(self.other(edge))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.edges.size_hint()
+    }
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> DoubleEndedIterator
+    for Neighbors<'a, N, E, I, G>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let edge = self.edges.next_back()?;
+        Some(self.other(edge))
+    }
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> ExactSizeIterator
+    for Neighbors<'a, N, E, I, G>
+{
+    fn len(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Neighbors<'a, N, E, I, G> {}
+
+/// A breadth-first iterator over the nodes of an `ArenaGraph` reachable from a
+/// node, created by `ArenaGraph::bfs`.
+#[derive(Clone, Debug)]
+pub struct Bfs<'a, N: 'a, E: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    graph: &'a ArenaGraph<N, E, I, G>,
+    queue: VecDeque<Index<N, I, G>>,
+    discovered: IndexSet<N, I, G>,
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> Iterator for Bfs<'a, N, E, I, G> {
+    type Item = Index<N, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        for successor in self.graph.successors(node) {
+            if self.discovered.insert(successor) {
+                self.queue.push_back(successor);
+            }
+        }
+        Some(node)
+    }
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Bfs<'a, N, E, I, G> {}
+
+/// A depth-first iterator over the nodes of an `ArenaGraph` reachable from a
+/// node, in preorder, created by `ArenaGraph::dfs`.
+#[derive(Clone, Debug)]
+pub struct Dfs<'a, N: 'a, E: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    graph: &'a ArenaGraph<N, E, I, G>,
+    stack: Vec<Index<N, I, G>>,
+    visited: IndexSet<N, I, G>,
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> Iterator for Dfs<'a, N, E, I, G> {
+    type Item = Index<N, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.visited.insert(node) {
+                continue;
+            }
+            // Pushed in reverse, so that the first successor is visited first
+            let visited = &self.visited;
+            self.stack.extend(
+                self.graph
+                    .successors(node)
+                    .rev()
+                    .filter(|&successor| !visited.contains(successor)),
+            );
+            return Some(node);
+        }
+        None
+    }
+}
+
+impl<'a, N, E, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Dfs<'a, N, E, I, G> {}
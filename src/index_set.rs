@@ -0,0 +1,522 @@
+//! A set of the indices of an `Arena`, stored as a bitset over its slots.
+
+use super::{Arena, ArenaIndex, Entry, FixedGenerationalIndex, Index, Vec};
+use core::fmt;
+use core::iter::{Extend, FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::mem;
+
+const WORD_BITS: usize = 64;
+
+/// A set of the indices of an `Arena`, stored as one bit per arena slot along
+/// with the generation of the index in each slot.
+///
+/// A slot holds at most one index, so inserting an index replaces any index
+/// to another generation of its slot, and a stale index is never found in
+/// the set once a newer index to its slot was inserted. Iteration is in slot
+/// order, and set operations work a word of slots at a time.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{IndexSet, StandardArena};
+///
+/// let mut arena = StandardArena::new();
+/// let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+///
+/// let even: IndexSet<_, _, _> = indices.iter().cloned().step_by(2).collect();
+/// let small: IndexSet<_, _, _> = indices[..3].iter().cloned().collect();
+///
+/// let both: Vec<_> = even.intersection(&small).iter().collect();
+/// assert_eq!(both, vec![indices[0], indices[2]]);
+///
+/// for (_, value) in arena.iter_mut_set(&even) {
+///     *value *= 10;
+/// }
+/// let values: Vec<_> = arena.iter_set(&even).map(|(_, value)| *value).collect();
+/// assert_eq!(values, vec![0, 20, 40]);
+/// ```
+pub struct IndexSet<T, I = usize, G = usize> {
+    /// One bit per slot, set if the slot holds an index
+    bits: Vec<u64>,
+    /// The generation of the index in each slot, for every slot up to the
+    /// highest one ever inserted
+    generations: Vec<G>,
+    len: usize,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> IndexSet<T, I, G> {
+    /// Constructs a new, empty `IndexSet`.
+    pub fn new() -> IndexSet<T, I, G> {
+        IndexSet::with_capacity(0)
+    }
+
+    /// Constructs a new, empty `IndexSet` able to hold indices to the first
+    /// `n` slots of an arena without further allocation.
+    pub fn with_capacity(n: usize) -> IndexSet<T, I, G> {
+        IndexSet {
+            bits: Vec::with_capacity(n.div_ceil(WORD_BITS)),
+            generations: Vec::with_capacity(n),
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the number of indices in this set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is this set empty?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove every index from this set, keeping its allocation.
+    pub fn clear(&mut self) {
+        self.bits.clear();
+        self.generations.clear();
+        self.len = 0;
+    }
+
+    /// Is the index `i` in this set?
+    pub fn contains(&self, i: Index<T, I, G>) -> bool {
+        let slot = i.index.to_idx();
+        self.slot_generation(slot) == Some(i.generation)
+    }
+
+    /// Insert the index `i` into this set, returning whether it was not
+    /// already in the set.
+    ///
+    /// An index to another generation of the same slot is replaced.
+    /// Generations may wrap around, so the set cannot tell a stale index from
+    /// a live one: only insert indices which are in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{IndexSet, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let mut set = IndexSet::new();
+    /// let old = arena.insert(());
+    /// arena.remove(old);
+    /// let new = arena.insert(());
+    ///
+    /// assert!(set.insert(new));
+    /// assert!(!set.insert(new));
+    /// assert!(!set.contains(old));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn insert(&mut self, i: Index<T, I, G>) -> bool {
+        let slot = i.index.to_idx();
+        match self.slot_generation(slot) {
+            Some(generation) if generation == i.generation => false,
+            Some(_) => {
+                self.generations[slot] = i.generation;
+                true
+            }
+            None => {
+                self.fill_slot(slot, i.generation);
+                true
+            }
+        }
+    }
+
+    /// Remove the index `i` from this set, returning whether it was in the
+    /// set.
+    pub fn remove(&mut self, i: Index<T, I, G>) -> bool {
+        if !self.contains(i) {
+            return false;
+        }
+        let slot = i.index.to_idx();
+        self.bits[slot / WORD_BITS] &= !(1 << (slot % WORD_BITS));
+        self.len -= 1;
+        true
+    }
+
+    /// Iterate over the indices in this set, in slot order.
+    pub fn iter(&self) -> Iter<'_, T, I, G> {
+        Iter {
+            set: self,
+            word: 0,
+            bits: self.bits.first().cloned().unwrap_or(0),
+            len: self.len,
+        }
+    }
+
+    /// Get the set of the indices in this set or in `other`.
+    ///
+    /// If both sets hold indices to different generations of the same slot,
+    /// only the index in `other` is kept, as if it were inserted into this
+    /// set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{IndexSet, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let a = arena.insert('a');
+    /// let b = arena.insert('b');
+    /// let c = arena.insert('c');
+    /// let left: IndexSet<_, _, _> = vec![a, b].into_iter().collect();
+    /// let right: IndexSet<_, _, _> = vec![b, c].into_iter().collect();
+    ///
+    /// let union: Vec<_> = left.union(&right).iter().collect();
+    /// assert_eq!(union, vec![a, b, c]);
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(
+            other,
+            |a, b| a | b,
+            |a, b| match (a, b) {
+                (a, None) => a,
+                (_, b) => b,
+            },
+        )
+    }
+
+    /// Get the set of the indices in both this set and `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{IndexSet, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let a = arena.insert('a');
+    /// let b = arena.insert('b');
+    /// let c = arena.insert('c');
+    /// let left: IndexSet<_, _, _> = vec![a, b].into_iter().collect();
+    /// let right: IndexSet<_, _, _> = vec![b, c].into_iter().collect();
+    ///
+    /// let intersection: Vec<_> = left.intersection(&right).iter().collect();
+    /// assert_eq!(intersection, vec![b]);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(
+            other,
+            |a, b| a & b,
+            |a, b| match (a, b) {
+                (Some(a), Some(b)) if a == b => Some(a),
+                _ => None,
+            },
+        )
+    }
+
+    /// Get the set of the indices in this set but not in `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{IndexSet, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let a = arena.insert('a');
+    /// let b = arena.insert('b');
+    /// let c = arena.insert('c');
+    /// let left: IndexSet<_, _, _> = vec![a, b].into_iter().collect();
+    /// let right: IndexSet<_, _, _> = vec![b, c].into_iter().collect();
+    ///
+    /// let difference: Vec<_> = left.difference(&right).iter().collect();
+    /// assert_eq!(difference, vec![a]);
+    /// ```
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(
+            other,
+            |a, _| a,
+            |a, b| match (a, b) {
+                (Some(a), Some(b)) if a == b => None,
+                (a, _) => a,
+            },
+        )
+    }
+
+    /// Build the set holding, for each slot whose bit is set in
+    /// `word(self_word, other_word)`, the generation `pick` chooses from the
+    /// generations of the slot in this set and `other`, if any
+    fn combine<W, P>(&self, other: &Self, word: W, pick: P) -> Self
+    where
+        W: Fn(u64, u64) -> u64,
+        P: Fn(Option<G>, Option<G>) -> Option<G>,
+    {
+        let words = self.bits.len().max(other.bits.len());
+        let mut set = IndexSet::with_capacity(words * WORD_BITS);
+        for w in 0..words {
+            let ours = self.bits.get(w).cloned().unwrap_or(0);
+            let theirs = other.bits.get(w).cloned().unwrap_or(0);
+            let mut candidates = word(ours, theirs);
+            while candidates != 0 {
+                let slot = w * WORD_BITS + candidates.trailing_zeros() as usize;
+                candidates &= candidates - 1;
+                if let Some(generation) =
+                    pick(self.slot_generation(slot), other.slot_generation(slot))
+                {
+                    set.fill_slot(slot, generation);
+                }
+            }
+        }
+        set
+    }
+
+    /// Get the generation of the index in `slot`, if any
+    fn slot_generation(&self, slot: usize) -> Option<G> {
+        match self.bits.get(slot / WORD_BITS) {
+            Some(word) if word & (1 << (slot % WORD_BITS)) != 0 => Some(self.generations[slot]),
+            _ => None,
+        }
+    }
+
+    /// Put an index to the empty `slot` with the given generation into this set
+    fn fill_slot(&mut self, slot: usize, generation: G) {
+        if slot >= self.generations.len() {
+            self.generations.resize(slot + 1, G::first_generation());
+            self.bits.resize(slot / WORD_BITS + 1, 0);
+        }
+        self.bits[slot / WORD_BITS] |= 1 << (slot % WORD_BITS);
+        self.generations[slot] = generation;
+        self.len += 1;
+    }
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Default for IndexSet<T, I, G> {
+    fn default() -> Self {
+        IndexSet::new()
+    }
+}
+
+impl<T, I, G: Clone> Clone for IndexSet<T, I, G> {
+    fn clone(&self) -> Self {
+        IndexSet {
+            bits: self.bits.clone(),
+            generations: self.generations.clone(),
+            len: self.len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, I: ArenaIndex + fmt::Debug, G: FixedGenerationalIndex + fmt::Debug> fmt::Debug
+    for IndexSet<T, I, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> PartialEq for IndexSet<T, I, G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.index.to_idx() == b.index.to_idx() && a.generation == b.generation)
+    }
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Eq for IndexSet<T, I, G> {}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Extend<Index<T, I, G>> for IndexSet<T, I, G> {
+    fn extend<It: IntoIterator<Item = Index<T, I, G>>>(&mut self, iter: It) {
+        for i in iter {
+            self.insert(i);
+        }
+    }
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> FromIterator<Index<T, I, G>>
+    for IndexSet<T, I, G>
+{
+    fn from_iter<It: IntoIterator<Item = Index<T, I, G>>>(iter: It) -> Self {
+        let mut set = IndexSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator for &'a IndexSet<T, I, G> {
+    type Item = Index<T, I, G>;
+    type IntoIter = Iter<'a, T, I, G>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the indices in an `IndexSet`, obtained from
+/// `IndexSet::iter`.
+#[derive(Debug)]
+pub struct Iter<'a, T: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    set: &'a IndexSet<T, I, G>,
+    /// The position of the current word in the bitset
+    word: usize,
+    /// The bits of the current word which were not visited yet
+    bits: u64,
+    len: usize,
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for Iter<'a, T, I, G> {
+    type Item = Index<T, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bits == 0 {
+            self.word += 1;
+            self.bits = *self.set.bits.get(self.word)?;
+        }
+        let slot = self.word * WORD_BITS + self.bits.trailing_zeros() as usize;
+        self.bits &= self.bits - 1;
+        self.len -= 1;
+        Some(Index::new(I::from_idx(slot), self.set.generations[slot]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator for Iter<'a, T, I, G> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for Iter<'a, T, I, G> {}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S> Arena<T, I, G, S> {
+    /// Iterate over shared references to the elements of the arena whose
+    /// indices are in `set`, in slot order.
+    ///
+    /// Indices in `set` which do not refer to an element of the arena are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{IndexSet, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let a = arena.insert('a');
+    /// let b = arena.insert('b');
+    /// arena.insert('c');
+    ///
+    /// let mut selected = IndexSet::new();
+    /// selected.insert(b);
+    /// selected.insert(a);
+    /// arena.remove(b);
+    ///
+    /// let values: Vec<_> = arena.iter_set(&selected).map(|(_, value)| *value).collect();
+    /// assert_eq!(values, vec!['a']);
+    /// ```
+    pub fn iter_set<'a>(&'a self, set: &'a IndexSet<T, I, G>) -> IterSet<'a, T, I, G> {
+        IterSet {
+            items: &self.items,
+            indices: set.iter(),
+        }
+    }
+
+    /// Iterate over exclusive references to the elements of the arena whose
+    /// indices are in `set`, in slot order.
+    ///
+    /// Indices in `set` which do not refer to an element of the arena are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{IndexSet, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let dirty: IndexSet<_, _, _> = (0..10)
+    ///     .map(|i| arena.insert(i))
+    ///     .filter(|idx| idx.to_idx() % 3 == 0)
+    ///     .collect();
+    ///
+    /// for (_, value) in arena.iter_mut_set(&dirty) {
+    ///     *value = 0;
+    /// }
+    /// assert_eq!(arena.iter().filter(|&(_, value)| *value == 0).count(), 4);
+    /// ```
+    pub fn iter_mut_set<'a>(&'a mut self, set: &'a IndexSet<T, I, G>) -> IterMutSet<'a, T, I, G> {
+        IterMutSet {
+            items: &mut self.items,
+            offset: 0,
+            indices: set.iter(),
+        }
+    }
+}
+
+/// An iterator over shared references to the elements of an arena whose
+/// indices are in an `IndexSet`, obtained from `Arena::iter_set`.
+#[derive(Debug)]
+pub struct IterSet<'a, T: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    items: &'a [Entry<T, I, G>],
+    indices: Iter<'a, T, I, G>,
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for IterSet<'a, T, I, G> {
+    type Item = (Index<T, I, G>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for i in &mut self.indices {
+            match self.items.get(i.index.to_idx()) {
+                Some(Entry::Occupied { generation, value }) if *generation == i.generation => {
+                    return Some((i, value))
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.indices.size_hint().1)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for IterSet<'a, T, I, G> {}
+
+/// An iterator over exclusive references to the elements of an arena whose
+/// indices are in an `IndexSet`, obtained from `Arena::iter_mut_set`.
+#[derive(Debug)]
+pub struct IterMutSet<'a, T: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    /// The slots of the arena after the last one visited
+    items: &'a mut [Entry<T, I, G>],
+    /// The slot `items` starts at
+    offset: usize,
+    indices: Iter<'a, T, I, G>,
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for IterMutSet<'a, T, I, G> {
+    type Item = (Index<T, I, G>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for i in &mut self.indices {
+            let slot = i.index.to_idx();
+            let items = mem::take(&mut self.items);
+            if slot - self.offset >= items.len() {
+                break;
+            }
+            let (entry, rest) = items[slot - self.offset..]
+                .split_first_mut()
+                .expect("slot is in bounds");
+            self.items = rest;
+            self.offset = slot + 1;
+            match entry {
+                Entry::Occupied { generation, value } if *generation == i.generation => {
+                    return Some((i, value))
+                }
+                _ => {}
+            }
+        }
+        self.items = &mut [];
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.indices.size_hint().1)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for IterMutSet<'a, T, I, G> {}
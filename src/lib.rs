@@ -61,7 +61,7 @@ then the operation fails.
 
 ## Features
 
-* Zero `unsafe`
+* Zero `unsafe`, unless the opt-in `unchecked` feature is enabled
 * Well tested, including quickchecks
 * `no_std` compatibility
 * All the trait implementations you expect: `IntoIterator`, `FromIterator`,
@@ -140,10 +140,34 @@ To enable serialization/deserialization support, enable the "serde" feature.
 ```toml
 [dependencies]
 typed-generational-arena = { version = "0.2", features = ["serde"] }
+```
+
+### Parallel iteration with [`rayon`](https://crates.io/crates/rayon)
+
+To iterate over arenas in parallel, enable the "rayon" feature. Arenas then get
+`par_iter`, `par_iter_mut`, `par_values_mut` and `par_retain`, and implement
+rayon's `ParallelExtend` and `FromParallelIterator`.
+
+```toml
+[dependencies]
+typed-generational-arena = { version = "0.2", features = ["rayon"] }
+```
+
+### Deriving `Trace`
+
+`Arena::collect_garbage` removes the elements which cannot be reached from a set
+of roots through the indices the elements hold, which they report by implementing
+`Trace`. To derive `Trace` for structs and enums, enable the "derive" feature.
+
+```toml
+[dependencies]
+typed-generational-arena = { version = "0.2", features = ["derive"] }
 ```
  */
 
-#![forbid(unsafe_code, missing_docs, missing_debug_implementations)]
+#![forbid(missing_docs, missing_debug_implementations)]
+#![cfg_attr(not(feature = "unchecked"), forbid(unsafe_code))]
+#![cfg_attr(feature = "unchecked", deny(unsafe_code))]
 #![no_std]
 #![cfg_attr(not(feature = "std"), feature(alloc))]
 
@@ -153,13 +177,19 @@ extern crate num_traits;
 extern crate cfg_if;
 #[cfg(feature = "serde")]
 extern crate serde;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "derive")]
+extern crate typed_generational_arena_derive;
 
 cfg_if! {
     if #[cfg(feature = "std")] {
         extern crate std;
+        use std::collections::BinaryHeap;
         use std::vec::{self, Vec};
     } else {
         extern crate alloc;
+        use alloc::collections::BinaryHeap;
         use alloc::vec::{self, Vec};
     }
 }
@@ -174,28 +204,197 @@ use core::ops::{self, Add, AddAssign};
 use core::slice;
 
 use nonzero_ext::{NonZero, NonZeroAble};
-use num_traits::{FromPrimitive, One, ToPrimitive, WrappingAdd, Zero};
+use num_traits::{Bounded, FromPrimitive, One, ToPrimitive, WrappingAdd, Zero};
 
 #[cfg(feature = "serde")]
 mod serde_impl;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+#[cfg(feature = "rayon")]
+pub use rayon_impl::{ParIter, ParIterMut, ParValuesMut};
+
 mod presets;
 pub use presets::*;
 
+mod entry;
+pub use entry::{OccupiedEntry, VacantEntry};
+
+mod arena_id;
+pub use arena_id::{ArenaId, IdGeneration};
+
+mod error;
+pub use error::{AccessError, CapacityError, GetManyError, LockError, TreeError};
+
+mod compact;
+pub use compact::IndexRemap;
+
+mod branded;
+pub use branded::{BrandedArena, Valid};
+
+mod trace;
+pub use trace::{Trace, Tracer};
+#[cfg(feature = "derive")]
+pub use typed_generational_arena_derive::Trace;
+
+pub mod secondary;
+pub use secondary::SecondaryMap;
+
+pub mod index_set;
+pub use index_set::IndexSet;
+
+pub mod tree;
+pub use tree::{ArenaTree, NodeId};
+
+pub mod graph;
+pub use graph::{ArenaGraph, Cycle};
+
+pub mod ordered;
+pub use ordered::OrderedArena;
+
+#[cfg(feature = "std")]
+mod slots;
+
+#[cfg(feature = "std")]
+mod concurrent;
+#[cfg(feature = "std")]
+pub use concurrent::{ConcurrentArena, ConcurrentGuard};
+
+#[cfg(feature = "std")]
+mod locking;
+#[cfg(feature = "std")]
+pub use locking::{LockingArena, LockingReadGuard, LockingWriteGuard};
+
+#[cfg(feature = "std")]
+mod rc_arena;
+#[cfg(feature = "std")]
+pub use rc_arena::{RcArena, StrongHandle, WeakHandle};
+
+#[cfg(feature = "std")]
+pub mod sparse_secondary;
+#[cfg(feature = "std")]
+pub use sparse_secondary::SparseSecondaryMap;
+
+#[cfg(feature = "std")]
+pub mod lru;
+#[cfg(feature = "std")]
+pub use lru::{LruArena, LruHandle};
+
 /// A type which can be used as the index of a generation which may not be able to be incremented
 pub trait FixedGenerationalIndex: Copy + Eq {
     /// Get an object representing the first possible generation
     fn first_generation() -> Self;
     /// Compare this generation with another.
     fn generation_lt(&self, other: &Self) -> bool;
+    /// Get the first generation of a newly constructed arena.
+    ///
+    /// Generation types which carry the id of the arena which issued them assign a
+    /// fresh id here. The default implementation is `first_generation`.
+    #[inline(always)]
+    fn arena_first_generation() -> Self {
+        Self::first_generation()
+    }
+    /// Get the first generation of the arena this generation belongs to, which an
+    /// arena wraps around to when generations are exhausted.
+    ///
+    /// The default implementation is `first_generation`.
+    #[inline(always)]
+    fn reset_generation(&self) -> Self {
+        Self::first_generation()
+    }
+    /// Get the id of the arena which issued this generation, for generation types
+    /// which carry one. The default implementation returns `None`.
+    #[inline(always)]
+    fn arena_id(&self) -> Option<u64> {
+        None
+    }
+    /// Advance this generation as `policy` dictates when `Arena::clear` or `Arena::drain`
+    /// frees its slot, returning `false` if it is exhausted and the slot should be retired.
+    ///
+    /// The default implementation leaves the generation unchanged and returns `true`, so
+    /// indices from before the call may refer to elements inserted afterwards. Types which
+    /// implement `GenerationalIndex` should implement this as `policy.advance(self)`.
+    #[inline(always)]
+    fn advance_generation(&mut self, _policy: ExhaustionPolicy) -> bool {
+        true
+    }
 }
 
 /// A type which can be used as the index of a generation, which can be incremented
 pub trait GenerationalIndex: FixedGenerationalIndex {
     /// Increment the generation of this object. May wrap or panic on overflow depending on type.
     fn increment_generation(&mut self);
+    /// Increment the generation of this object, unless it is the last generation this type
+    /// can represent without wrapping. Returns whether the generation was incremented.
+    ///
+    /// The default implementation assumes generations are never exhausted, and always
+    /// increments.
+    #[inline(always)]
+    fn checked_increment_generation(&mut self) -> bool {
+        self.increment_generation();
+        true
+    }
+}
+
+/// What an arena does when the generation of a removed element cannot be incremented
+/// any further.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExhaustionPolicy {
+    /// Leave it to `GenerationalIndex::increment_generation`, which may panic or wrap
+    /// depending on the generation type. This is the default.
+    #[default]
+    Increment,
+    /// Panic.
+    Panic,
+    /// Wrap around to the first generation. Stale indices may then, with low
+    /// probability, alias newer elements.
+    Wrap,
+    /// Permanently retire the slot, so that no stale index can ever alias a newer
+    /// element. Retired slots are never reused, and still count towards capacity.
+    Retire,
+}
+
+impl ExhaustionPolicy {
+    /// Advance `generation` according to this policy, returning `false` if it is exhausted
+    /// and the slot it belongs to should be retired.
+    #[inline]
+    pub fn advance<G: GenerationalIndex>(self, generation: &mut G) -> bool {
+        match self {
+            ExhaustionPolicy::Increment => generation.increment_generation(),
+            ExhaustionPolicy::Panic => {
+                if !generation.checked_increment_generation() {
+                    panic!("generation counter exhausted")
+                }
+            }
+            ExhaustionPolicy::Wrap => {
+                if !generation.checked_increment_generation() {
+                    *generation = generation.reset_generation()
+                }
+            }
+            ExhaustionPolicy::Retire => return generation.checked_increment_generation(),
+        }
+        true
+    }
+}
+
+/// Which free slot an arena reuses first when inserting.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReusePolicy {
+    /// Reuse the most recently freed slot. This is the default, and the cheapest.
+    #[default]
+    Lifo,
+    /// Reuse the least recently freed slot, maximizing the time before a slot is
+    /// reused. This makes stale indices less likely to alias newer elements when
+    /// generations wrap around.
+    Fifo,
+    /// Reuse the free slot with the lowest index, keeping elements packed at the
+    /// front of the arena for faster iteration. Removing an element takes
+    /// logarithmic time in the number of free slots.
+    LowestFirst,
 }
 
 /// A generation counter which is always nonzero. Useful for size optimizations on Option<Index>
@@ -231,6 +430,7 @@ where
         + Eq
         + From<<<T as NonZeroAble>::NonZero as NonZero>::Primitive>,
     T::NonZero: PartialOrd + Eq + Copy,
+    T: Bounded,
 {
     #[inline(always)]
     fn first_generation() -> Self {
@@ -242,6 +442,10 @@ where
     fn generation_lt(&self, other: &Self) -> bool {
         self.gen < other.gen
     }
+    #[inline(always)]
+    fn advance_generation(&mut self, policy: ExhaustionPolicy) -> bool {
+        policy.advance(self)
+    }
 }
 
 impl<T> GenerationalIndex for NonzeroGeneration<T>
@@ -253,11 +457,21 @@ where
         + Eq
         + From<<<T as NonZeroAble>::NonZero as NonZero>::Primitive>,
     T::NonZero: PartialOrd + Eq + Copy,
+    T: Bounded,
 {
     #[inline(always)]
     fn increment_generation(&mut self) {
         self.gen = (T::from(self.gen.get()) + T::one()).as_nonzero().unwrap()
     }
+    #[inline(always)]
+    fn checked_increment_generation(&mut self) -> bool {
+        if T::from(self.gen.get()) == T::max_value() {
+            false
+        } else {
+            self.increment_generation();
+            true
+        }
+    }
 }
 
 /// A wrapping generation counter which is always nonzero.
@@ -289,6 +503,10 @@ where
     fn generation_lt(&self, other: &Self) -> bool {
         self.gen < other.gen
     }
+    #[inline(always)]
+    fn advance_generation(&mut self, policy: ExhaustionPolicy) -> bool {
+        policy.advance(self)
+    }
 }
 
 impl<T> GenerationalIndex for NonzeroWrapGeneration<T>
@@ -311,9 +529,19 @@ where
             new.as_nonzero().unwrap()
         }
     }
+    #[inline(always)]
+    fn checked_increment_generation(&mut self) -> bool {
+        let new = T::from(self.gen.get()).wrapping_add(&T::one());
+        if T::zero() == new {
+            false
+        } else {
+            self.gen = new.as_nonzero().unwrap();
+            true
+        }
+    }
 }
 
-impl<T: Eq + One + AddAssign + Default + PartialOrd + Copy> FixedGenerationalIndex for T {
+impl<T: Eq + One + AddAssign + Default + PartialOrd + Copy + Bounded> FixedGenerationalIndex for T {
     #[inline(always)]
     fn first_generation() -> Self {
         Default::default()
@@ -322,13 +550,26 @@ impl<T: Eq + One + AddAssign + Default + PartialOrd + Copy> FixedGenerationalInd
     fn generation_lt(&self, other: &Self) -> bool {
         self.lt(other)
     }
+    #[inline(always)]
+    fn advance_generation(&mut self, policy: ExhaustionPolicy) -> bool {
+        policy.advance(self)
+    }
 }
 
-impl<T: Eq + One + AddAssign + Default + PartialOrd + Copy> GenerationalIndex for T {
+impl<T: Eq + One + AddAssign + Default + PartialOrd + Copy + Bounded> GenerationalIndex for T {
     #[inline(always)]
     fn increment_generation(&mut self) {
         *self += Self::one()
     }
+    #[inline(always)]
+    fn checked_increment_generation(&mut self) -> bool {
+        if *self == Self::max_value() {
+            false
+        } else {
+            self.increment_generation();
+            true
+        }
+    }
 }
 
 /// If this is used as a generational index, then the arena ignores generation
@@ -345,6 +586,10 @@ impl FixedGenerationalIndex for IgnoreGeneration {
     fn generation_lt(&self, _other: &Self) -> bool {
         false
     }
+    #[inline(always)]
+    fn advance_generation(&mut self, policy: ExhaustionPolicy) -> bool {
+        policy.advance(self)
+    }
 }
 
 impl GenerationalIndex for IgnoreGeneration {
@@ -375,12 +620,45 @@ impl FixedGenerationalIndex for DisableRemoval {
 
 impl IgnoredGeneration for DisableRemoval {}
 
+/// A strategy for how an arena assigns generations to its slots
+pub trait GenerationStrategy {
+    /// Whether every slot keeps its own generation counter, rather than the
+    /// whole arena sharing a single one
+    const PER_SLOT: bool;
+}
+
+/// The arena keeps a single generation counter, which is incremented on every removal.
+/// This is the default strategy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Default)]
+pub struct SharedGeneration;
+
+impl GenerationStrategy for SharedGeneration {
+    const PER_SLOT: bool = false;
+}
+
+/// Every slot of the arena keeps its own generation counter, which is only incremented
+/// when that slot is freed. Since a generation is only used up when a particular slot
+/// is reused, small generation types last far longer than with `SharedGeneration`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Default)]
+pub struct PerSlotGeneration;
+
+impl GenerationStrategy for PerSlotGeneration {
+    const PER_SLOT: bool = true;
+}
+
 /// A type which can be used as an index to an arena
 pub trait ArenaIndex: Copy {
     /// Create an arena index from a usize
     fn from_idx(idx: usize) -> Self;
     /// Transform an arena index into a usize
     fn to_idx(self) -> usize;
+    /// The greatest usize this type can represent as an arena index.
+    ///
+    /// The default implementation assumes every usize can be represented.
+    #[inline(always)]
+    fn max_idx() -> usize {
+        usize::MAX
+    }
 }
 impl<T: ToPrimitive + FromPrimitive + Copy> ArenaIndex for T {
     #[inline(always)]
@@ -391,6 +669,29 @@ impl<T: ToPrimitive + FromPrimitive + Copy> ArenaIndex for T {
     fn to_idx(self) -> usize {
         self.to_usize().unwrap()
     }
+    #[inline(always)]
+    fn max_idx() -> usize {
+        greatest_from_usize::<Self>()
+    }
+}
+
+/// Get the greatest usize which `T::from_usize` can convert, assuming that it can
+/// convert every smaller one too.
+fn greatest_from_usize<T: FromPrimitive>() -> usize {
+    if T::from_usize(usize::MAX).is_some() {
+        return usize::MAX;
+    }
+    // `lo` can always be converted, and `hi` never can
+    let (mut lo, mut hi) = (0, usize::MAX);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if T::from_usize(mid).is_some() {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
 }
 
 /// An arena index which is always nonzero. Useful for Option<T> size optimizations
@@ -416,22 +717,40 @@ where
     fn to_idx(self) -> usize {
         self.idx.get().to_usize().unwrap() - 1
     }
+    #[inline(always)]
+    fn max_idx() -> usize {
+        greatest_from_usize::<T>().saturating_sub(1)
+    }
 }
 
 /// The `Arena` allows inserting and removing elements that are referred to by
 /// `Index`.
 ///
+/// The generation strategy `S` decides whether the arena shares one generation
+/// counter between all of its slots (`SharedGeneration`, the default) or keeps one
+/// per slot (`PerSlotGeneration`). Either way, the arena hands out the same `Index` type.
+///
 /// [See the module-level documentation for example usage and motivation.](./index.html)
 #[derive(Clone, Debug)]
-pub struct Arena<T, I = usize, G = usize> {
+pub struct Arena<T, I = usize, G = usize, S = SharedGeneration> {
     // It is a breaking change to modify these three members, as they are needed for serialization
     items: Vec<Entry<T, I, G>>,
+    /// With `SharedGeneration`, the generation of the next inserted element. With
+    /// `PerSlotGeneration`, the generation newly allocated slots start at.
     generation: G,
     len: usize,
     free_list_head: Option<I>,
+    free_list_tail: Option<I>,
+    /// With `ReusePolicy::LowestFirst`, the free slots, which are then not linked into
+    /// the free list.
+    lowest_free: BinaryHeap<cmp::Reverse<usize>>,
+    reuse: ReusePolicy,
+    exhaustion: ExhaustionPolicy,
+    retired: usize,
+    _strategy: core::marker::PhantomData<S>,
 }
 
-impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Default for Arena<T, I, G> {
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Default for Arena<T, I, G, S> {
     fn default() -> Self {
         Arena::new()
     }
@@ -439,8 +758,12 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Default for Arena<T, I, G> {
 
 #[derive(Clone, Debug)]
 enum Entry<T, I = usize, G = u64> {
-    Free { next_free: Option<I> },
+    /// A free slot. `generation` is the lowest generation the slot's next occupant
+    /// may have: every index ever handed out for this slot is older than it.
+    Free { next_free: Option<I>, generation: G },
     Occupied { generation: G, value: T },
+    /// A slot whose generations are exhausted, which is never reused.
+    Retired,
 }
 
 /// An index (and generation) into an `Arena`.
@@ -543,8 +866,8 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Index<T, I, G> {
     #[inline]
     pub fn new(index: I, generation: G) -> Index<T, I, G> {
         Index {
-            index: index,
-            generation: generation,
+            index,
+            generation,
             _phantom: std::marker::PhantomData,
         }
     }
@@ -587,7 +910,7 @@ impl<T, I: Ord, G: FixedGenerationalIndex> Ord for Index<T, I, G> {
 
 const DEFAULT_CAPACITY: usize = 4;
 
-impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Arena<T, I, G, S> {
     /// Constructs a new, empty `Arena`.
     ///
     /// # Examples
@@ -598,7 +921,7 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     /// let mut arena = StandardArena::<usize>::new();
     /// # let _ = arena;
     /// ```
-    pub fn new() -> Arena<T, I, G> {
+    pub fn new() -> Arena<T, I, G, S> {
         Arena::with_capacity(DEFAULT_CAPACITY)
     }
 
@@ -621,48 +944,269 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     /// // But now we are at capacity, and there is no more room.
     /// assert!(arena.try_insert(99).is_err());
     /// ```
-    pub fn with_capacity(n: usize) -> Arena<T, I, G> {
+    pub fn with_capacity(n: usize) -> Arena<T, I, G, S> {
         let n = cmp::max(n, 1);
         let mut arena = Arena {
             items: Vec::new(),
-            generation: G::first_generation(),
+            generation: G::arena_first_generation(),
             free_list_head: None,
+            free_list_tail: None,
+            lowest_free: BinaryHeap::new(),
+            reuse: ReusePolicy::default(),
             len: 0,
+            exhaustion: ExhaustionPolicy::default(),
+            retired: 0,
+            _strategy: core::marker::PhantomData,
         };
         arena.reserve(n);
         arena
     }
 
+    /// Constructs a new, empty `Arena` which reuses free slots according to `policy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{ReusePolicy, StandardArena};
+    ///
+    /// let mut arena = StandardArena::with_reuse_policy(ReusePolicy::Fifo);
+    /// let a = arena.insert(1);
+    /// let b = arena.insert(2);
+    /// arena.remove(a);
+    /// arena.remove(b);
+    ///
+    /// // Slots are reused in the order they were freed, after the never used ones
+    /// let c = arena.insert(3);
+    /// assert_eq!(c.to_idx(), 2);
+    /// ```
+    pub fn with_reuse_policy(policy: ReusePolicy) -> Arena<T, I, G, S> {
+        let mut arena = Arena::new();
+        arena.set_reuse_policy(policy);
+        arena
+    }
+
     /// Clear all the items inside the arena, but keep its allocation.
     ///
+    /// Every index into the arena from before the call is invalidated, so it will
+    /// never refer to an element inserted afterwards. Generation types which cannot
+    /// be advanced, such as `DisableRemoval`, are the exception: their indices from
+    /// before the call may refer to elements inserted afterwards.
+    ///
     /// # Examples
     ///
     /// ```
     /// use typed_generational_arena::StandardArena;
     ///
     /// let mut arena = StandardArena::with_capacity(1);
-    /// arena.insert(42);
+    /// let idx = arena.insert(42);
     /// arena.insert(43);
     ///
     /// arena.clear();
     ///
     /// assert_eq!(arena.capacity(), 2);
+    /// arena.insert(44);
+    /// assert!(!arena.contains(idx));
     /// ```
     pub fn clear(&mut self) {
-        self.items.clear();
+        self.drain();
+    }
 
-        let end = self.items.capacity();
-        self.items.extend((0..end).map(|i| {
-            if i == end - 1 {
-                Entry::Free { next_free: None }
+    /// Iterate over elements of the arena and remove them.
+    ///
+    /// Yields pairs of `(Index<T>, T)` items.
+    ///
+    /// Order of iteration is not defined.
+    ///
+    /// Note: All elements are removed even if the iterator is only partially consumed or not consumed at all.
+    /// As with `clear`, every index into the arena from before the call is invalidated,
+    /// unless the generation type cannot be advanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let idx_1 = arena.insert("hello");
+    /// let idx_2 = arena.insert("world");
+    ///
+    /// assert!(arena.get(idx_1).is_some());
+    /// assert!(arena.get(idx_2).is_some());
+    /// for (idx, value) in arena.drain() {
+    ///     assert!((idx == idx_1 && value == "hello") || (idx == idx_2 && value == "world"));
+    /// }
+    /// assert!(arena.get(idx_1).is_none());
+    /// assert!(arena.get(idx_2).is_none());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T, I, G, S> {
+        // Nothing needs invalidating if the arena is empty, so don't use up a generation.
+        let shared_advanced =
+            self.is_empty() || self.advance_shared_generation(G::advance_generation);
+        Drain::new(self, shared_advanced)
+    }
+
+    /// Vacate the slot at `slot` for a `Drain`.
+    fn drain_slot(&mut self, slot: usize, shared_advanced: bool) -> Option<(Index<T, I, G>, T)> {
+        self.vacate_slot(slot, shared_advanced, G::advance_generation)
+    }
+
+    /// Advance the shared generation with `advance` before freeing slots, returning whether
+    /// it could be advanced. Always `true` with `PerSlotGeneration`, where there is nothing
+    /// to advance.
+    fn advance_shared_generation(&mut self, advance: AdvanceGeneration<G>) -> bool {
+        if S::PER_SLOT {
+            return true;
+        }
+        let policy = self.exhaustion;
+        advance(&mut self.generation, policy)
+    }
+
+    /// Get the generation to free a slot whose last occupant had `generation` with, or
+    /// `None` if the slot has to be retired.
+    fn next_generation(
+        &self,
+        generation: G,
+        shared_advanced: bool,
+        advance: AdvanceGeneration<G>,
+    ) -> Option<G> {
+        if S::PER_SLOT {
+            let mut next_generation = generation;
+            if advance(&mut next_generation, self.exhaustion) {
+                Some(next_generation)
             } else {
-                Entry::Free {
-                    next_free: Some(I::from_idx(i + 1)),
+                None
+            }
+        } else if shared_advanced || generation.generation_lt(&self.generation) {
+            // Even once the shared generation is exhausted, a slot can still be
+            // reused once at the last generation.
+            Some(self.generation)
+        } else {
+            None
+        }
+    }
+
+    /// Vacate the slot at `slot` without linking it into the free list, returning its
+    /// element if it was occupied.
+    fn vacate_slot(
+        &mut self,
+        slot: usize,
+        shared_advanced: bool,
+        advance: AdvanceGeneration<G>,
+    ) -> Option<(Index<T, I, G>, T)> {
+        let generation = match self.items[slot] {
+            Entry::Occupied { generation, .. } => generation,
+            _ => return None,
+        };
+        let vacant = match self.next_generation(generation, shared_advanced, advance) {
+            Some(generation) => Entry::Free {
+                next_free: None,
+                generation,
+            },
+            None => {
+                self.retired += 1;
+                Entry::Retired
+            }
+        };
+        self.take_slot(slot, vacant)
+    }
+
+    /// Replace the slot at `slot` with `vacant` if it is occupied, returning its element.
+    fn take_slot(&mut self, slot: usize, vacant: Entry<T, I, G>) -> Option<(Index<T, I, G>, T)> {
+        if let Entry::Occupied { .. } = self.items[slot] {
+            match mem::replace(&mut self.items[slot], vacant) {
+                Entry::Occupied { generation, value } => {
+                    self.len -= 1;
+                    Some((Index::new(I::from_idx(slot), generation), value))
                 }
+                _ => unreachable!(),
             }
-        }));
-        self.free_list_head = Some(I::from_idx(0));
-        self.len = 0;
+        } else {
+            None
+        }
+    }
+
+    /// Link every free slot into the free list, in ascending order.
+    fn rebuild_free_list(&mut self) {
+        self.free_list_head = None;
+        self.free_list_tail = None;
+        self.lowest_free.clear();
+        for slot in 0..self.items.len() {
+            if let Entry::Free { .. } = self.items[slot] {
+                self.append_free(slot);
+            }
+        }
+    }
+
+    /// Make the free slot `slot` available for reuse after the other free slots, or
+    /// in its place with `ReusePolicy::LowestFirst`.
+    fn append_free(&mut self, slot: usize) {
+        match self.reuse {
+            ReusePolicy::LowestFirst => self.lowest_free.push(cmp::Reverse(slot)),
+            _ => self.push_free_back(slot),
+        }
+    }
+
+    /// Make the free slot `slot` available for reuse, as the reuse policy dictates.
+    fn push_free(&mut self, slot: usize) {
+        match self.reuse {
+            ReusePolicy::Lifo => self.push_free_front(slot),
+            ReusePolicy::Fifo => self.push_free_back(slot),
+            ReusePolicy::LowestFirst => self.lowest_free.push(cmp::Reverse(slot)),
+        }
+    }
+
+    fn push_free_front(&mut self, slot: usize) {
+        if let Entry::Free { ref mut next_free, .. } = self.items[slot] {
+            *next_free = self.free_list_head;
+        }
+        if self.free_list_tail.is_none() {
+            self.free_list_tail = Some(I::from_idx(slot));
+        }
+        self.free_list_head = Some(I::from_idx(slot));
+    }
+
+    fn push_free_back(&mut self, slot: usize) {
+        if let Entry::Free { ref mut next_free, .. } = self.items[slot] {
+            *next_free = None;
+        }
+        match self.free_list_tail {
+            Some(tail) => match self.items[tail.to_idx()] {
+                Entry::Free {
+                    ref mut next_free, ..
+                } => *next_free = Some(I::from_idx(slot)),
+                _ => panic!("corrupt free list"),
+            },
+            None => self.free_list_head = Some(I::from_idx(slot)),
+        }
+        self.free_list_tail = Some(I::from_idx(slot));
+    }
+
+    /// Get the free slot the next insertion will occupy.
+    #[inline]
+    fn peek_free(&self) -> Option<usize> {
+        match self.reuse {
+            ReusePolicy::LowestFirst => self.lowest_free.peek().map(|slot| slot.0),
+            _ => self.free_list_head.map(I::to_idx),
+        }
+    }
+
+    /// The free slots, in the order they will be reused.
+    fn free_slots(&self) -> Vec<usize> {
+        if self.reuse == ReusePolicy::LowestFirst {
+            let mut slots: Vec<usize> = self.lowest_free.iter().map(|slot| slot.0).collect();
+            slots.sort_unstable();
+            return slots;
+        }
+        let mut slots = Vec::with_capacity(self.items.len() - self.len - self.retired);
+        let mut next = self.free_list_head;
+        while let Some(slot) = next {
+            slots.push(slot.to_idx());
+            next = match self.items[slot.to_idx()] {
+                Entry::Free { next_free, .. } => next_free,
+                _ => panic!("corrupt free list"),
+            };
+        }
+        slots
     }
 
     /// Attempts to insert `value` into the arena using existing capacity.
@@ -693,23 +1237,191 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     /// ```
     #[inline]
     pub fn try_insert(&mut self, value: T) -> Result<Index<T, I, G>, T> {
-        match self.free_list_head {
+        match self.next_vacant() {
             None => Err(value),
-            Some(i) => {
-                let idx = i.to_idx();
-                match &self.items[idx] {
-                    Entry::Occupied { .. } => panic!("corrupt free list"),
-                    Entry::Free { next_free } => {
-                        self.free_list_head = *next_free;
-                        self.len += 1;
-                        self.items[idx] = Entry::Occupied {
-                            generation: self.generation,
-                            value,
-                        };
-                        Ok(Index::new(i, self.generation))
+            Some(index) => {
+                self.fill_vacant(index, value);
+                Ok(index)
+            }
+        }
+    }
+
+    /// Get the index the next insertion will occupy, without allocating.
+    #[inline]
+    fn next_vacant(&self) -> Option<Index<T, I, G>> {
+        let slot = self.peek_free()?;
+        if !S::PER_SLOT {
+            return Some(Index::new(I::from_idx(slot), self.generation));
+        }
+        match self.items[slot] {
+            Entry::Free { generation, .. } => Some(Index::new(I::from_idx(slot), generation)),
+            _ => panic!("corrupt free list"),
+        }
+    }
+
+    /// Fill the free slot the next insertion will occupy, which must be `index`.
+    #[inline]
+    fn fill_vacant(&mut self, index: Index<T, I, G>, value: T) {
+        let idx = index.index.to_idx();
+        match self.items[idx] {
+            Entry::Free { next_free, .. } => {
+                if self.reuse == ReusePolicy::LowestFirst {
+                    self.lowest_free.pop();
+                } else {
+                    self.free_list_head = next_free;
+                    if next_free.is_none() {
+                        self.free_list_tail = None;
                     }
                 }
+                self.len += 1;
+                self.items[idx] = Entry::Occupied {
+                    generation: index.generation,
+                    value,
+                };
             }
+            _ => panic!("corrupt free list"),
+        }
+    }
+
+    /// Attempts to insert the value returned by `create` into the arena using
+    /// existing capacity. `create` is passed the index the value will occupy,
+    /// which makes it possible to build values which refer to themselves.
+    ///
+    /// This method will never allocate new capacity in the arena.
+    ///
+    /// If insertion succeeds, then the new index is returned. If insertion
+    /// fails, then `Err(create)` is returned without calling `create`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{StandardArena, StandardIndex};
+    ///
+    /// struct Node {
+    ///     this: StandardIndex<Node>,
+    /// }
+    ///
+    /// let mut arena = StandardArena::with_capacity(1);
+    /// let idx = arena.try_insert_with(|this| Node { this }).ok().unwrap();
+    /// assert_eq!(arena[idx].this, idx);
+    ///
+    /// // The arena is now full.
+    /// assert!(arena.try_insert_with(|this| Node { this }).is_err());
+    /// ```
+    #[inline]
+    pub fn try_insert_with<F: FnOnce(Index<T, I, G>) -> T>(
+        &mut self,
+        create: F,
+    ) -> Result<Index<T, I, G>, F> {
+        match self.next_vacant() {
+            None => Err(create),
+            Some(index) => {
+                self.fill_vacant(index, create(index));
+                Ok(index)
+            }
+        }
+    }
+
+    /// Insert the value returned by `create` into the arena, allocating more
+    /// capacity if necessary. `create` is passed the index the value will
+    /// occupy, which makes it possible to build values which refer to themselves.
+    ///
+    /// The new index is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{StandardArena, StandardIndex};
+    ///
+    /// struct Node {
+    ///     parent: Option<StandardIndex<Node>>,
+    ///     this: StandardIndex<Node>,
+    /// }
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let root = arena.insert_with(|this| Node { parent: None, this });
+    /// let child = arena.insert_with(|this| Node { parent: Some(root), this });
+    /// assert_eq!(arena[child].this, child);
+    /// assert_eq!(arena[child].parent, Some(root));
+    /// ```
+    #[inline]
+    pub fn insert_with<F: FnOnce(Index<T, I, G>) -> T>(&mut self, create: F) -> Index<T, I, G> {
+        self.vacant_entry().insert_with(create)
+    }
+
+    /// Reserve a vacant slot in the arena, allocating more capacity if
+    /// necessary.
+    ///
+    /// The returned `VacantEntry` knows the index the next inserted value
+    /// will have before that value is constructed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::new();
+    ///
+    /// let entry = arena.vacant_entry();
+    /// let idx = entry.index();
+    /// assert_eq!(entry.insert(idx.to_idx()), idx);
+    /// assert_eq!(arena[idx], idx.to_idx());
+    /// ```
+    pub fn vacant_entry(&mut self) -> VacantEntry<'_, T, I, G, S> {
+        if self.peek_free().is_none() {
+            if let Err(error) = self.grow() {
+                panic!("{}", error);
+            }
+        }
+        self.try_vacant_entry()
+            .expect("a vacant entry will always exist after reserving additional space")
+    }
+
+    /// Attempts to reserve a vacant slot in the arena using existing capacity.
+    ///
+    /// This method will never allocate new capacity in the arena, and returns
+    /// `None` if the arena is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::with_capacity(1);
+    /// let idx = arena.try_vacant_entry().unwrap().insert(42);
+    /// assert_eq!(arena[idx], 42);
+    /// assert!(arena.try_vacant_entry().is_none());
+    /// ```
+    pub fn try_vacant_entry(&mut self) -> Option<VacantEntry<'_, T, I, G, S>> {
+        self.next_vacant()
+            .map(move |index| VacantEntry::new(self, index))
+    }
+
+    /// Get an `OccupiedEntry` for the element at index `i` if it is in the
+    /// arena.
+    ///
+    /// If the element at index `i` is not in the arena, then `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let idx = arena.insert(42);
+    ///
+    /// {
+    ///     let mut entry = arena.entry(idx).unwrap();
+    ///     assert_eq!(entry.replace(43), 42);
+    ///     assert_eq!(entry.remove(), 43);
+    /// }
+    /// assert!(arena.entry(idx).is_none());
+    /// ```
+    pub fn entry(&mut self, i: Index<T, I, G>) -> Option<OccupiedEntry<'_, T, I, G, S>> {
+        if self.contains(i) {
+            Some(OccupiedEntry::new(self, i))
+        } else {
+            None
         }
     }
 
@@ -737,11 +1449,54 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
 
     #[inline(never)]
     fn insert_slow_path(&mut self, value: T) -> Index<T, I, G> {
-        let len = self.items.len();
-        self.reserve(len);
-        self.try_insert(value)
-            .map_err(|_| ())
-            .expect("inserting will always succeed after reserving additional space")
+        match self.try_insert_grow(value) {
+            Ok(index) => index,
+            Err((_, error)) => panic!("{}", error),
+        }
+    }
+
+    /// Insert `value` into the arena, allocating more capacity if necessary, unless the
+    /// index type cannot address any more slots or the allocation fails.
+    ///
+    /// The `value`'s associated index in the arena is returned. On failure, `value` is
+    /// returned along with the reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{CapacityError, NanoArena};
+    ///
+    /// let mut arena = NanoArena::new();
+    /// for i in 0..256 {
+    ///     assert!(arena.try_insert_grow(i).is_ok());
+    /// }
+    /// assert_eq!(
+    ///     arena.try_insert_grow(256),
+    ///     Err((256, CapacityError::IndexSpaceExhausted { max_capacity: 256 }))
+    /// );
+    /// ```
+    pub fn try_insert_grow(&mut self, value: T) -> Result<Index<T, I, G>, (T, CapacityError)> {
+        let value = match self.try_insert(value) {
+            Ok(index) => return Ok(index),
+            Err(value) => value,
+        };
+        match self.grow() {
+            Ok(()) => self
+                .try_insert(value)
+                .map_err(|_| unreachable!("inserting will always succeed after growing")),
+            Err(error) => Err((value, error)),
+        }
+    }
+
+    /// Double the capacity of a full arena, or take whatever index space is left.
+    fn grow(&mut self) -> Result<(), CapacityError> {
+        let remaining = self.max_capacity() - self.items.len();
+        if remaining == 0 {
+            return Err(CapacityError::IndexSpaceExhausted {
+                max_capacity: self.max_capacity(),
+            });
+        }
+        self.try_reserve(cmp::min(cmp::max(self.items.len(), 1), remaining))
     }
 
     /// Is the element at index `i` in the arena?
@@ -782,13 +1537,7 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     /// assert!(arena.get(idx).is_none());
     /// ```
     pub fn get(&self, i: Index<T, I, G>) -> Option<&T> {
-        match self.items.get(i.index.to_idx()) {
-            Some(Entry::Occupied {
-                generation,
-                ref value,
-            }) if *generation == i.generation => Some(value),
-            _ => None,
-        }
+        self.try_get(i).ok()
     }
 
     /// Get an exclusive reference to the element at index `i` if it is in the
@@ -809,12 +1558,216 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     /// assert!(arena.get_mut(idx).is_none());
     /// ```
     pub fn get_mut(&mut self, i: Index<T, I, G>) -> Option<&mut T> {
-        match self.items.get_mut(i.index.to_idx()) {
+        self.try_get_mut(i).ok()
+    }
+
+    /// Get a shared reference to the element at index `i`, or an
+    /// `AccessError` describing why it is not in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{AccessError, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let idx = arena.insert(42);
+    /// assert_eq!(arena.try_get(idx), Ok(&42));
+    ///
+    /// arena.remove(idx);
+    /// match arena.try_get(idx) {
+    ///     Err(AccessError::Stale { current, requested, .. }) => assert!(requested < current),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn try_get(&self, i: Index<T, I, G>) -> Result<&T, AccessError<G>> {
+        match self.items.get(i.index.to_idx()) {
             Some(Entry::Occupied {
                 generation,
-                ref mut value,
-            }) if *generation == i.generation => Some(value),
-            _ => None,
+                ref value,
+            }) if *generation == i.generation => Ok(value),
+            _ => Err(self.access_error(i)),
+        }
+    }
+
+    /// Get an exclusive reference to the element at index `i`, or an
+    /// `AccessError` describing why it is not in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{AccessError, StandardArena};
+    ///
+    /// let mut arena = StandardArena::with_capacity(1);
+    /// let idx = arena.insert(42);
+    ///
+    /// *arena.try_get_mut(idx).unwrap() += 1;
+    /// assert_eq!(arena[idx], 43);
+    ///
+    /// let mut other = StandardArena::with_capacity(2);
+    /// other.insert(0);
+    /// let far = other.insert(1);
+    /// assert_eq!(
+    ///     arena.try_get_mut(far),
+    ///     Err(AccessError::OutOfBounds { slot: 1, capacity: 1 })
+    /// );
+    /// ```
+    pub fn try_get_mut(&mut self, i: Index<T, I, G>) -> Result<&mut T, AccessError<G>> {
+        self.try_get(i)?;
+        match self.items[i.index.to_idx()] {
+            Entry::Occupied { ref mut value, .. } => Ok(value),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Get exclusive references to the elements at `N` distinct indices at once.
+    ///
+    /// Returns an error if an index does not refer to an element of the arena, or if two
+    /// indices refer to the same element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{GetManyError, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let a = arena.insert(1);
+    /// let b = arena.insert(2);
+    /// let c = arena.insert(3);
+    ///
+    /// let [x, y, z] = arena.get_many_mut([c, a, b]).unwrap();
+    /// *x += *y + *z;
+    /// assert_eq!(arena[c], 6);
+    ///
+    /// arena.remove(b);
+    /// match arena.get_many_mut([a, b]) {
+    ///     Err(GetManyError::Invalid { position, .. }) => assert_eq!(position, 1),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn get_many_mut<const N: usize>(
+        &mut self,
+        indices: [Index<T, I, G>; N],
+    ) -> Result<[&mut T; N], GetManyError<G>> {
+        let mut order = [(0, 0); N];
+        let mut values: [Option<&mut T>; N] = core::array::from_fn(|_| None);
+        self.fill_many_mut(&indices, &mut order, &mut values)?;
+        Ok(values.map(|value| value.expect("every position was filled")))
+    }
+
+    /// Get exclusive references to the elements at a slice of distinct indices at once,
+    /// in the same order as the indices.
+    ///
+    /// Returns an error if an index does not refer to an element of the arena, or if two
+    /// indices refer to the same element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{GetManyError, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let indices: Vec<_> = (0..5).map(|i| arena.insert(i)).collect();
+    ///
+    /// for value in arena.get_many_mut_slice(&indices[1..]).unwrap() {
+    ///     *value *= 10;
+    /// }
+    /// assert_eq!(arena[indices[0]], 0);
+    /// assert_eq!(arena[indices[4]], 40);
+    ///
+    /// assert_eq!(
+    ///     arena.get_many_mut_slice(&[indices[2], indices[3], indices[2]]),
+    ///     Err(GetManyError::Duplicate { first: 0, second: 2 })
+    /// );
+    /// ```
+    pub fn get_many_mut_slice(
+        &mut self,
+        indices: &[Index<T, I, G>],
+    ) -> Result<Vec<&mut T>, GetManyError<G>> {
+        let mut order = Vec::new();
+        order.resize(indices.len(), (0, 0));
+        let mut values: Vec<Option<&mut T>> = iter::repeat_with(|| None).take(indices.len()).collect();
+        self.fill_many_mut(indices, &mut order, &mut values)?;
+        Ok(values
+            .into_iter()
+            .map(|value| value.expect("every position was filled"))
+            .collect())
+    }
+
+    /// Check `indices` refer to distinct elements, and put a reference to the element at
+    /// each index in the same position of `values`, using `order` as scratch space
+    fn fill_many_mut<'a>(
+        &'a mut self,
+        indices: &[Index<T, I, G>],
+        order: &mut [(usize, usize)],
+        values: &mut [Option<&'a mut T>],
+    ) -> Result<(), GetManyError<G>> {
+        for (position, &i) in indices.iter().enumerate() {
+            if let Err(error) = self.try_get(i) {
+                return Err(GetManyError::Invalid { position, error });
+            }
+            order[position] = (i.index.to_idx(), position);
+        }
+        order.sort_unstable();
+        for pair in order.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                return Err(GetManyError::Duplicate {
+                    first: pair[0].1,
+                    second: pair[1].1,
+                });
+            }
+        }
+
+        // Walk the slots in ascending order, splitting off each requested one
+        let mut rest = &mut self.items[..];
+        let mut offset = 0;
+        for &(slot, position) in order.iter() {
+            let (entry, tail) = mem::take(&mut rest)[slot - offset..]
+                .split_first_mut()
+                .expect("slot is in bounds");
+            rest = tail;
+            offset = slot + 1;
+            values[position] = match entry {
+                Entry::Occupied { ref mut value, .. } => Some(value),
+                _ => unreachable!("slot is occupied"),
+            };
+        }
+        Ok(())
+    }
+
+    /// Describe why index `i` does not refer to an element of the arena
+    fn access_error(&self, i: Index<T, I, G>) -> AccessError<G> {
+        if let Err(error) = AccessError::check_arena(&self.generation, &i.generation) {
+            return error;
+        }
+        let slot = i.index.to_idx();
+        match self.items.get(slot) {
+            None => AccessError::OutOfBounds {
+                slot,
+                capacity: self.items.len(),
+            },
+            Some(Entry::Occupied { generation, .. }) | Some(Entry::Free { generation, .. })
+                if i.generation.generation_lt(generation) =>
+            {
+                AccessError::Stale {
+                    slot,
+                    current: *generation,
+                    requested: i.generation,
+                }
+            }
+            Some(Entry::Occupied { generation, .. }) => AccessError::Newer {
+                slot,
+                current: *generation,
+                requested: i.generation,
+            },
+            // A free slot has not handed out its generation yet
+            Some(Entry::Free { generation, .. }) if *generation != i.generation => {
+                AccessError::Newer {
+                    slot,
+                    current: *generation,
+                    requested: i.generation,
+                }
+            }
+            Some(Entry::Free { .. }) | Some(Entry::Retired) => AccessError::Vacant { slot },
         }
     }
 
@@ -972,39 +1925,273 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
         self.items.len()
     }
 
+    /// Get the number of slots which have been permanently retired because their
+    /// generations were exhausted under `ExhaustionPolicy::Retire`.
+    ///
+    /// Retired slots count towards the capacity of the arena, but are never reused.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{ExhaustionPolicy, NanoArena};
+    ///
+    /// let mut arena = NanoArena::with_capacity(1);
+    /// arena.set_exhaustion_policy(ExhaustionPolicy::Retire);
+    /// for i in 0..255 {
+    ///     let idx = arena.insert(i);
+    ///     arena.remove(idx);
+    /// }
+    /// assert_eq!(arena.retired_slots(), 0);
+    ///
+    /// // The last generation has been used up, so the slot is retired
+    /// let idx = arena.insert(255);
+    /// arena.remove(idx);
+    /// assert_eq!(arena.retired_slots(), 1);
+    /// assert!(arena.try_insert(0).is_err());
+    /// ```
+    pub fn retired_slots(&self) -> usize {
+        self.retired
+    }
+
+    /// Get the policy this arena follows when a generation counter is exhausted.
+    pub fn exhaustion_policy(&self) -> ExhaustionPolicy {
+        self.exhaustion
+    }
+
+    /// Set the policy this arena follows when a generation counter is exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{ExhaustionPolicy, TinySlotArena};
+    ///
+    /// let mut arena = TinySlotArena::new();
+    /// assert_eq!(arena.exhaustion_policy(), ExhaustionPolicy::Increment);
+    /// arena.set_exhaustion_policy(ExhaustionPolicy::Retire);
+    /// assert_eq!(arena.exhaustion_policy(), ExhaustionPolicy::Retire);
+    /// # arena.insert(());
+    /// ```
+    pub fn set_exhaustion_policy(&mut self, policy: ExhaustionPolicy) {
+        self.exhaustion = policy;
+    }
+
+    /// Get the policy this arena follows when choosing which free slot to reuse.
+    pub fn reuse_policy(&self) -> ReusePolicy {
+        self.reuse
+    }
+
+    /// Set the policy this arena follows when choosing which free slot to reuse.
+    ///
+    /// Switching to or from `ReusePolicy::LowestFirst` forgets the order in which slots
+    /// were freed, and takes time linear in the capacity of the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{ReusePolicy, StandardArena};
+    ///
+    /// let mut arena = StandardArena::with_capacity(4);
+    /// let indices: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+    /// arena.set_reuse_policy(ReusePolicy::LowestFirst);
+    /// arena.remove(indices[3]);
+    /// arena.remove(indices[1]);
+    /// arena.remove(indices[2]);
+    ///
+    /// assert_eq!(arena.insert(4).to_idx(), 1);
+    /// assert_eq!(arena.insert(5).to_idx(), 2);
+    /// ```
+    pub fn set_reuse_policy(&mut self, policy: ReusePolicy) {
+        let rebuild = (self.reuse == ReusePolicy::LowestFirst) != (policy == ReusePolicy::LowestFirst);
+        self.reuse = policy;
+        if rebuild {
+            self.rebuild_free_list();
+        }
+    }
+
+    /// The greatest capacity an arena with this index type can have: the number of slots
+    /// it can address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{NanoArena, StandardArena, TinyArena};
+    ///
+    /// assert_eq!(NanoArena::<()>::new().max_capacity(), 256);
+    /// assert_eq!(TinyArena::<()>::new().max_capacity(), 65536);
+    /// assert_eq!(StandardArena::<()>::new().max_capacity(), usize::MAX);
+    /// ```
+    pub fn max_capacity(&self) -> usize {
+        I::max_idx().saturating_add(1)
+    }
+
     /// Allocate space for `additional_capacity` more elements in the arena.
     ///
+    /// The arena never grows past `max_capacity`: at most the remaining index space is
+    /// allocated.
+    ///
     /// # Panics
     ///
-    /// Panics if this causes the capacity to overflow.
+    /// Panics if the allocation fails.
     ///
     /// # Examples
     ///
     /// ```
-    /// use typed_generational_arena::StandardArena;
+    /// use typed_generational_arena::{NanoArena, StandardArena};
     ///
     /// let mut arena = StandardArena::with_capacity(10);
     /// arena.reserve(5);
     /// assert_eq!(arena.capacity(), 15);
     /// # let _: StandardArena<usize> = arena;
+    ///
+    /// let mut arena = NanoArena::with_capacity(200);
+    /// arena.reserve(100);
+    /// assert_eq!(arena.capacity(), 256);
+    /// # let _: NanoArena<usize> = arena;
     /// ```
     pub fn reserve(&mut self, additional_capacity: usize) {
-        let start = self.items.len();
-        let end = self.items.len() + additional_capacity;
-        let old_head = self.free_list_head;
+        let remaining = self.max_capacity() - self.items.len();
+        let additional_capacity = cmp::min(additional_capacity, remaining);
         self.items.reserve_exact(additional_capacity);
-        self.items.extend((start..end).map(|i| {
-            if i == end - 1 {
-                Entry::Free {
-                    next_free: old_head,
+        self.extend_free_list(additional_capacity);
+    }
+
+    /// Allocate space for `additional_capacity` more elements in the arena, unless this
+    /// would exceed `max_capacity` or the allocation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{CapacityError, NanoArena};
+    ///
+    /// let mut arena = NanoArena::<u32>::with_capacity(200);
+    /// assert_eq!(arena.try_reserve(56), Ok(()));
+    /// assert_eq!(
+    ///     arena.try_reserve(1),
+    ///     Err(CapacityError::IndexSpaceExhausted { max_capacity: 256 })
+    /// );
+    /// assert_eq!(arena.capacity(), 256);
+    /// ```
+    pub fn try_reserve(&mut self, additional_capacity: usize) -> Result<(), CapacityError> {
+        if additional_capacity > self.max_capacity() - self.items.len() {
+            return Err(CapacityError::IndexSpaceExhausted {
+                max_capacity: self.max_capacity(),
+            });
+        }
+        self.items
+            .try_reserve_exact(additional_capacity)
+            .map_err(|_| CapacityError::AllocationFailed)?;
+        self.extend_free_list(additional_capacity);
+        Ok(())
+    }
+
+    /// Drop the free slots at the end of the arena, keeping at least `min_capacity`
+    /// slots, and release the memory they used.
+    ///
+    /// Every live index stays valid. With `PerSlotGeneration`, the slots regrown in
+    /// place of the dropped ones start past the generations the dropped slots reached,
+    /// so stale indices into them stay invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::with_capacity(100);
+    /// let a = arena.insert(1);
+    /// let b = arena.insert(2);
+    /// arena.remove(b);
+    ///
+    /// arena.shrink_to(10);
+    /// assert_eq!(arena.capacity(), 10);
+    /// assert_eq!(arena[a], 1);
+    /// ```
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        let free = self.free_slots();
+        if self.truncate_free_tail(min_capacity) {
+            let capacity = self.items.len();
+            self.free_list_head = None;
+            self.free_list_tail = None;
+            self.lowest_free.clear();
+            for slot in free {
+                if slot < capacity {
+                    self.append_free(slot);
                 }
-            } else {
-                Entry::Free {
-                    next_free: Some(I::from_idx(i + 1)),
+            }
+        }
+        self.items.shrink_to(min_capacity);
+    }
+
+    /// Drop the free slots at the end of the arena, and release the memory they used.
+    ///
+    /// Every live index stays valid, and stale indices stay invalid. Free slots before
+    /// the last element are kept, and reused in the same order as before.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena = StandardArena::with_capacity(100);
+    /// let indices: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+    /// arena.remove(indices[1]);
+    /// arena.remove(indices[3]);
+    ///
+    /// arena.shrink_to_fit();
+    /// assert_eq!(arena.capacity(), 3);
+    /// assert_eq!(arena[indices[2]], 2);
+    /// assert_eq!(arena.insert(4).to_idx(), 1);
+    /// assert!(!arena.contains(indices[1]));
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0)
+    }
+
+    /// Drop the free slots at the end of the arena, keeping at least `min_capacity`
+    /// slots, without repairing the free list. Returns whether any slot was dropped.
+    ///
+    /// With `PerSlotGeneration`, the generation new slots start at is raised to that of
+    /// every dropped slot, so that stale indices into them stay invalid.
+    fn truncate_free_tail(&mut self, min_capacity: usize) -> bool {
+        let capacity = self.items.len();
+        while self.items.len() > min_capacity {
+            match self.items.last() {
+                Some(&Entry::Free { generation, .. }) => {
+                    if S::PER_SLOT && self.generation.generation_lt(&generation) {
+                        self.generation = generation;
+                    }
+                    self.items.pop();
                 }
+                _ => break,
             }
+        }
+        self.items.len() < capacity
+    }
+
+    /// Add `additional_capacity` free slots, which must already be allocated, to the
+    /// free list
+    fn extend_free_list(&mut self, additional_capacity: usize) {
+        let start = self.items.len();
+        let end = start + additional_capacity;
+        let generation = self.generation;
+        self.items.extend((start..end).map(|_| Entry::Free {
+            next_free: None,
+            generation,
         }));
-        self.free_list_head = Some(I::from_idx(start));
+        // New slots are reused in ascending order: before the older free slots with
+        // LIFO, after them with FIFO
+        match self.reuse {
+            ReusePolicy::Lifo => {
+                for slot in (start..end).rev() {
+                    self.push_free_front(slot);
+                }
+            }
+            ReusePolicy::Fifo => {
+                for slot in start..end {
+                    self.push_free_back(slot);
+                }
+            }
+            ReusePolicy::LowestFirst => self.lowest_free.extend((start..end).map(cmp::Reverse)),
+        }
     }
 
     /// Iterate over shared references to the elements in this arena.
@@ -1027,7 +2214,7 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     ///     println!("{} is at index {:?}", value, idx);
     /// }
     /// ```
-    pub fn iter(&self) -> Iter<T, I, G> {
+    pub fn iter(&self) -> Iter<'_, T, I, G> {
         Iter {
             len: self.len,
             inner: self.items.iter().enumerate(),
@@ -1054,44 +2241,13 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     ///     *value += 5;
     /// }
     /// ```
-    pub fn iter_mut(&mut self) -> IterMut<T, I, G> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, I, G> {
         IterMut {
             len: self.len,
             inner: self.items.iter_mut().enumerate(),
         }
     }
 
-    /// Iterate over elements of the arena and remove them.
-    ///
-    /// Yields pairs of `(Index<T>, T)` items.
-    ///
-    /// Order of iteration is not defined.
-    ///
-    /// Note: All elements are removed even if the iterator is only partially consumed or not consumed at all.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use typed_generational_arena::StandardArena;
-    ///
-    /// let mut arena = StandardArena::new();
-    /// let idx_1 = arena.insert("hello");
-    /// let idx_2 = arena.insert("world");
-    ///
-    /// assert!(arena.get(idx_1).is_some());
-    /// assert!(arena.get(idx_2).is_some());
-    /// for (idx, value) in arena.drain() {
-    ///     assert!((idx == idx_1 && value == "hello") || (idx == idx_2 && value == "world"));
-    /// }
-    /// assert!(arena.get(idx_1).is_none());
-    /// assert!(arena.get(idx_2).is_none());
-    /// ```
-    pub fn drain(&mut self) -> Drain<T, I, G> {
-        Drain {
-            inner: self.items.drain(..).enumerate(),
-        }
-    }
-
     /// If an integer index is valid, returns it as a generational index
     ///
     /// # Examples
@@ -1113,7 +2269,7 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Arena<T, I, G> {
     }
 }
 
-impl<T, I: ArenaIndex, G: GenerationalIndex> Arena<T, I, G> {
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> Arena<T, I, G, S> {
     /// Remove the element at index `i` from the arena.
     ///
     /// If the element at index `i` is still in the arena, then it is
@@ -1131,32 +2287,52 @@ impl<T, I: ArenaIndex, G: GenerationalIndex> Arena<T, I, G> {
     /// assert_eq!(arena.remove(idx), None);
     /// ```
     pub fn remove(&mut self, i: Index<T, I, G>) -> Option<T> {
-        if i.index.to_idx() >= self.items.len() {
-            return None;
-        }
+        self.try_remove(i).ok()
+    }
 
-        let entry = mem::replace(
-            &mut self.items[i.index.to_idx()],
-            Entry::Free {
-                next_free: self.free_list_head,
-            },
-        );
-        match entry {
-            Entry::Occupied { generation, value } => {
-                if generation == i.generation {
-                    self.generation.increment_generation();
-                    self.free_list_head = Some(i.index);
-                    self.len -= 1;
-                    Some(value)
-                } else {
-                    self.items[i.index.to_idx()] = Entry::Occupied { generation, value };
-                    None
-                }
+    /// Remove the element at index `i` from the arena, returning it, or an
+    /// `AccessError` describing why it is not in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{AccessError, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let idx = arena.insert(42);
+    ///
+    /// assert_eq!(arena.try_remove(idx), Ok(42));
+    /// match arena.try_remove(idx) {
+    ///     Err(AccessError::Stale { slot, .. }) => assert_eq!(slot, 0),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn try_remove(&mut self, i: Index<T, I, G>) -> Result<T, AccessError<G>> {
+        self.try_get(i)?;
+        let idx = i.index.to_idx();
+
+        let shared_advanced = self.advance_shared_generation(Self::advance);
+        let entry = match self.next_generation(i.generation, shared_advanced, Self::advance) {
+            Some(generation) => {
+                let entry = mem::replace(
+                    &mut self.items[idx],
+                    Entry::Free {
+                        next_free: None,
+                        generation,
+                    },
+                );
+                self.push_free(idx);
+                entry
             }
-            e @ Entry::Free { .. } => {
-                self.items[i.index.to_idx()] = e;
-                None
+            None => {
+                self.retired += 1;
+                mem::replace(&mut self.items[idx], Entry::Retired)
             }
+        };
+        self.len -= 1;
+        match entry {
+            Entry::Occupied { value, .. } => Ok(value),
+            _ => unreachable!(),
         }
     }
 
@@ -1196,9 +2372,14 @@ impl<T, I: ArenaIndex, G: GenerationalIndex> Arena<T, I, G> {
             }
         }
     }
+
+    /// Advance `generation` as `policy` dictates, for removing elements.
+    fn advance(generation: &mut G, policy: ExhaustionPolicy) -> bool {
+        policy.advance(generation)
+    }
 }
 
-impl<T, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator for Arena<T, I, G> {
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S> IntoIterator for Arena<T, I, G, S> {
     type Item = T;
     type IntoIter = IntoIter<T, I, G>;
     fn into_iter(self) -> Self::IntoIter {
@@ -1241,7 +2422,7 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for IntoIter<T, I, G>
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             match self.inner.next() {
-                Some(Entry::Free { .. }) => continue,
+                Some(Entry::Free { .. }) | Some(Entry::Retired) => continue,
                 Some(Entry::Occupied { value, .. }) => {
                     self.len -= 1;
                     return Some(value);
@@ -1263,7 +2444,7 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> DoubleEndedIterator for IntoIt
     fn next_back(&mut self) -> Option<Self::Item> {
         loop {
             match self.inner.next_back() {
-                Some(Entry::Free { .. }) => continue,
+                Some(Entry::Free { .. }) | Some(Entry::Retired) => continue,
                 Some(Entry::Occupied { value, .. }) => {
                     self.len -= 1;
                     return Some(value);
@@ -1285,7 +2466,9 @@ impl<T, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator for IntoIter
 
 impl<T, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for IntoIter<T, I, G> {}
 
-impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator for &'a Arena<T, I, G> {
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> IntoIterator
+    for &'a Arena<T, I, G, S>
+{
     type Item = (Index<T, I, G>, &'a T);
     type IntoIter = Iter<'a, T, I, G>;
     fn into_iter(self) -> Self::IntoIter {
@@ -1325,7 +2508,7 @@ impl<'a, T, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> Iterator for Ite
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             match self.inner.next() {
-                Some((_, &Entry::Free { .. })) => continue,
+                Some((_, &Entry::Free { .. })) | Some((_, &Entry::Retired)) => continue,
                 Some((
                     index,
                     &Entry::Occupied {
@@ -1356,7 +2539,7 @@ impl<'a, T, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> DoubleEndedItera
     fn next_back(&mut self) -> Option<Self::Item> {
         loop {
             match self.inner.next_back() {
-                Some((_, &Entry::Free { .. })) => continue,
+                Some((_, &Entry::Free { .. })) | Some((_, &Entry::Retired)) => continue,
                 Some((
                     index,
                     &Entry::Occupied {
@@ -1385,7 +2568,9 @@ impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator for Iter
 
 impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for Iter<'a, T, I, G> {}
 
-impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator for &'a mut Arena<T, I, G> {
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> IntoIterator
+    for &'a mut Arena<T, I, G, S>
+{
     type Item = (Index<T, I, G>, &'a mut T);
     type IntoIter = IterMut<'a, T, I, G>;
     fn into_iter(self) -> Self::IntoIter {
@@ -1425,7 +2610,7 @@ impl<'a, T, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> Iterator for Ite
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             match self.inner.next() {
-                Some((_, &mut Entry::Free { .. })) => continue,
+                Some((_, &mut Entry::Free { .. })) | Some((_, &mut Entry::Retired)) => continue,
                 Some((
                     index,
                     &mut Entry::Occupied {
@@ -1456,7 +2641,7 @@ impl<'a, T, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> DoubleEndedItera
     fn next_back(&mut self) -> Option<Self::Item> {
         loop {
             match self.inner.next_back() {
-                Some((_, &mut Entry::Free { .. })) => continue,
+                Some((_, &mut Entry::Free { .. })) | Some((_, &mut Entry::Retired)) => continue,
                 Some((
                     index,
                     &mut Entry::Occupied {
@@ -1516,28 +2701,65 @@ impl<'a, T, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> FusedIterator
 /// assert!(arena.get(idx_2).is_none());
 /// ```
 #[derive(Debug)]
-pub struct Drain<'a, T: 'a, I: ArenaIndex, G: FixedGenerationalIndex> {
-    inner: iter::Enumerate<vec::Drain<'a, Entry<T, I, G>>>,
+pub struct Drain<
+    'a,
+    T: 'a,
+    I: 'a + ArenaIndex,
+    G: 'a + FixedGenerationalIndex,
+    S: 'a + GenerationStrategy = SharedGeneration,
+> {
+    arena: &'a mut Arena<T, I, G, S>,
+    slot: usize,
+    shared_advanced: bool,
+}
+
+/// Advances a generation as an `ExhaustionPolicy` dictates, returning `false` if it is
+/// exhausted.
+type AdvanceGeneration<G> = fn(&mut G, ExhaustionPolicy) -> bool;
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Drain<'a, T, I, G, S> {
+    fn new(arena: &'a mut Arena<T, I, G, S>, shared_advanced: bool) -> Self {
+        Drain {
+            arena,
+            slot: 0,
+            shared_advanced,
+        }
+    }
 }
 
-impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for Drain<'a, T, I, G> {
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Iterator
+    for Drain<'a, T, I, G, S>
+{
     type Item = (Index<T, I, G>, T);
 
     fn next(&mut self) -> Option<Self::Item> {
-        loop {
-            match self.inner.next() {
-                Some((_, Entry::Free { .. })) => continue,
-                Some((index, Entry::Occupied { generation, value })) => {
-                    let idx = Index::new(I::from_idx(index), generation);
-                    return Some((idx, value));
-                }
-                None => return None,
+        while self.slot < self.arena.items.len() {
+            let slot = self.slot;
+            self.slot += 1;
+            if let Some(item) = self.arena.drain_slot(slot, self.shared_advanced) {
+                return Some(item);
             }
         }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.arena.len, Some(self.arena.len))
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Drop
+    for Drain<'a, T, I, G, S>
+{
+    fn drop(&mut self) {
+        self.by_ref().for_each(drop);
+        self.arena.rebuild_free_list();
     }
 }
 
-impl<T, Idx: ArenaIndex, G: FixedGenerationalIndex> Extend<T> for Arena<T, Idx, G> {
+impl<T, Idx: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Extend<T>
+    for Arena<T, Idx, G, S>
+{
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
         for t in iter {
             self.insert(t);
@@ -1545,7 +2767,9 @@ impl<T, Idx: ArenaIndex, G: FixedGenerationalIndex> Extend<T> for Arena<T, Idx,
     }
 }
 
-impl<T, Idx: ArenaIndex, G: FixedGenerationalIndex> FromIterator<T> for Arena<T, Idx, G> {
+impl<T, Idx: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> FromIterator<T>
+    for Arena<T, Idx, G, S>
+{
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let iter = iter.into_iter();
         let (lower, upper) = iter.size_hint();
@@ -1557,16 +2781,26 @@ impl<T, Idx: ArenaIndex, G: FixedGenerationalIndex> FromIterator<T> for Arena<T,
     }
 }
 
-impl<T, I: ArenaIndex, G: FixedGenerationalIndex> ops::Index<Index<T, I, G>> for Arena<T, I, G> {
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> ops::Index<Index<T, I, G>>
+    for Arena<T, I, G, S>
+{
     type Output = T;
 
     fn index(&self, index: Index<T, I, G>) -> &Self::Output {
-        self.get(index).expect("No element at index")
+        match self.try_get(index) {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error.summary()),
+        }
     }
 }
 
-impl<T, I: ArenaIndex, G: FixedGenerationalIndex> ops::IndexMut<Index<T, I, G>> for Arena<T, I, G> {
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> ops::IndexMut<Index<T, I, G>>
+    for Arena<T, I, G, S>
+{
     fn index_mut(&mut self, index: Index<T, I, G>) -> &mut Self::Output {
-        self.get_mut(index).expect("No element at index")
+        match self.try_get_mut(index) {
+            Ok(value) => value,
+            Err(error) => panic!("{}", error.summary()),
+        }
     }
 }
@@ -0,0 +1,337 @@
+use super::slots::{checked, would_block, Slot, Slots};
+use super::{AccessError, ArenaIndex, CapacityError, GenerationalIndex, Index, LockError};
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// An arena whose elements can be read and written by several threads at once,
+/// each element behind its own reader-writer lock.
+///
+/// Insertion and removal lock the list of free slots, while `get_read` and
+/// `get_write` only lock the slot of the element, so threads mutating different
+/// elements never wait for each other. Slots are allocated in
+/// segments which never move. Every slot keeps its own generation, like in an
+/// `Arena` with `PerSlotGeneration`, and a slot whose generation is exhausted is
+/// never reused.
+///
+/// A thread panicking while it holds a write guard poisons the element, which may
+/// be left half updated: the element can then only be removed, and the methods
+/// locking it report `LockError::Poisoned`, or `None`.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use typed_generational_arena::LockingArena;
+///
+/// let arena = LockingArena::<Vec<u32>>::new();
+/// let indices: Vec<_> = (0..4).map(|_| arena.insert(Vec::new())).collect();
+/// thread::scope(|s| {
+///     for (t, &idx) in indices.iter().enumerate() {
+///         let arena = &arena;
+///         s.spawn(move || {
+///             for i in 0..100 {
+///                 arena.get_write(idx).unwrap().push(t as u32 * 100 + i);
+///             }
+///         });
+///     }
+/// });
+/// assert!(indices
+///     .iter()
+///     .all(|&idx| arena.get_read(idx).unwrap().len() == 100));
+/// ```
+pub struct LockingArena<T, I = usize, G = usize> {
+    slots: Slots<RwLock<Slot<T, G>>, G>,
+    _phantom: core::marker::PhantomData<fn() -> I>,
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> LockingArena<T, I, G> {
+    /// Constructs a new, empty `LockingArena`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::LockingArena;
+    ///
+    /// let arena = LockingArena::<usize>::new();
+    /// # let _ = arena;
+    /// ```
+    pub fn new() -> LockingArena<T, I, G> {
+        LockingArena::with_capacity(0)
+    }
+
+    /// Constructs a new, empty `LockingArena` able to hold `n` elements without
+    /// further allocation.
+    pub fn with_capacity(n: usize) -> LockingArena<T, I, G> {
+        LockingArena {
+            slots: Slots::new(n, 1, I::max_idx().saturating_add(1)),
+            _phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Get the number of elements in the arena.
+    ///
+    /// Other threads may insert or remove elements at any time, so this is only a
+    /// snapshot.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Is the arena empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of slots handed out so far, whether they hold an element or
+    /// not.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Get the number of slots the index type of the arena can address.
+    pub fn max_capacity(&self) -> usize {
+        self.slots.max_capacity()
+    }
+
+    /// Insert `value` into the arena, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index type cannot address any more slots.
+    pub fn insert(&self, value: T) -> Index<T, I, G> {
+        match self.try_insert(value) {
+            Ok(index) => index,
+            Err((_, error)) => panic!("{}", error),
+        }
+    }
+
+    /// Insert `value` into the arena, unless the index type cannot address any more
+    /// slots, in which case `value` is returned along with the reason.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{CapacityError, LockingArena};
+    ///
+    /// let arena = LockingArena::<u32, u8, u8>::new();
+    /// for i in 0..256 {
+    ///     assert!(arena.try_insert(i).is_ok());
+    /// }
+    /// assert_eq!(
+    ///     arena.try_insert(256),
+    ///     Err((256, CapacityError::IndexSpaceExhausted { max_capacity: 256 }))
+    /// );
+    /// ```
+    pub fn try_insert(&self, value: T) -> Result<Index<T, I, G>, (T, CapacityError)> {
+        match self.slots.insert(value) {
+            Ok((slot, generation)) => Ok(Index::new(I::from_idx(slot), generation)),
+            Err(value) => Err((
+                value,
+                CapacityError::IndexSpaceExhausted {
+                    max_capacity: self.max_capacity(),
+                },
+            )),
+        }
+    }
+
+    /// Remove the element at index `i` from the arena, returning it, or `None` if
+    /// `i` does not refer to an element of the arena.
+    ///
+    /// Waits until every guard to the element is dropped. A poisoned element is
+    /// removed too, and returned as the panicking thread left it.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread holds a guard to the element.
+    pub fn remove(&self, i: Index<T, I, G>) -> Option<T> {
+        self.slots.remove(i.index.to_idx(), i.generation)
+    }
+
+    /// Is the element at index `i` in the arena, poisoned or not?
+    ///
+    /// Waits while another thread holds a write guard to the element.
+    pub fn contains(&self, i: Index<T, I, G>) -> bool {
+        match self.lookup(i) {
+            Ok(lock) => lock
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .check(i.index.to_idx(), i.generation)
+                .is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Lock the element at index `i` for reading and get a guard to it, or `None`
+    /// if `i` does not refer to an element of the arena or the element is
+    /// poisoned.
+    ///
+    /// Several threads can read the same element at once. Waits while another
+    /// thread holds a write guard to the element.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread holds a write guard to the
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::LockingArena;
+    ///
+    /// let arena = LockingArena::<&str>::new();
+    /// let idx = arena.insert("hello");
+    /// let first = arena.get_read(idx).unwrap();
+    /// let second = arena.get_read(idx).unwrap();
+    /// assert_eq!(*first, *second);
+    /// ```
+    pub fn get_read(&self, i: Index<T, I, G>) -> Option<LockingReadGuard<'_, T, G>> {
+        let slot = i.index.to_idx();
+        let entry = checked(self.lookup(i).ok()?.read(), slot, i.generation).ok()?;
+        Some(LockingReadGuard { entry })
+    }
+
+    /// Lock the element at index `i` for writing and get a guard to it, or `None`
+    /// if `i` does not refer to an element of the arena or the element is
+    /// poisoned.
+    ///
+    /// Waits while other threads hold guards to the element.
+    ///
+    /// # Panics
+    ///
+    /// Deadlocks or panics if the current thread already holds a guard to the
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::LockingArena;
+    ///
+    /// let arena = LockingArena::<String>::new();
+    /// let idx = arena.insert("hello".to_string());
+    /// arena.get_write(idx).unwrap().push_str(", world");
+    /// assert_eq!(*arena.get_read(idx).unwrap(), "hello, world");
+    ///
+    /// arena.remove(idx);
+    /// assert!(arena.get_write(idx).is_none());
+    /// ```
+    pub fn get_write(&self, i: Index<T, I, G>) -> Option<LockingWriteGuard<'_, T, G>> {
+        let slot = i.index.to_idx();
+        let entry = checked(self.lookup(i).ok()?.write(), slot, i.generation).ok()?;
+        Some(LockingWriteGuard { entry })
+    }
+
+    /// Lock the element at index `i` for reading without waiting, or get the reason
+    /// it cannot be locked: `i` does not refer to an element of the arena, another
+    /// guard holds a write lock on it, or it is poisoned.
+    pub fn try_get_read(
+        &self,
+        i: Index<T, I, G>,
+    ) -> Result<LockingReadGuard<'_, T, G>, LockError<G>> {
+        let slot = i.index.to_idx();
+        let lock = self.lookup(i)?;
+        let entry = checked(would_block(lock.try_read())?, slot, i.generation)?;
+        Ok(LockingReadGuard { entry })
+    }
+
+    /// Lock the element at index `i` for writing without waiting, or get the reason
+    /// it cannot be locked: `i` does not refer to an element of the arena, other
+    /// guards hold a lock on it, or it is poisoned.
+    pub fn try_get_write(
+        &self,
+        i: Index<T, I, G>,
+    ) -> Result<LockingWriteGuard<'_, T, G>, LockError<G>> {
+        let slot = i.index.to_idx();
+        let lock = self.lookup(i)?;
+        let entry = checked(would_block(lock.try_write())?, slot, i.generation)?;
+        Ok(LockingWriteGuard { entry })
+    }
+
+    /// Get an exclusive reference to the element at index `i`, without locking
+    /// since the arena is borrowed exclusively, or `None` if `i` does not refer to
+    /// an element of the arena or the element is poisoned.
+    pub fn get_mut(&mut self, i: Index<T, I, G>) -> Option<&mut T> {
+        self.slots.get_mut(i.index.to_idx(), i.generation)
+    }
+
+    /// Get the lock of the slot of index `i`, or the reason it is out of bounds
+    fn lookup(&self, i: Index<T, I, G>) -> Result<&RwLock<Slot<T, G>>, AccessError<G>> {
+        self.slots.lookup(i.index.to_idx(), &i.generation)
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> Default for LockingArena<T, I, G> {
+    fn default() -> Self {
+        LockingArena::new()
+    }
+}
+
+impl<T, I, G> fmt::Debug for LockingArena<T, I, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LockingArena")
+            .field("len", &self.slots.len())
+            .field("capacity", &self.slots.capacity())
+            .finish()
+    }
+}
+
+/// An element of a `LockingArena` locked for reading, obtained from
+/// `LockingArena::get_read`.
+///
+/// Other threads can read the element too, but cannot write or remove it until
+/// the guard is dropped.
+pub struct LockingReadGuard<'a, T: 'a, G: 'a> {
+    /// The locked slot, which holds an element
+    entry: RwLockReadGuard<'a, Slot<T, G>>,
+}
+
+impl<'a, T, G> Deref for LockingReadGuard<'a, T, G> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.entry.value {
+            Some(ref value) => value,
+            None => unreachable!("a locked element cannot be removed"),
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug, G> fmt::Debug for LockingReadGuard<'a, T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// An element of a `LockingArena` locked for writing, obtained from
+/// `LockingArena::get_write`.
+///
+/// Other threads cannot access or remove the element until the guard is dropped.
+pub struct LockingWriteGuard<'a, T: 'a, G: 'a> {
+    /// The locked slot, which holds an element
+    entry: RwLockWriteGuard<'a, Slot<T, G>>,
+}
+
+impl<'a, T, G> Deref for LockingWriteGuard<'a, T, G> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.entry.value {
+            Some(ref value) => value,
+            None => unreachable!("a locked element cannot be removed"),
+        }
+    }
+}
+
+impl<'a, T, G> DerefMut for LockingWriteGuard<'a, T, G> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self.entry.value {
+            Some(ref mut value) => value,
+            None => unreachable!("a locked element cannot be removed"),
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug, G> fmt::Debug for LockingWriteGuard<'a, T, G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,279 @@
+//! A least recently used cache whose entries are the elements of an `Arena`.
+
+use super::{ArenaIndex, GenerationalIndex, Index, OrderedArena};
+use core::borrow::Borrow;
+use core::hash::Hash;
+use core::iter::FusedIterator;
+use std::collections::HashMap;
+
+/// A handle to an entry of an `LruArena` with values of type `V`: a plain
+/// `Index`, which goes stale once the entry is evicted or removed.
+pub type LruHandle<V, I = usize, G = usize> = Index<V, I, G>;
+
+/// An entry of the cache
+#[derive(Clone, Debug)]
+struct Slot<K, V> {
+    key: K,
+    value: V,
+}
+
+/// A cache mapping keys of type `K` to values of type `V`, which evicts its least
+/// recently used entry to make room for a new one once it holds `capacity`
+/// entries.
+///
+/// Entries are stored in an `OrderedArena` listing them from the most to the
+/// least recently used. Inserting or getting an entry moves it to the front of
+/// the list, and peeking at it does not. Besides their keys, entries can be
+/// accessed through `LruHandle`s, which are checked against the generation of
+/// their slot, so a handle to an evicted entry is never confused with a later
+/// one in the same slot.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::LruArena;
+///
+/// let mut cache = LruArena::<&str, u32>::new(2);
+/// let (one, _) = cache.insert("one", 1);
+/// cache.insert("two", 2);
+///
+/// // Getting "one" makes "two" the least recently used entry
+/// assert_eq!(cache.get("one"), Some(&1));
+/// let (_, evicted) = cache.insert("three", 3);
+/// assert_eq!(evicted, Some(("two", 2)));
+///
+/// assert_eq!(cache.value(one), Some(&1));
+/// cache.insert("four", 4);
+/// assert_eq!(cache.value(one), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LruArena<K, V, I = usize, G = usize> {
+    entries: OrderedArena<Slot<K, V>, I, G>,
+    handles: HashMap<K, LruHandle<V, I, G>>,
+    capacity: usize,
+}
+
+/// Convert a handle to or from the index of its entry in the arena
+#[inline]
+fn cast<A, B, I: ArenaIndex, G: GenerationalIndex>(i: Index<A, I, G>) -> Index<B, I, G> {
+    Index::new(i.index, i.generation)
+}
+
+impl<K, V, I, G> LruArena<K, V, I, G>
+where
+    K: Clone + Hash + Eq,
+    I: ArenaIndex,
+    G: GenerationalIndex,
+{
+    /// Constructs a new, empty `LruArena` holding at most `capacity` entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> LruArena<K, V, I, G> {
+        assert!(capacity > 0, "LRU capacity must be at least 1");
+        LruArena {
+            entries: OrderedArena::with_capacity(capacity),
+            handles: HashMap::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Get the number of entries the cache holds before evicting any.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Is the cache empty?
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Insert `value` for `key` as the most recently used entry, returning its
+    /// handle, and the entry it pushed out of the cache, if any.
+    ///
+    /// That is the previous entry for `key`, whose handle goes stale, or else the
+    /// least recently used entry if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) -> (LruHandle<V, I, G>, Option<(K, V)>) {
+        let pushed_out = match self.handles.get(&key) {
+            Some(&handle) => self.remove_handle(handle),
+            None if self.len() == self.capacity => self.pop_lru(),
+            None => None,
+        };
+        let handle = cast(self.entries.push_front(Slot {
+            key: key.clone(),
+            value,
+        }));
+        self.handles.insert(key, handle);
+        (handle, pushed_out)
+    }
+
+    /// Is there an entry for `key` in the cache?
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.handles.contains_key(key)
+    }
+
+    /// Get the handle of the entry for `key`, without marking it as used.
+    pub fn handle<Q>(&self, key: &Q) -> Option<LruHandle<V, I, G>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.handles.get(key).cloned()
+    }
+
+    /// Get a shared reference to the value for `key`, marking its entry as the
+    /// most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.handle(key)?;
+        self.touch(handle);
+        self.value(handle)
+    }
+
+    /// Get an exclusive reference to the value for `key`, marking its entry as
+    /// the most recently used.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.handle(key)?;
+        self.touch(handle);
+        self.value_mut(handle)
+    }
+
+    /// Get a shared reference to the value for `key`, without marking it as
+    /// used.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.value(self.handle(key)?)
+    }
+
+    /// Remove the entry for `key`, returning its value.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.handle(key)?;
+        self.remove_handle(handle).map(|(_, value)| value)
+    }
+
+    /// Is the entry with the handle `handle` in the cache?
+    pub fn contains(&self, handle: LruHandle<V, I, G>) -> bool {
+        self.entries.contains(cast(handle))
+    }
+
+    /// Mark the entry with the handle `handle` as the most recently used,
+    /// returning whether it is in the cache.
+    pub fn touch(&mut self, handle: LruHandle<V, I, G>) -> bool {
+        match self.entries.front() {
+            Some(front) => self.entries.move_before(cast(handle), front).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Get the key of the entry with the handle `handle`, if it is in the cache.
+    pub fn key(&self, handle: LruHandle<V, I, G>) -> Option<&K> {
+        self.entries.get(cast(handle)).map(|slot| &slot.key)
+    }
+
+    /// Get a shared reference to the value of the entry with the handle
+    /// `handle`, without marking it as used.
+    pub fn value(&self, handle: LruHandle<V, I, G>) -> Option<&V> {
+        self.entries.get(cast(handle)).map(|slot| &slot.value)
+    }
+
+    /// Get an exclusive reference to the value of the entry with the handle
+    /// `handle`, without marking it as used.
+    pub fn value_mut(&mut self, handle: LruHandle<V, I, G>) -> Option<&mut V> {
+        self.entries
+            .get_mut(cast(handle))
+            .map(|slot| &mut slot.value)
+    }
+
+    /// Remove the entry with the handle `handle`, returning its key and value.
+    pub fn remove_handle(&mut self, handle: LruHandle<V, I, G>) -> Option<(K, V)> {
+        let slot = self.entries.remove(cast(handle))?;
+        self.handles.remove(&slot.key);
+        Some((slot.key, slot.value))
+    }
+
+    /// Get the handle of the least recently used entry.
+    pub fn lru(&self) -> Option<LruHandle<V, I, G>> {
+        self.entries.back().map(cast)
+    }
+
+    /// Remove the least recently used entry, returning its key and value.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let handle = self.lru()?;
+        self.remove_handle(handle)
+    }
+
+    /// Remove every entry from the cache.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.handles.clear();
+    }
+
+    /// Iterate over the entries of the cache, from the most to the least recently
+    /// used, with their handles, without marking them as used.
+    pub fn iter(&self) -> Iter<'_, K, V, I, G> {
+        Iter {
+            entries: self.entries.iter(),
+        }
+    }
+}
+
+/// An iterator over the entries of an `LruArena`, from the most to the least
+/// recently used, created by `LruArena::iter`.
+#[derive(Clone, Debug)]
+pub struct Iter<'a, K: 'a, V: 'a, I: 'a, G: 'a> {
+    entries: super::ordered::Iter<'a, Slot<K, V>, I, G>,
+}
+
+impl<'a, K, V, I: ArenaIndex, G: GenerationalIndex> Iterator for Iter<'a, K, V, I, G> {
+    type Item = (LruHandle<V, I, G>, &'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next()
+            .map(|(index, slot)| (cast(index), &slot.key, &slot.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<'a, K, V, I: ArenaIndex, G: GenerationalIndex> DoubleEndedIterator for Iter<'a, K, V, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|(index, slot)| (cast(index), &slot.key, &slot.value))
+    }
+}
+
+impl<'a, K, V, I: ArenaIndex, G: GenerationalIndex> ExactSizeIterator for Iter<'a, K, V, I, G> {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<'a, K, V, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Iter<'a, K, V, I, G> {}
@@ -0,0 +1,355 @@
+//! An arena whose elements are kept in a doubly-linked list, for iteration in
+//! insertion order or in an order chosen by the user.
+
+use super::{AccessError, Arena, ArenaIndex, Entry, GenerationalIndex, Index, Vec};
+use core::iter::FusedIterator;
+use core::ops;
+
+/// The neighbours of the element in a slot, by slot
+#[derive(Clone, Copy, Debug, Default)]
+struct Link {
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// An `Arena` which also keeps its elements in a list, so that they can be
+/// iterated over in a defined order.
+///
+/// Every slot of the arena has links to the slots of the previous and next
+/// elements of the list, so elements can be inserted at either end or next to
+/// another element, moved, and removed, all in constant time. Indices are the
+/// indices of the underlying `Arena`, and are generation-checked the same way.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::OrderedArena;
+///
+/// let mut arena = OrderedArena::<&str>::new();
+/// let b = arena.push_back("b");
+/// let d = arena.push_back("d");
+/// let a = arena.push_front("a");
+/// let c = arena.insert_after(b, "c").unwrap();
+///
+/// let order: Vec<_> = arena.iter().map(|(_, &value)| value).collect();
+/// assert_eq!(order, ["a", "b", "c", "d"]);
+///
+/// arena.move_before(d, a).unwrap();
+/// arena.remove(c);
+/// let order: Vec<_> = arena.iter().map(|(_, &value)| value).collect();
+/// assert_eq!(order, ["d", "a", "b"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OrderedArena<T, I = usize, G = usize> {
+    arena: Arena<T, I, G>,
+    /// The links of each slot, which are only meaningful for occupied slots
+    links: Vec<Link>,
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> OrderedArena<T, I, G> {
+    /// Constructs a new, empty `OrderedArena`.
+    pub fn new() -> OrderedArena<T, I, G> {
+        OrderedArena::with_capacity(0)
+    }
+
+    /// Constructs a new, empty `OrderedArena` able to hold `n` elements without
+    /// further allocation.
+    pub fn with_capacity(n: usize) -> OrderedArena<T, I, G> {
+        OrderedArena {
+            arena: Arena::with_capacity(n),
+            links: Vec::with_capacity(n),
+            head: None,
+            tail: None,
+        }
+    }
+
+    /// Get the number of elements in the arena.
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    /// Is the arena empty?
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty()
+    }
+
+    /// Get the number of elements the arena can hold without further allocation.
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// Is the element at index `i` in the arena?
+    pub fn contains(&self, i: Index<T, I, G>) -> bool {
+        self.arena.contains(i)
+    }
+
+    /// Get a shared reference to the element at index `i`, if it is in the
+    /// arena.
+    pub fn get(&self, i: Index<T, I, G>) -> Option<&T> {
+        self.arena.get(i)
+    }
+
+    /// Get an exclusive reference to the element at index `i`, if it is in the
+    /// arena.
+    pub fn get_mut(&mut self, i: Index<T, I, G>) -> Option<&mut T> {
+        self.arena.get_mut(i)
+    }
+
+    /// Get the index of the first element of the list.
+    pub fn front(&self) -> Option<Index<T, I, G>> {
+        self.head.map(|slot| self.index_at(slot))
+    }
+
+    /// Get the index of the last element of the list.
+    pub fn back(&self) -> Option<Index<T, I, G>> {
+        self.tail.map(|slot| self.index_at(slot))
+    }
+
+    /// Get the index of the element after the element at index `i` in the list,
+    /// or `None` if it is the last one or not in the arena.
+    pub fn next(&self, i: Index<T, I, G>) -> Option<Index<T, I, G>> {
+        let slot = self.slot(i)?;
+        self.links[slot].next.map(|next| self.index_at(next))
+    }
+
+    /// Get the index of the element before the element at index `i` in the list,
+    /// or `None` if it is the first one or not in the arena.
+    pub fn prev(&self, i: Index<T, I, G>) -> Option<Index<T, I, G>> {
+        let slot = self.slot(i)?;
+        self.links[slot].prev.map(|prev| self.index_at(prev))
+    }
+
+    /// Insert `value` at the front of the list, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is full and its index type cannot address any more
+    /// slots, like `Arena::insert`.
+    pub fn push_front(&mut self, value: T) -> Index<T, I, G> {
+        let i = self.arena.insert(value);
+        let head = self.head;
+        self.link(i.index.to_idx(), None, head);
+        i
+    }
+
+    /// Insert `value` at the back of the list, returning its index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is full and its index type cannot address any more
+    /// slots, like `Arena::insert`.
+    pub fn push_back(&mut self, value: T) -> Index<T, I, G> {
+        let i = self.arena.insert(value);
+        let tail = self.tail;
+        self.link(i.index.to_idx(), tail, None);
+        i
+    }
+
+    /// Insert `value` right after the element at index `after` in the list,
+    /// returning its index, or why `after` is not in the arena.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is full and its index type cannot address any more
+    /// slots, like `Arena::insert`.
+    pub fn insert_after(
+        &mut self,
+        after: Index<T, I, G>,
+        value: T,
+    ) -> Result<Index<T, I, G>, AccessError<G>> {
+        self.arena.try_get(after)?;
+        let i = self.arena.insert(value);
+        let prev = after.index.to_idx();
+        let next = self.links[prev].next;
+        self.link(i.index.to_idx(), Some(prev), next);
+        Ok(i)
+    }
+
+    /// Move the element at index `i` right before the element at index `before`
+    /// in the list, or get why one of them is not in the arena.
+    ///
+    /// Moving an element before itself leaves the list unchanged.
+    pub fn move_before(
+        &mut self,
+        i: Index<T, I, G>,
+        before: Index<T, I, G>,
+    ) -> Result<(), AccessError<G>> {
+        self.arena.try_get(i)?;
+        self.arena.try_get(before)?;
+        let (slot, next) = (i.index.to_idx(), before.index.to_idx());
+        if slot != next {
+            self.unlink(slot);
+            let prev = self.links[next].prev;
+            self.link(slot, prev, Some(next));
+        }
+        Ok(())
+    }
+
+    /// Remove the element at index `i` from the arena and the list, returning it,
+    /// or `None` if it is not in the arena.
+    pub fn remove(&mut self, i: Index<T, I, G>) -> Option<T> {
+        let value = self.arena.remove(i)?;
+        self.unlink(i.index.to_idx());
+        Some(value)
+    }
+
+    /// Remove every element from the arena and the list.
+    pub fn clear(&mut self) {
+        self.arena.clear();
+        self.links.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    /// Iterate over the elements of the arena in list order, with their indices.
+    pub fn iter(&self) -> Iter<'_, T, I, G> {
+        Iter {
+            arena: &self.arena,
+            links: &self.links,
+            front: self.head,
+            back: self.tail,
+            len: self.arena.len(),
+        }
+    }
+
+    /// Get the slot of the index `i`, if it refers to an element of the arena
+    fn slot(&self, i: Index<T, I, G>) -> Option<usize> {
+        if self.arena.contains(i) {
+            Some(i.index.to_idx())
+        } else {
+            None
+        }
+    }
+
+    /// Get the index of the element in `slot`, which is in the list
+    fn index_at(&self, slot: usize) -> Index<T, I, G> {
+        occupied(&self.arena, slot).0
+    }
+
+    /// Link the element in `slot` between the elements in the slots `prev` and
+    /// `next`, which are neighbours in the list
+    fn link(&mut self, slot: usize, prev: Option<usize>, next: Option<usize>) {
+        if slot >= self.links.len() {
+            self.links.resize(slot + 1, Link::default());
+        }
+        self.links[slot] = Link { prev, next };
+        match prev {
+            Some(prev) => self.links[prev].next = Some(slot),
+            None => self.head = Some(slot),
+        }
+        match next {
+            Some(next) => self.links[next].prev = Some(slot),
+            None => self.tail = Some(slot),
+        }
+    }
+
+    /// Unlink the element in `slot` from its neighbours in the list
+    fn unlink(&mut self, slot: usize) {
+        let Link { prev, next } = self.links[slot];
+        match prev {
+            Some(prev) => self.links[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.links[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+}
+
+/// Get the index and value of the element in `slot`, which is in the list
+fn occupied<T, I: ArenaIndex, G: GenerationalIndex>(
+    arena: &Arena<T, I, G>,
+    slot: usize,
+) -> (Index<T, I, G>, &T) {
+    match arena.items[slot] {
+        Entry::Occupied {
+            generation,
+            ref value,
+        } => (Index::new(I::from_idx(slot), generation), value),
+        _ => unreachable!("slots in the list are occupied"),
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> Default for OrderedArena<T, I, G> {
+    fn default() -> OrderedArena<T, I, G> {
+        OrderedArena::new()
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> ops::Index<Index<T, I, G>> for OrderedArena<T, I, G> {
+    type Output = T;
+
+    fn index(&self, i: Index<T, I, G>) -> &T {
+        &self.arena[i]
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> ops::IndexMut<Index<T, I, G>>
+    for OrderedArena<T, I, G>
+{
+    fn index_mut(&mut self, i: Index<T, I, G>) -> &mut T {
+        &mut self.arena[i]
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> IntoIterator for &'a OrderedArena<T, I, G> {
+    type Item = (Index<T, I, G>, &'a T);
+    type IntoIter = Iter<'a, T, I, G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of an `OrderedArena` in list order, created by
+/// `OrderedArena::iter`.
+#[derive(Clone, Debug)]
+pub struct Iter<'a, T: 'a, I: 'a, G: 'a> {
+    arena: &'a Arena<T, I, G>,
+    links: &'a [Link],
+    front: Option<usize>,
+    back: Option<usize>,
+    /// The number of elements left, which tells when `front` and `back` have met
+    len: usize,
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> Iterator for Iter<'a, T, I, G> {
+    type Item = (Index<T, I, G>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.front?;
+        self.front = self.links[slot].next;
+        self.len -= 1;
+        Some(occupied(self.arena, slot))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> DoubleEndedIterator for Iter<'a, T, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.back?;
+        self.back = self.links[slot].prev;
+        self.len -= 1;
+        Some(occupied(self.arena, slot))
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> ExactSizeIterator for Iter<'a, T, I, G> {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Iter<'a, T, I, G> {}
@@ -1,4 +1,4 @@
-use super::{Arena, Index, NonzeroGeneration, NonzeroWrapGeneration, NonZeroIndex, DisableRemoval};
+use super::{Arena, Index, IdGeneration, NonzeroGeneration, NonzeroWrapGeneration, NonZeroIndex, DisableRemoval, PerSlotGeneration};
 
 /// An arena of `T` indexed by `usize`, with `2^{64}` generations
 pub type U64Arena<T> = Arena<T, usize, u64>;
@@ -37,6 +37,40 @@ pub type NanoIndex<T> = Index<T, u8, core::num::Wrapping<u8>>;
 pub type PicoArena<T> = Arena<T, u8, NonzeroWrapGeneration<u8>>;
 /// A typed index into a `NanoArena`
 pub type PicoIndex<T> = Index<T, u8, NonzeroWrapGeneration<u8>>;
+/// A standard arena of `T` indexed by `usize`, where every slot has its own
+/// `2^{64} - 1` generations. Indexed by a `StandardIndex`.
+pub type StandardSlotArena<T> = Arena<T, usize, NonzeroGeneration<usize>, PerSlotGeneration>;
+/// An arena which can only hold up to \(2^{32} - 1\) elements, where every slot has
+/// its own \(2^{32} - 1\) generations. Indexed by a `SmallIndex`.
+pub type SmallSlotArena<T> = Arena<T, u32, NonzeroGeneration<u32>, PerSlotGeneration>;
+/// An arena which can only hold up to \(2^{16}\) elements, where every slot has
+/// its own \(2^{16} - 1\) generations. Indexed by a `TinyIndex`.
+pub type TinySlotArena<T> = Arena<T, u16, NonzeroGeneration<u16>, PerSlotGeneration>;
+/// An arena which can only hold up to \(2^{16}\) elements, where every slot has
+/// its own unlimited generations, with the caveat that generations of a slot after
+/// \(2^{16} - 1\) wrap and hence may, with very low probability, collide.
+/// Indexed by a `TinyWrapIndex`.
+pub type TinyWrapSlotArena<T> = Arena<T, u16, NonzeroWrapGeneration<u16>, PerSlotGeneration>;
+/// A standard arena of `T` indexed by `usize`, with `2^{64} - 1` generations, whose
+/// indices carry a 32-bit id of the arena which issued them
+pub type StandardIdArena<T> = Arena<T, usize, IdGeneration<NonzeroGeneration<usize>>>;
+/// A typed index into a `StandardIdArena`
+pub type StandardIdIndex<T> = Index<T, usize, IdGeneration<NonzeroGeneration<usize>>>;
+/// An arena which can only hold up to \(2^{32} - 1\) elements and generations, whose
+/// indices carry a 32-bit id of the arena which issued them
+pub type SmallIdArena<T> = Arena<T, u32, IdGeneration<NonzeroGeneration<u32>>>;
+/// A typed index into a `SmallIdArena`
+pub type SmallIdIndex<T> = Index<T, u32, IdGeneration<NonzeroGeneration<u32>>>;
+/// An arena which can only hold up to \(2^{16}\) elements and \(2^{16} - 1\)
+/// generations, whose indices carry a 16-bit id of the arena which issued them
+pub type TinyIdArena<T> = Arena<T, u16, IdGeneration<NonzeroGeneration<u16>, u16>>;
+/// A typed index into a `TinyIdArena`
+pub type TinyIdIndex<T> = Index<T, u16, IdGeneration<NonzeroGeneration<u16>, u16>>;
+/// A standard arena of `T` indexed by `usize`, where every slot has its own
+/// `2^{64} - 1` generations, whose indices carry a 32-bit id of the arena which
+/// issued them. Indexed by a `StandardIdIndex`.
+pub type StandardIdSlotArena<T> =
+    Arena<T, usize, IdGeneration<NonzeroGeneration<usize>>, PerSlotGeneration>;
 /// A slab arena with a given index, which does *not* support efficient removal
 pub type Slab<T, I> = Arena<T, I, DisableRemoval>;
 /// An index into a slab of type `T` by a certain type
@@ -0,0 +1,251 @@
+use super::{
+    Arena, ArenaIndex, Entry, FixedGenerationalIndex, GenerationStrategy, GenerationalIndex, Index,
+    Vec,
+};
+use rayon::iter::plumbing::UnindexedConsumer;
+use rayon::prelude::*;
+
+// Parallel iterators split the slots of an arena, and skip the free ones in
+// each part. Anything which inserts or removes elements runs sequentially, so
+// that elements get the same indices they would without rayon.
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex, S: GenerationStrategy> Arena<T, I, G, S> {
+    /// Iterate in parallel over shared references to the elements in this
+    /// arena, along with their indices, like `iter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rayon;
+    /// extern crate typed_generational_arena;
+    /// use rayon::prelude::*;
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let arena: StandardArena<u64> = (0..1000).collect();
+    /// let sum: u64 = arena.par_iter().map(|(_, value)| *value).sum();
+    /// assert_eq!(sum, 499500);
+    /// ```
+    pub fn par_iter(&self) -> ParIter<'_, T, I, G> {
+        ParIter { items: &self.items }
+    }
+
+    /// Iterate in parallel over exclusive references to the elements in this
+    /// arena, along with their indices, like `iter_mut`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rayon;
+    /// extern crate typed_generational_arena;
+    /// use rayon::prelude::*;
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena: StandardArena<usize> = (0..1000).collect();
+    /// arena.par_iter_mut().for_each(|(idx, value)| *value = idx.to_idx() * 2);
+    /// assert!(arena.iter().all(|(idx, value)| *value == idx.to_idx() * 2));
+    /// ```
+    pub fn par_iter_mut(&mut self) -> ParIterMut<'_, T, I, G> {
+        ParIterMut {
+            items: &mut self.items,
+        }
+    }
+
+    /// Iterate in parallel over exclusive references to the elements in this
+    /// arena, without their indices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate rayon;
+    /// extern crate typed_generational_arena;
+    /// use rayon::prelude::*;
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena: StandardArena<u32> = (0..1000).collect();
+    /// arena.par_values_mut().for_each(|value| *value += 1);
+    /// assert_eq!(arena.iter().map(|(_, value)| *value).min(), Some(1));
+    /// ```
+    pub fn par_values_mut(&mut self) -> ParValuesMut<'_, T, I, G> {
+        ParValuesMut {
+            items: &mut self.items,
+        }
+    }
+}
+
+impl<T, I, G, S> Arena<T, I, G, S>
+where
+    T: Sync,
+    I: ArenaIndex + Send + Sync,
+    G: GenerationalIndex + Send + Sync,
+    S: GenerationStrategy,
+{
+    /// Retains only the elements specified by the predicate, like `retain`,
+    /// evaluating the predicate in parallel.
+    ///
+    /// Elements are then removed sequentially, in slot order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::StandardArena;
+    ///
+    /// let mut arena: StandardArena<u32> = (0..1000).collect();
+    /// arena.par_retain(|_, value| value % 10 == 0);
+    /// assert_eq!(arena.len(), 100);
+    /// ```
+    pub fn par_retain<F>(&mut self, predicate: F)
+    where
+        F: Fn(Index<T, I, G>, &T) -> bool + Sync,
+    {
+        let removed: Vec<Index<T, I, G>> = self
+            .par_iter()
+            .filter(|&(index, value)| !predicate(index, value))
+            .map(|(index, _)| index)
+            .collect();
+        for index in removed {
+            self.remove(index);
+        }
+    }
+}
+
+impl<T, I, G, S> ParallelExtend<T> for Arena<T, I, G, S>
+where
+    T: Send,
+    I: ArenaIndex,
+    G: FixedGenerationalIndex,
+    S: GenerationStrategy,
+{
+    fn par_extend<P: IntoParallelIterator<Item = T>>(&mut self, par_iter: P) {
+        let values: Vec<T> = par_iter.into_par_iter().collect();
+        self.extend(values);
+    }
+}
+
+impl<T, I, G, S> FromParallelIterator<T> for Arena<T, I, G, S>
+where
+    T: Send,
+    I: ArenaIndex,
+    G: FixedGenerationalIndex,
+    S: GenerationStrategy,
+{
+    fn from_par_iter<P: IntoParallelIterator<Item = T>>(par_iter: P) -> Self {
+        let values: Vec<T> = par_iter.into_par_iter().collect();
+        let mut arena = Arena::with_capacity(values.len().max(1));
+        arena.extend(values);
+        arena
+    }
+}
+
+impl<'a, T, I, G, S> IntoParallelIterator for &'a Arena<T, I, G, S>
+where
+    T: Sync,
+    I: ArenaIndex + Send + Sync,
+    G: FixedGenerationalIndex + Send + Sync,
+    S: GenerationStrategy,
+{
+    type Item = (Index<T, I, G>, &'a T);
+    type Iter = ParIter<'a, T, I, G>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+impl<'a, T, I, G, S> IntoParallelIterator for &'a mut Arena<T, I, G, S>
+where
+    T: Send,
+    I: ArenaIndex + Send + Sync,
+    G: FixedGenerationalIndex + Send + Sync,
+    S: GenerationStrategy,
+{
+    type Item = (Index<T, I, G>, &'a mut T);
+    type Iter = ParIterMut<'a, T, I, G>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter_mut()
+    }
+}
+
+/// A parallel iterator over shared references to the elements of an arena,
+/// obtained from `Arena::par_iter`.
+#[derive(Debug)]
+pub struct ParIter<'a, T: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    items: &'a [Entry<T, I, G>],
+}
+
+impl<'a, T, I, G> ParallelIterator for ParIter<'a, T, I, G>
+where
+    T: Sync,
+    I: ArenaIndex + Send + Sync,
+    G: FixedGenerationalIndex + Send + Sync,
+{
+    type Item = (Index<T, I, G>, &'a T);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.items
+            .par_iter()
+            .enumerate()
+            .filter_map(|(slot, entry)| match entry {
+                Entry::Occupied { generation, value } => {
+                    Some((Index::new(I::from_idx(slot), *generation), value))
+                }
+                _ => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator over exclusive references to the elements of an arena,
+/// obtained from `Arena::par_iter_mut`.
+#[derive(Debug)]
+pub struct ParIterMut<'a, T: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    items: &'a mut [Entry<T, I, G>],
+}
+
+impl<'a, T, I, G> ParallelIterator for ParIterMut<'a, T, I, G>
+where
+    T: Send,
+    I: ArenaIndex + Send + Sync,
+    G: FixedGenerationalIndex + Send + Sync,
+{
+    type Item = (Index<T, I, G>, &'a mut T);
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.items
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(|(slot, entry)| match entry {
+                Entry::Occupied { generation, value } => {
+                    Some((Index::new(I::from_idx(slot), *generation), value))
+                }
+                _ => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator over exclusive references to the elements of an arena,
+/// without their indices, obtained from `Arena::par_values_mut`.
+#[derive(Debug)]
+pub struct ParValuesMut<'a, T: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    items: &'a mut [Entry<T, I, G>],
+}
+
+impl<'a, T, I, G> ParallelIterator for ParValuesMut<'a, T, I, G>
+where
+    T: Send,
+    I: ArenaIndex + Send + Sync,
+    G: FixedGenerationalIndex + Send + Sync,
+{
+    type Item = &'a mut T;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        self.items
+            .par_iter_mut()
+            .filter_map(|entry| match entry {
+                Entry::Occupied { value, .. } => Some(value),
+                _ => None,
+            })
+            .drive_unindexed(consumer)
+    }
+}
@@ -0,0 +1,369 @@
+use super::{Arena, ArenaIndex, GenerationStrategy, GenerationalIndex, Index, SharedGeneration};
+use core::cell::{Ref, RefCell, RefMut};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::vec::Vec;
+
+/// A weak handle to an element of an `RcArena`: a plain `Index`, which does not
+/// keep the element alive, and can be upgraded with `RcArena::upgrade` while it
+/// is.
+pub type WeakHandle<T, I = usize, G = usize> = Index<T, I, G>;
+
+/// An arena whose elements are removed once the last `StrongHandle` to them is
+/// dropped.
+///
+/// Every slot has a count of the strong handles to its element. Cloning a handle
+/// increments it, and dropping a handle decrements it, removing the element with
+/// `Arena::remove` when it reaches zero. If the arena is borrowed at that point,
+/// because of a guard from `get` or `get_mut`, the element is no longer reachable,
+/// and is actually removed the next time the arena is borrowed mutably.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::RcArena;
+///
+/// let arena = RcArena::<&str>::new();
+/// let strong = arena.insert("texture");
+/// let weak = strong.downgrade();
+///
+/// let another = arena.upgrade(weak).unwrap();
+/// assert_eq!(*arena.get(&another), "texture");
+/// assert_eq!(strong.strong_count(), 2);
+///
+/// drop(strong);
+/// drop(another);
+/// assert!(arena.upgrade(weak).is_none());
+/// assert!(arena.is_empty());
+/// ```
+pub struct RcArena<T, I = usize, G = usize, S = SharedGeneration> {
+    shared: Rc<Shared<T, I, G, S>>,
+}
+
+/// The state shared by an `RcArena` and its strong handles
+struct Shared<T, I, G, S> {
+    arena: RefCell<Arena<T, I, G, S>>,
+    /// The number of strong handles to the element in each slot, which is zero for
+    /// free slots and elements waiting to be removed
+    counts: RefCell<Vec<usize>>,
+    /// The elements whose last strong handle was dropped while the arena was
+    /// borrowed
+    pending: RefCell<Vec<Index<T, I, G>>>,
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> Shared<T, I, G, S> {
+    /// Borrow the arena mutably, after removing the pending elements
+    fn arena_mut(&self) -> RefMut<'_, Arena<T, I, G, S>> {
+        let mut arena = self.arena.borrow_mut();
+        loop {
+            let next = self.pending.borrow_mut().pop();
+            match next {
+                // Dropping the element may release more elements, which are
+                // queued since the arena is borrowed
+                Some(index) => drop(arena.remove(index)),
+                None => break,
+            }
+        }
+        arena
+    }
+
+    /// Drop a strong handle to the element at `index`
+    fn release(&self, index: Index<T, I, G>) {
+        let last = {
+            let mut counts = self.counts.borrow_mut();
+            let count = &mut counts[index.to_idx()];
+            *count -= 1;
+            *count == 0
+        };
+        if !last {
+            return;
+        }
+        // The element is dropped once the arena is no longer borrowed, since it may
+        // hold strong handles into this arena itself
+        let _removed = match self.arena.try_borrow_mut() {
+            Ok(mut arena) => arena.remove(index),
+            Err(_) => {
+                self.pending.borrow_mut().push(index);
+                None
+            }
+        };
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> RcArena<T, I, G, S> {
+    /// Constructs a new, empty `RcArena`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::RcArena;
+    ///
+    /// let arena = RcArena::<usize>::new();
+    /// # let _ = arena;
+    /// ```
+    pub fn new() -> RcArena<T, I, G, S> {
+        RcArena::from_arena(Arena::new())
+    }
+
+    /// Constructs a new, empty `RcArena` able to hold `n` elements without further
+    /// allocation.
+    pub fn with_capacity(n: usize) -> RcArena<T, I, G, S> {
+        RcArena::from_arena(Arena::with_capacity(n))
+    }
+
+    fn from_arena(arena: Arena<T, I, G, S>) -> RcArena<T, I, G, S> {
+        RcArena {
+            shared: Rc::new(Shared {
+                arena: RefCell::new(arena),
+                counts: RefCell::new(Vec::new()),
+                pending: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Get the number of elements in the arena which have strong handles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is borrowed mutably by a guard from `get_mut`.
+    pub fn len(&self) -> usize {
+        self.shared.arena.borrow().len() - self.shared.pending.borrow().len()
+    }
+
+    /// Is the arena empty?
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is borrowed mutably by a guard from `get_mut`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert `value` into the arena, returning the first strong handle to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is borrowed by a guard from `get` or `get_mut`, or if
+    /// it is full and its index type cannot address any more slots.
+    pub fn insert(&self, value: T) -> StrongHandle<T, I, G, S> {
+        let index = self.shared.arena_mut().insert(value);
+        let slot = index.to_idx();
+        let mut counts = self.shared.counts.borrow_mut();
+        if slot >= counts.len() {
+            counts.resize(slot + 1, 0);
+        }
+        counts[slot] = 1;
+        StrongHandle {
+            shared: self.shared.clone(),
+            index,
+        }
+    }
+
+    /// Get a new strong handle to the element at index `weak`, or `None` if it was
+    /// removed, or its last strong handle was dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is borrowed mutably by a guard from `get_mut`.
+    pub fn upgrade(&self, weak: WeakHandle<T, I, G>) -> Option<StrongHandle<T, I, G, S>> {
+        if !self.shared.arena.borrow().contains(weak) {
+            return None;
+        }
+        let mut counts = self.shared.counts.borrow_mut();
+        match counts.get_mut(weak.to_idx()) {
+            Some(count) if *count > 0 => *count += 1,
+            _ => return None,
+        }
+        Some(StrongHandle {
+            shared: self.shared.clone(),
+            index: weak,
+        })
+    }
+
+    /// Is the element at index `weak` in the arena, with strong handles to it?
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is borrowed mutably by a guard from `get_mut`.
+    pub fn contains(&self, weak: WeakHandle<T, I, G>) -> bool {
+        self.strong_count(weak) > 0
+    }
+
+    /// Get the number of strong handles to the element at index `weak`, which is
+    /// zero if it is not in the arena.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena is borrowed mutably by a guard from `get_mut`.
+    pub fn strong_count(&self, weak: WeakHandle<T, I, G>) -> usize {
+        if !self.shared.arena.borrow().contains(weak) {
+            return 0;
+        }
+        self.shared
+            .counts
+            .borrow()
+            .get(weak.to_idx())
+            .cloned()
+            .unwrap_or(0)
+    }
+
+    /// Borrow the element a strong handle refers to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` belongs to another arena, or if the arena is borrowed
+    /// mutably by a guard from `get_mut`.
+    pub fn get(&self, handle: &StrongHandle<T, I, G, S>) -> Ref<'_, T> {
+        self.check(handle);
+        Ref::map(self.shared.arena.borrow(), |arena| {
+            match arena.get(handle.index) {
+                Some(value) => value,
+                None => unreachable!("an element with strong handles cannot be removed"),
+            }
+        })
+    }
+
+    /// Borrow the element a strong handle refers to mutably.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` belongs to another arena, or if the arena is borrowed by
+    /// a guard from `get` or `get_mut`.
+    pub fn get_mut(&self, handle: &StrongHandle<T, I, G, S>) -> RefMut<'_, T> {
+        self.check(handle);
+        RefMut::map(self.shared.arena_mut(), |arena| {
+            match arena.get_mut(handle.index) {
+                Some(value) => value,
+                None => unreachable!("an element with strong handles cannot be removed"),
+            }
+        })
+    }
+
+    /// Check that `handle` belongs to this arena
+    fn check(&self, handle: &StrongHandle<T, I, G, S>) {
+        assert!(
+            Rc::ptr_eq(&self.shared, &handle.shared),
+            "strong handle belongs to another arena"
+        );
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> Default
+    for RcArena<T, I, G, S>
+{
+    fn default() -> Self {
+        RcArena::new()
+    }
+}
+
+impl<T, I, G, S> fmt::Debug for RcArena<T, I, G, S>
+where
+    T: fmt::Debug,
+    I: fmt::Debug,
+    G: fmt::Debug,
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RcArena")
+            .field("arena", &self.shared.arena)
+            .field("counts", &self.shared.counts)
+            .finish()
+    }
+}
+
+/// A strong handle to an element of an `RcArena`, which keeps the element in the
+/// arena for as long as it, or one of its clones, exists.
+///
+/// The arena and its elements stay alive while any strong handle does, even
+/// once the `RcArena` itself is dropped.
+pub struct StrongHandle<T, I = usize, G = usize, S = SharedGeneration>
+where
+    I: ArenaIndex,
+    G: GenerationalIndex,
+    S: GenerationStrategy,
+{
+    shared: Rc<Shared<T, I, G, S>>,
+    index: Index<T, I, G>,
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> StrongHandle<T, I, G, S> {
+    /// Get a weak handle to the element, which does not keep it alive.
+    pub fn downgrade(&self) -> WeakHandle<T, I, G> {
+        self.index
+    }
+
+    /// Get the index of the element.
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Get the number of strong handles to the element, including this one.
+    pub fn strong_count(&self) -> usize {
+        self.shared.counts.borrow()[self.index.to_idx()]
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> Clone
+    for StrongHandle<T, I, G, S>
+{
+    fn clone(&self) -> Self {
+        self.shared.counts.borrow_mut()[self.index.to_idx()] += 1;
+        StrongHandle {
+            shared: self.shared.clone(),
+            index: self.index,
+        }
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> Drop
+    for StrongHandle<T, I, G, S>
+{
+    fn drop(&mut self) {
+        self.shared.release(self.index);
+    }
+}
+
+impl<T, I, G, S> PartialEq for StrongHandle<T, I, G, S>
+where
+    I: ArenaIndex + PartialEq,
+    G: GenerationalIndex,
+    S: GenerationStrategy,
+{
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.shared, &other.shared) && self.index == other.index
+    }
+}
+
+impl<T, I, G, S> Eq for StrongHandle<T, I, G, S>
+where
+    I: ArenaIndex + Eq,
+    G: GenerationalIndex,
+    S: GenerationStrategy,
+{
+}
+
+impl<T, I, G, S> Hash for StrongHandle<T, I, G, S>
+where
+    I: ArenaIndex + Hash,
+    G: GenerationalIndex + Hash,
+    S: GenerationStrategy,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T, I, G, S> fmt::Debug for StrongHandle<T, I, G, S>
+where
+    I: ArenaIndex + fmt::Debug,
+    G: GenerationalIndex + fmt::Debug,
+    S: GenerationStrategy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StrongHandle")
+            .field("index", &self.index)
+            .field("strong_count", &self.strong_count())
+            .finish()
+    }
+}
@@ -0,0 +1,540 @@
+//! A dense map from the indices of an `Arena` to values stored outside of it.
+
+use super::{ArenaIndex, FixedGenerationalIndex, Index, Vec};
+use core::fmt;
+use core::iter::{self, Extend, FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops;
+use core::slice;
+
+/// A map from the indices of an `Arena` to values, stored in a `Vec` with one
+/// slot per arena slot.
+///
+/// Every value is stored along with the generation of the index it was
+/// inserted at, so a stale index does not see the value of the element which
+/// has since taken its slot. This makes it cheap to attach extra data to some
+/// or all of the elements of an arena, without storing it in the elements
+/// themselves. Memory use grows with the highest slot used as a key, so
+/// prefer a `SparseSecondaryMap` for data attached to few elements of a large
+/// arena.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{SecondaryMap, StandardArena};
+///
+/// let mut arena = StandardArena::new();
+/// let mut names = SecondaryMap::new();
+/// let a = arena.insert(1.0);
+/// names.insert(a, "a");
+/// assert_eq!(names[a], "a");
+///
+/// // A new element in the same slot does not see the old name
+/// arena.remove(a);
+/// let b = arena.insert(2.0);
+/// assert_eq!(a.to_idx(), b.to_idx());
+/// assert_eq!(names.get(b), None);
+/// ```
+pub struct SecondaryMap<T, V, I = usize, G = usize> {
+    pub(crate) slots: Vec<Option<(G, V)>>,
+    pub(crate) len: usize,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> SecondaryMap<T, V, I, G> {
+    /// Constructs a new, empty `SecondaryMap`.
+    pub fn new() -> SecondaryMap<T, V, I, G> {
+        SecondaryMap::with_capacity(0)
+    }
+
+    /// Constructs a new, empty `SecondaryMap` able to hold values for the
+    /// first `n` slots of an arena without further allocation.
+    pub fn with_capacity(n: usize) -> SecondaryMap<T, V, I, G> {
+        SecondaryMap {
+            slots: Vec::with_capacity(n),
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the number of slots this map can hold values for without further
+    /// allocation.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Get the number of values in this map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is this map empty?
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove every value from this map, keeping its allocation.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    /// Is there a value for the index `i` in this map?
+    pub fn contains_key(&self, i: Index<T, I, G>) -> bool {
+        self.get(i).is_some()
+    }
+
+    /// Get a shared reference to the value for the index `i`, if any.
+    ///
+    /// Returns `None` if there is no value for the slot of `i`, or the value
+    /// was inserted at another generation.
+    pub fn get(&self, i: Index<T, I, G>) -> Option<&V> {
+        match self.slots.get(i.index.to_idx()) {
+            Some(&Some((generation, ref value))) if generation == i.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get an exclusive reference to the value for the index `i`, if any.
+    ///
+    /// Returns `None` if there is no value for the slot of `i`, or the value
+    /// was inserted at another generation.
+    pub fn get_mut(&mut self, i: Index<T, I, G>) -> Option<&mut V> {
+        match self.slots.get_mut(i.index.to_idx()) {
+            Some(&mut Some((generation, ref mut value))) if generation == i.generation => {
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Insert `value` for the index `i`, returning the value it replaces, if
+    /// it was inserted for the same index.
+    ///
+    /// A value for another generation of the same slot is dropped, as the
+    /// element it belonged to has been removed. Generations may wrap around,
+    /// so the map cannot tell a stale index from a live one: only insert
+    /// values for indices which are in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{SecondaryMap, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let mut map = SecondaryMap::new();
+    /// let old = arena.insert(());
+    /// arena.remove(old);
+    /// let new = arena.insert(());
+    ///
+    /// assert_eq!(map.insert(new, 1), None);
+    /// assert_eq!(map.insert(new, 2), Some(1));
+    /// assert_eq!(map.get(old), None);
+    /// assert_eq!(map[new], 2);
+    /// ```
+    pub fn insert(&mut self, i: Index<T, I, G>, value: V) -> Option<V> {
+        match self.entry(i) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Remove the value for the index `i` from this map, if any.
+    pub fn remove(&mut self, i: Index<T, I, G>) -> Option<V> {
+        match self.entry(i) {
+            Entry::Occupied(entry) => Some(entry.remove()),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Get the entry for the index `i`, to insert or update its value in
+    /// place.
+    ///
+    /// The entry is vacant if the slot of `i` holds no value, or a value for
+    /// another generation, which inserting into the entry drops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{SecondaryMap, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let mut visits = SecondaryMap::new();
+    /// let idx = arena.insert("page");
+    ///
+    /// for _ in 0..3 {
+    ///     *visits.entry(idx).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(visits[idx], 3);
+    /// ```
+    pub fn entry(&mut self, i: Index<T, I, G>) -> Entry<'_, T, V, I, G> {
+        let occupied = match self.slots.get(i.index.to_idx()) {
+            Some(&Some((generation, _))) => generation == i.generation,
+            _ => false,
+        };
+        if occupied {
+            Entry::Occupied(OccupiedEntry {
+                map: self,
+                index: i,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                map: self,
+                index: i,
+            })
+        }
+    }
+
+    /// Only keep the values for which `predicate` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{SecondaryMap, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let mut map = SecondaryMap::new();
+    /// for i in 0..10 {
+    ///     map.insert(arena.insert(()), i);
+    /// }
+    /// map.retain(|_, value| *value % 2 == 0);
+    /// assert_eq!(map.len(), 5);
+    /// ```
+    pub fn retain<F: FnMut(Index<T, I, G>, &mut V) -> bool>(&mut self, mut predicate: F) {
+        for (slot, entry) in self.slots.iter_mut().enumerate() {
+            let keep = match *entry {
+                Some((generation, ref mut value)) => {
+                    predicate(Index::new(I::from_idx(slot), generation), value)
+                }
+                None => continue,
+            };
+            if !keep {
+                *entry = None;
+                self.len -= 1;
+            }
+        }
+    }
+
+    /// Iterate over shared references to the values in this map, along with
+    /// their indices, in slot order.
+    pub fn iter(&self) -> Iter<'_, T, V, I, G> {
+        Iter {
+            len: self.len,
+            inner: self.slots.iter().enumerate(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterate over exclusive references to the values in this map, along
+    /// with their indices, in slot order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, V, I, G> {
+        IterMut {
+            len: self.len,
+            inner: self.slots.iter_mut().enumerate(),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn vacate(&mut self, slot: usize) -> V {
+        self.len -= 1;
+        let (_, value) = self.slots[slot].take().expect("slot is occupied");
+        value
+    }
+
+    fn fill(&mut self, i: Index<T, I, G>, value: V) -> &mut V {
+        let slot = i.index.to_idx();
+        if slot >= self.slots.len() {
+            self.slots.resize_with(slot + 1, || None);
+        }
+        if self.slots[slot].is_none() {
+            self.len += 1;
+        }
+        self.slots[slot] = Some((i.generation, value));
+        match self.slots[slot] {
+            Some((_, ref mut value)) => value,
+            None => unreachable!(),
+        }
+    }
+
+    fn value_mut(&mut self, slot: usize) -> &mut V {
+        match self.slots[slot] {
+            Some((_, ref mut value)) => value,
+            None => unreachable!("slot is occupied"),
+        }
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> Default for SecondaryMap<T, V, I, G> {
+    fn default() -> Self {
+        SecondaryMap::new()
+    }
+}
+
+impl<T, V: Clone, I, G: Clone> Clone for SecondaryMap<T, V, I, G> {
+    fn clone(&self) -> Self {
+        SecondaryMap {
+            slots: self.slots.clone(),
+            len: self.len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, V: fmt::Debug, I: ArenaIndex + fmt::Debug, G: FixedGenerationalIndex + fmt::Debug>
+    fmt::Debug for SecondaryMap<T, V, I, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> ops::Index<Index<T, I, G>>
+    for SecondaryMap<T, V, I, G>
+{
+    type Output = V;
+
+    fn index(&self, index: Index<T, I, G>) -> &Self::Output {
+        self.get(index).expect("No value for index")
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> ops::IndexMut<Index<T, I, G>>
+    for SecondaryMap<T, V, I, G>
+{
+    fn index_mut(&mut self, index: Index<T, I, G>) -> &mut Self::Output {
+        self.get_mut(index).expect("No value for index")
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> Extend<(Index<T, I, G>, V)>
+    for SecondaryMap<T, V, I, G>
+{
+    fn extend<It: IntoIterator<Item = (Index<T, I, G>, V)>>(&mut self, iter: It) {
+        for (i, value) in iter {
+            self.insert(i, value);
+        }
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> FromIterator<(Index<T, I, G>, V)>
+    for SecondaryMap<T, V, I, G>
+{
+    fn from_iter<It: IntoIterator<Item = (Index<T, I, G>, V)>>(iter: It) -> Self {
+        let mut map = SecondaryMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator
+    for &'a SecondaryMap<T, V, I, G>
+{
+    type Item = (Index<T, I, G>, &'a V);
+    type IntoIter = Iter<'a, T, V, I, G>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator
+    for &'a mut SecondaryMap<T, V, I, G>
+{
+    type Item = (Index<T, I, G>, &'a mut V);
+    type IntoIter = IterMut<'a, T, V, I, G>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An entry of a `SecondaryMap`, obtained from `SecondaryMap::entry`.
+#[derive(Debug)]
+pub enum Entry<'a, T: 'a, V: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    /// The map holds a value for the index
+    Occupied(OccupiedEntry<'a, T, V, I, G>),
+    /// The map holds no value for the index
+    Vacant(VacantEntry<'a, T, V, I, G>),
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> Entry<'a, T, V, I, G> {
+    /// Get the index of this entry
+    pub fn index(&self) -> Index<T, I, G> {
+        match *self {
+            Entry::Occupied(ref entry) => entry.index,
+            Entry::Vacant(ref entry) => entry.index,
+        }
+    }
+
+    /// Insert `value` if this entry is vacant, and get a reference to the
+    /// value of this entry
+    pub fn or_insert(self, value: V) -> &'a mut V {
+        self.or_insert_with(|| value)
+    }
+
+    /// Insert the value returned by `create` if this entry is vacant, and get
+    /// a reference to the value of this entry
+    pub fn or_insert_with<F: FnOnce() -> V>(self, create: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(create()),
+        }
+    }
+
+    /// Call `modify` on the value of this entry if it is occupied
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, modify: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            modify(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, T, V: Default, I: ArenaIndex, G: FixedGenerationalIndex> Entry<'a, T, V, I, G> {
+    /// Insert the default value if this entry is vacant, and get a reference
+    /// to the value of this entry
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An entry of a `SecondaryMap` which holds a value for its index.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, T: 'a, V: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    map: &'a mut SecondaryMap<T, V, I, G>,
+    index: Index<T, I, G>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> OccupiedEntry<'a, T, V, I, G> {
+    /// Get the index of this entry
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Get a shared reference to the value of this entry
+    pub fn get(&self) -> &V {
+        match self.map.slots[self.index.index.to_idx()] {
+            Some((_, ref value)) => value,
+            None => unreachable!("slot is occupied"),
+        }
+    }
+
+    /// Get an exclusive reference to the value of this entry
+    pub fn get_mut(&mut self) -> &mut V {
+        self.map.value_mut(self.index.index.to_idx())
+    }
+
+    /// Turn this entry into an exclusive reference to its value, borrowing
+    /// from the map
+    pub fn into_mut(self) -> &'a mut V {
+        self.map.value_mut(self.index.index.to_idx())
+    }
+
+    /// Replace the value of this entry, returning the old value
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the value of this entry from the map
+    pub fn remove(self) -> V {
+        self.map.vacate(self.index.index.to_idx())
+    }
+}
+
+/// An entry of a `SecondaryMap` which holds no value for its index.
+#[derive(Debug)]
+pub struct VacantEntry<'a, T: 'a, V: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    map: &'a mut SecondaryMap<T, V, I, G>,
+    index: Index<T, I, G>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> VacantEntry<'a, T, V, I, G> {
+    /// Get the index of this entry
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Insert `value` into this entry, dropping any value for another
+    /// generation of its slot, and get a reference to it
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.fill(self.index, value)
+    }
+}
+
+/// An iterator over shared references to the values of a `SecondaryMap`,
+/// obtained from `SecondaryMap::iter`.
+#[derive(Debug)]
+pub struct Iter<'a, T, V: 'a, I: ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    len: usize,
+    inner: iter::Enumerate<slice::Iter<'a, Option<(G, V)>>>,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for Iter<'a, T, V, I, G> {
+    type Item = (Index<T, I, G>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (slot, entry) in &mut self.inner {
+            if let Some((generation, ref value)) = *entry {
+                self.len -= 1;
+                return Some((Index::new(I::from_idx(slot), generation), value));
+            }
+        }
+        debug_assert_eq!(self.len, 0);
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator
+    for Iter<'a, T, V, I, G>
+{
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for Iter<'a, T, V, I, G> {}
+
+/// An iterator over exclusive references to the values of a `SecondaryMap`,
+/// obtained from `SecondaryMap::iter_mut`.
+#[derive(Debug)]
+pub struct IterMut<'a, T, V: 'a, I: ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    len: usize,
+    inner: iter::Enumerate<slice::IterMut<'a, Option<(G, V)>>>,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for IterMut<'a, T, V, I, G> {
+    type Item = (Index<T, I, G>, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for (slot, entry) in &mut self.inner {
+            if let Some((generation, ref mut value)) = *entry {
+                self.len -= 1;
+                return Some((Index::new(I::from_idx(slot), generation), value));
+            }
+        }
+        debug_assert_eq!(self.len, 0);
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator
+    for IterMut<'a, T, V, I, G>
+{
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for IterMut<'a, T, V, I, G> {}
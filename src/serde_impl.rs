@@ -1,63 +1,167 @@
-use super::{Arena, GenerationalIndex, ArenaIndex, Entry, Vec, DEFAULT_CAPACITY};
+use super::{Arena, BinaryHeap, DisableRemoval, ExhaustionPolicy, ReusePolicy, GenerationalIndex, GenerationStrategy, ArenaIndex, Entry, Vec, DEFAULT_CAPACITY};
+use super::{FixedGenerationalIndex, SecondaryMap};
+#[cfg(feature = "std")]
+use super::SparseSecondaryMap;
+use core::cmp;
 use core::fmt;
 use core::iter;
 use core::marker::PhantomData;
-use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
-use serde::ser::{Serialize, Serializer};
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStruct, SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
 
-impl<T, I, G> Serialize for Arena<T, I, G>
+// Arenas are serialized in a versioned format which records every slot
+// (occupied, free or retired) together with its generation, the arena
+// generation, the order of the free list and the reuse and exhaustion
+// policies, so that a deserialized arena hands out exactly the indices the
+// original would have.
+//
+// Human-readable formats get a struct with named fields. Compact formats get
+// a tuple whose first element is `COMPACT_MARKER`: the legacy format, a plain
+// sequence of `Option<(G, T)>`, starts with its length at that position
+// instead, which lets both be read back without a self-describing format.
+//
+// Slabs never have holes, so they are serialized as the sequence of their
+// values instead.
+//
+// Secondary maps are serialized as the sequence of their values, each as a
+// tuple of its slot, generation and value. Both kinds of map share this
+// format, so either can be read back as the other.
+//
+// Note: do not change an existing version of the format, or it may break
+// forward and backward compatibility of serialized data! Bump
+// `FORMAT_VERSION` instead.
+
+/// The current version of the serialization format
+const FORMAT_VERSION: u32 = 1;
+
+/// The first element of the compact form of the versioned format. No legacy
+/// arena can have this many slots.
+const COMPACT_MARKER: u64 = u64::MAX;
+
+const FIELDS: &[&str] = &[
+    "version",
+    "generation",
+    "free_list",
+    "slots",
+    "reuse_policy",
+    "exhaustion_policy",
+];
+
+/// A serialized slot
+#[derive(Deserialize)]
+enum Slot<G, T> {
+    Occupied(G, T),
+    Free(G),
+    Retired,
+}
+
+/// A serialized slot, borrowed from an arena
+#[derive(Serialize)]
+#[serde(rename = "Slot")]
+enum SlotRef<'a, G: 'a, T: 'a> {
+    Occupied(&'a G, &'a T),
+    Free(&'a G),
+    Retired,
+}
+
+struct Slots<'a, T: 'a, I: 'a, G: 'a>(&'a [Entry<T, I, G>]);
+
+impl<'a, T: Serialize, I, G: Serialize> Serialize for Slots<'a, T, I, G> {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        serializer.collect_seq(self.0.iter().map(|entry| match entry {
+            Entry::Occupied { generation, value } => SlotRef::Occupied(generation, value),
+            Entry::Free { generation, .. } => SlotRef::Free(generation),
+            Entry::Retired => SlotRef::Retired,
+        }))
+    }
+}
+
+impl<T, I, G, S> Serialize for Arena<T, I, G, S>
 where
     T: Serialize,
     I: ArenaIndex,
-    G: GenerationalIndex + Serialize
+    G: GenerationalIndex + Serialize,
+    S: GenerationStrategy,
 {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
     where
-        S: Serializer,
+        Ser: Serializer,
     {
-        // Note: do not change the serialization format, or it may break
-        // forward and backward compatibility of serialized data!
-        serializer.collect_seq(self.items.iter().map(|entry| match entry {
-            Entry::Occupied { generation, value } => Some((generation, value)),
-            Entry::Free { .. } => None,
-        }))
+        let free_list = self.free_slots();
+        let slots = Slots(&self.items);
+        if serializer.is_human_readable() {
+            let mut state = serializer.serialize_struct("Arena", FIELDS.len())?;
+            state.serialize_field("version", &FORMAT_VERSION)?;
+            state.serialize_field("generation", &self.generation)?;
+            state.serialize_field("free_list", &free_list)?;
+            state.serialize_field("slots", &slots)?;
+            state.serialize_field("reuse_policy", &self.reuse)?;
+            state.serialize_field("exhaustion_policy", &self.exhaustion)?;
+            state.end()
+        } else {
+            let mut state = serializer.serialize_tuple(FIELDS.len() + 1)?;
+            state.serialize_element(&COMPACT_MARKER)?;
+            state.serialize_element(&FORMAT_VERSION)?;
+            state.serialize_element(&self.generation)?;
+            state.serialize_element(&free_list)?;
+            state.serialize_element(&slots)?;
+            state.serialize_element(&self.reuse)?;
+            state.serialize_element(&self.exhaustion)?;
+            state.end()
+        }
     }
 }
 
-impl<'de, T, I, G> Deserialize<'de>
-for Arena<T, I, G>
+impl<'de, T, I, G, S> Deserialize<'de>
+for Arena<T, I, G, S>
 where
     T: Deserialize<'de>,
     I: ArenaIndex,
-    G: GenerationalIndex + Deserialize<'de>
+    G: GenerationalIndex + Deserialize<'de>,
+    S: GenerationStrategy,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_seq(ArenaVisitor::new())
+        if deserializer.is_human_readable() {
+            // The versioned format is a map, the legacy one a sequence
+            deserializer.deserialize_struct("Arena", FIELDS, ArenaVisitor::new(false))
+        } else {
+            // The length of the sequence is only known once we know which
+            // format we are reading
+            deserializer.deserialize_tuple(usize::MAX, ArenaVisitor::new(true))
+        }
     }
 }
 
-struct ArenaVisitor<T, I: ArenaIndex, G: GenerationalIndex> {
-    marker: PhantomData<fn() -> Arena<T, I, G>>,
+#[allow(clippy::type_complexity)]
+struct ArenaVisitor<T, I, G, S> {
+    compact: bool,
+    marker: PhantomData<fn() -> Arena<T, I, G, S>>,
 }
 
-impl<T, I: ArenaIndex, G: GenerationalIndex> ArenaVisitor<T, I, G> {
-    fn new() -> Self {
+impl<T, I: ArenaIndex, G: GenerationalIndex, S: GenerationStrategy> ArenaVisitor<T, I, G, S> {
+    fn new(compact: bool) -> Self {
         Self {
+            compact,
             marker: PhantomData,
         }
     }
 }
 
-impl<'de, T, I, G> Visitor<'de> for ArenaVisitor<T, I, G>
+impl<'de, T, I, G, S> Visitor<'de> for ArenaVisitor<T, I, G, S>
 where
     T: Deserialize<'de>,
     I: ArenaIndex,
-    G: GenerationalIndex + Deserialize<'de>
+    G: GenerationalIndex + Deserialize<'de>,
+    S: GenerationStrategy,
 {
-    type Value = Arena<T, I, G>;
+    type Value = Arena<T, I, G, S>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
         write!(formatter, "a generational arena")
@@ -67,49 +171,496 @@ where
     where
         M: SeqAccess<'de>,
     {
-        let init_cap = access.size_hint().unwrap_or(DEFAULT_CAPACITY);
-        let mut items = Vec::with_capacity(init_cap);
-
-        let mut generation = G::first_generation();
-        while let Some(element) = access.next_element::<Option<(G, T)>>()? {
-            let item = match element {
-                Some((gen, value)) => {
-                    generation = if generation.generation_lt(&gen) { gen } else { generation };
-                    Entry::Occupied {
-                        generation: gen,
-                        value,
-                    }
+        if !self.compact {
+            let init_cap = access.size_hint().unwrap_or(DEFAULT_CAPACITY);
+            return legacy_arena(access, init_cap, None);
+        }
+        let marker: u64 = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        if marker != COMPACT_MARKER {
+            // A legacy arena, `marker` being its number of slots
+            let len = marker as usize;
+            return legacy_arena(access, cmp::min(len, 4096), Some(len));
+        }
+        let version: u32 = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        check_version(version)?;
+        let generation = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        let free_list = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(3, &self))?;
+        let slots = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(4, &self))?;
+        let reuse = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(5, &self))?;
+        let exhaustion = access
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(6, &self))?;
+        versioned_arena(generation, free_list, slots, reuse, exhaustion)
+    }
+
+    fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut generation = None;
+        let mut free_list = None;
+        let mut slots = None;
+        let mut reuse = None;
+        let mut exhaustion = None;
+        while let Some(key) = access.next_key::<Field>()? {
+            match key {
+                Field::Version => {
+                    let value = access.next_value()?;
+                    check_version(value)?;
+                    version = Some(value);
                 }
-                None => Entry::Free { next_free: None },
-            };
-            items.push(item);
+                Field::Generation => generation = Some(access.next_value()?),
+                Field::FreeList => free_list = Some(access.next_value()?),
+                Field::Slots => slots = Some(access.next_value()?),
+                Field::ReusePolicy => reuse = Some(access.next_value()?),
+                Field::ExhaustionPolicy => exhaustion = Some(access.next_value()?),
+            }
+        }
+        version.ok_or_else(|| de::Error::missing_field("version"))?;
+        versioned_arena(
+            generation.ok_or_else(|| de::Error::missing_field("generation"))?,
+            free_list.ok_or_else(|| de::Error::missing_field("free_list"))?,
+            slots.ok_or_else(|| de::Error::missing_field("slots"))?,
+            reuse.ok_or_else(|| de::Error::missing_field("reuse_policy"))?,
+            exhaustion.ok_or_else(|| de::Error::missing_field("exhaustion_policy"))?,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    Version,
+    Generation,
+    FreeList,
+    Slots,
+    ReusePolicy,
+    ExhaustionPolicy,
+}
+
+fn check_version<E: de::Error>(version: u32) -> Result<(), E> {
+    if version == FORMAT_VERSION {
+        Ok(())
+    } else {
+        Err(E::invalid_value(
+            de::Unexpected::Unsigned(version.into()),
+            &"a supported arena format version",
+        ))
+    }
+}
+
+/// Get the number of slots an arena with indices of type `I` can have
+fn max_slots<I: ArenaIndex>() -> usize {
+    I::max_idx().saturating_add(1)
+}
+
+/// Check that an arena with `len` slots can be addressed by indices of type `I`
+fn check_slot_count<I: ArenaIndex, E: de::Error>(len: usize) -> Result<(), E> {
+    if len > max_slots::<I>() {
+        Err(E::invalid_length(
+            len,
+            &"no more slots than the index type can address",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Build an arena from the versioned format, checking that the index type can
+/// address every slot, and that the free list links every free slot exactly
+/// once
+fn versioned_arena<T, I, G, S, E>(
+    generation: G,
+    free_list: Vec<usize>,
+    slots: Vec<Slot<G, T>>,
+    reuse: ReusePolicy,
+    exhaustion: ExhaustionPolicy,
+) -> Result<Arena<T, I, G, S>, E>
+where
+    I: ArenaIndex,
+    G: GenerationalIndex,
+    S: GenerationStrategy,
+    E: de::Error,
+{
+    check_slot_count::<I, E>(slots.len())?;
+    let mut items: Vec<Entry<T, I, G>> = slots
+        .into_iter()
+        .map(|slot| match slot {
+            Slot::Occupied(generation, value) => Entry::Occupied { generation, value },
+            Slot::Free(generation) => Entry::Free {
+                next_free: None,
+                generation,
+            },
+            Slot::Retired => Entry::Retired,
+        })
+        .collect();
+
+    let mut linked = Vec::new();
+    linked.resize(items.len(), false);
+    for (i, &idx) in free_list.iter().enumerate() {
+        match items.get_mut(idx) {
+            Some(Entry::Free { next_free, .. }) if !linked[idx] => {
+                *next_free = free_list.get(i + 1).map(|&next| I::from_idx(next));
+                linked[idx] = true;
+            }
+            _ => return Err(E::custom("free list does not match the free slots of the arena")),
         }
+    }
 
-        // items.len() must be same as item.capacity(), so fill the unused elements with Free.
-        if items.len() + 1 < items.capacity() {
-            let add_cap = items.capacity() - (items.len() + 1);
-            items.reserve_exact(add_cap);
-            items.extend(iter::repeat_with(|| Entry::Free { next_free: None }).take(add_cap));
-            debug_assert_eq!(items.len(), items.capacity());
+    let mut len = 0;
+    let mut retired = 0;
+    for (idx, entry) in items.iter().enumerate() {
+        match entry {
+            Entry::Occupied { .. } => len += 1,
+            Entry::Retired => retired += 1,
+            Entry::Free { .. } if !linked[idx] => {
+                return Err(E::custom("free list does not match the free slots of the arena"))
+            }
+            Entry::Free { .. } => {}
         }
+    }
+
+    let mut arena = Arena {
+        items,
+        generation,
+        free_list_head: free_list.first().map(|&idx| I::from_idx(idx)),
+        free_list_tail: free_list.last().map(|&idx| I::from_idx(idx)),
+        lowest_free: BinaryHeap::new(),
+        reuse: ReusePolicy::default(),
+        len,
+        exhaustion,
+        retired,
+        _strategy: PhantomData,
+    };
+    // The free list is linked in reuse order, which `LowestFirst` keeps in a
+    // heap instead
+    arena.set_reuse_policy(reuse);
+    Ok(arena)
+}
+
+/// Build an arena from the legacy format, a sequence of `Option<(G, T)>`
+/// holding `len` elements if known.
+///
+/// The legacy format does not record the generations of removed elements, so
+/// the arena generation is moved past the greatest generation in use, and free
+/// slots never hand out an index equal to one from before serialization.
+fn legacy_arena<'de, T, I, G, S, M>(
+    mut access: M,
+    init_cap: usize,
+    len: Option<usize>,
+) -> Result<Arena<T, I, G, S>, M::Error>
+where
+    T: Deserialize<'de>,
+    I: ArenaIndex,
+    G: GenerationalIndex + Deserialize<'de>,
+    S: GenerationStrategy,
+    M: SeqAccess<'de>,
+{
+    let mut items = Vec::with_capacity(init_cap);
 
-        let mut free_list_head = None;
-        let mut len = items.len();
-        // Iterates `arena.items` in reverse order so that free_list concatenates
-        // indices in ascending order.
-        for (idx, entry) in items.iter_mut().enumerate().rev() {
-            if let Entry::Free { next_free } = entry {
-                *next_free = free_list_head;
-                free_list_head = Some(I::from_idx(idx));
-                len -= 1;
+    let mut generation = G::first_generation();
+    loop {
+        if len == Some(items.len()) {
+            break;
+        }
+        let element = match access.next_element::<Option<(G, T)>>()? {
+            Some(element) => element,
+            None if len.is_none() => break,
+            None => return Err(de::Error::invalid_length(items.len(), &"a legacy arena")),
+        };
+        check_slot_count::<I, M::Error>(items.len() + 1)?;
+        let item = match element {
+            Some((gen, value)) => {
+                generation = if generation.generation_lt(&gen) { gen } else { generation };
+                Entry::Occupied {
+                    generation: gen,
+                    value,
+                }
             }
+            None => Entry::Free {
+                next_free: None,
+                generation: G::first_generation(),
+            },
+        };
+        items.push(item);
+    }
+
+    // items.len() must be same as item.capacity(), so fill the unused elements with Free.
+    if items.len() + 1 < items.capacity() {
+        let add_cap = items.capacity() - (items.len() + 1);
+        items.reserve_exact(add_cap);
+        items.extend(
+            iter::repeat_with(|| Entry::Free {
+                next_free: None,
+                generation: G::first_generation(),
+            })
+            .take(add_cap),
+        );
+        debug_assert_eq!(items.len(), items.capacity());
+    }
+    // Padding never adds slots the index type cannot address
+    items.truncate(max_slots::<I>());
+    generation.increment_generation();
+
+    let mut len = items.len();
+    for entry in items.iter_mut() {
+        if let Entry::Free { generation: free_generation, .. } = entry {
+            *free_generation = generation;
+            len -= 1;
         }
+    }
 
-        Ok(Arena {
-            items,
-            generation,
-            free_list_head,
-            len,
+    let mut arena = Arena {
+        items,
+        generation,
+        free_list_head: None,
+        free_list_tail: None,
+        lowest_free: BinaryHeap::new(),
+        reuse: ReusePolicy::default(),
+        len,
+        exhaustion: ExhaustionPolicy::default(),
+        retired: 0,
+        _strategy: PhantomData,
+    };
+    arena.rebuild_free_list();
+    Ok(arena)
+}
+
+impl<T, I, S> Serialize for Arena<T, I, DisableRemoval, S>
+where
+    T: Serialize,
+    I: ArenaIndex,
+    S: GenerationStrategy,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len))?;
+        for entry in &self.items {
+            if let Entry::Occupied { value, .. } = entry {
+                seq.serialize_element(value)?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T, I, S> Deserialize<'de> for Arena<T, I, DisableRemoval, S>
+where
+    T: Deserialize<'de>,
+    I: ArenaIndex,
+    S: GenerationStrategy,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SlabVisitor {
+            marker: PhantomData,
         })
     }
 }
+
+#[allow(clippy::type_complexity)]
+struct SlabVisitor<T, I, S> {
+    marker: PhantomData<fn() -> Arena<T, I, DisableRemoval, S>>,
+}
+
+impl<'de, T, I, S> Visitor<'de> for SlabVisitor<T, I, S>
+where
+    T: Deserialize<'de>,
+    I: ArenaIndex,
+    S: GenerationStrategy,
+{
+    type Value = Arena<T, I, DisableRemoval, S>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of slab values")
+    }
+
+    fn visit_seq<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: SeqAccess<'de>,
+    {
+        // Do not trust the size hint with more than a modest allocation
+        let init_cap = cmp::min(access.size_hint().unwrap_or(DEFAULT_CAPACITY), 4096);
+        let mut slab = Arena::with_capacity(init_cap);
+        while let Some(value) = access.next_element()? {
+            slab.insert(value);
+        }
+        Ok(slab)
+    }
+}
+
+impl<T, V, I, G> Serialize for SecondaryMap<T, V, I, G>
+where
+    V: Serialize,
+    I: ArenaIndex,
+    G: FixedGenerationalIndex + Serialize,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        serializer.collect_seq(
+            self.iter()
+                .map(|(i, value)| (i.index.to_idx(), i.generation, value)),
+        )
+    }
+}
+
+impl<'de, T, V, I, G> Deserialize<'de> for SecondaryMap<T, V, I, G>
+where
+    V: Deserialize<'de>,
+    I: ArenaIndex,
+    G: FixedGenerationalIndex + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SecondaryVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, V, I, G> Serialize for SparseSecondaryMap<T, V, I, G>
+where
+    V: Serialize,
+    I: ArenaIndex,
+    G: FixedGenerationalIndex + Serialize,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        serializer.collect_seq(
+            self.iter()
+                .map(|(i, value)| (i.index.to_idx(), i.generation, value)),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, T, V, I, G> Deserialize<'de> for SparseSecondaryMap<T, V, I, G>
+where
+    V: Deserialize<'de>,
+    I: ArenaIndex,
+    G: FixedGenerationalIndex + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SecondaryVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+/// A secondary map being deserialized
+trait SecondarySlots: Default {
+    type Index: ArenaIndex;
+    type Generation;
+    type Value;
+
+    /// Store `value` at the given slot and generation, unless the slot already
+    /// holds a value. Returns whether `value` was stored.
+    fn fill_slot(&mut self, slot: usize, generation: Self::Generation, value: Self::Value) -> bool;
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> SecondarySlots for SecondaryMap<T, V, I, G> {
+    type Index = I;
+    type Generation = G;
+    type Value = V;
+
+    fn fill_slot(&mut self, slot: usize, generation: G, value: V) -> bool {
+        if slot >= self.slots.len() {
+            self.slots.resize_with(slot + 1, || None);
+        } else if self.slots[slot].is_some() {
+            return false;
+        }
+        self.slots[slot] = Some((generation, value));
+        self.len += 1;
+        true
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> SecondarySlots
+    for SparseSecondaryMap<T, V, I, G>
+{
+    type Index = I;
+    type Generation = G;
+    type Value = V;
+
+    fn fill_slot(&mut self, slot: usize, generation: G, value: V) -> bool {
+        match self.slots.entry(slot) {
+            std::collections::hash_map::Entry::Occupied(_) => false,
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert((generation, value));
+                true
+            }
+        }
+    }
+}
+
+struct SecondaryVisitor<M> {
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<'de, M> Visitor<'de> for SecondaryVisitor<M>
+where
+    M: SecondarySlots,
+    M::Generation: Deserialize<'de>,
+    M::Value: Deserialize<'de>,
+{
+    type Value = M;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a sequence of secondary map values with their slot and generation"
+        )
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut map = M::default();
+        while let Some((slot, generation, value)) =
+            access.next_element::<(usize, M::Generation, M::Value)>()?
+        {
+            if slot > M::Index::max_idx() {
+                return Err(de::Error::invalid_value(
+                    de::Unexpected::Unsigned(slot as u64),
+                    &"a slot the index type can address",
+                ));
+            }
+            if !map.fill_slot(slot, generation, value) {
+                return Err(de::Error::custom(format_args!(
+                    "duplicate slot {} in secondary map",
+                    slot
+                )));
+            }
+        }
+        Ok(map)
+    }
+}
@@ -0,0 +1,391 @@
+use super::{AccessError, FixedGenerationalIndex, GenerationalIndex, LockError};
+use core::ops::Deref;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::boxed::Box;
+use std::sync::{
+    LockResult, Mutex, MutexGuard, OnceLock, PoisonError, RwLock, TryLockError, TryLockResult,
+};
+use std::thread_local;
+use std::vec::Vec;
+
+// Storage for the arenas which insert and remove elements through a shared
+// reference. Slots live in segments which are allocated on first use and never
+// move, so a slot can be borrowed while other threads hand out new ones.
+
+/// The number of slots in the first segment. Every following segment is twice
+/// as large as the one before.
+const FIRST_SEGMENT: usize = 64;
+
+/// The number of segments needed to address every `usize` slot
+const SEGMENTS: usize = (usize::BITS - FIRST_SEGMENT.trailing_zeros()) as usize;
+
+/// A slot which keeps its own generation
+#[derive(Debug)]
+pub(crate) struct Slot<T, G> {
+    pub(crate) generation: G,
+    pub(crate) value: Option<T>,
+}
+
+impl<T, G: FixedGenerationalIndex> Slot<T, G> {
+    /// A vacant slot at generation `generation`
+    pub(crate) fn vacant(generation: G) -> Slot<T, G> {
+        Slot {
+            generation,
+            value: None,
+        }
+    }
+
+    /// Check that an index into slot `slot` with generation `generation` refers
+    /// to the element in this slot
+    pub(crate) fn check(&self, slot: usize, generation: G) -> Result<(), AccessError<G>> {
+        AccessError::check_arena(&self.generation, &generation)?;
+        if self.value.is_none() {
+            Err(AccessError::Vacant { slot })
+        } else if self.generation == generation {
+            Ok(())
+        } else if generation.generation_lt(&self.generation) {
+            Err(AccessError::Stale {
+                slot,
+                current: self.generation,
+                requested: generation,
+            })
+        } else {
+            Err(AccessError::Newer {
+                slot,
+                current: self.generation,
+                requested: generation,
+            })
+        }
+    }
+}
+
+/// Check that an index into slot `slot` with generation `generation` refers to
+/// the element of the locked slot `entry`, and that no thread panicked while
+/// holding a guard to it
+pub(crate) fn checked<E, T, G>(
+    entry: LockResult<E>,
+    slot: usize,
+    generation: G,
+) -> Result<E, LockError<G>>
+where
+    E: Deref<Target = Slot<T, G>>,
+    G: FixedGenerationalIndex,
+{
+    let (entry, poisoned) = match entry {
+        Ok(entry) => (entry, false),
+        Err(poisoned) => (poisoned.into_inner(), true),
+    };
+    entry.check(slot, generation)?;
+    if poisoned {
+        Err(LockError::Poisoned)
+    } else {
+        Ok(entry)
+    }
+}
+
+/// Get the result of locking a slot from the result of trying to, unless locking
+/// it would block
+pub(crate) fn would_block<E, G>(result: TryLockResult<E>) -> Result<LockResult<E>, LockError<G>> {
+    match result {
+        Ok(entry) => Ok(Ok(entry)),
+        Err(TryLockError::Poisoned(poisoned)) => Ok(Err(poisoned)),
+        Err(TryLockError::WouldBlock) => Err(LockError::WouldBlock),
+    }
+}
+
+/// A lock around a `Slot`, through which an arena accesses its elements
+pub(crate) trait SlotLock<G>: Sized {
+    /// The type of the elements
+    type Value;
+
+    /// A lock around a vacant slot at generation `generation`
+    fn vacant(generation: G) -> Self;
+
+    /// Lock the slot for writing, and call `f` on it, even if the lock is
+    /// poisoned: `f` only fills a vacant slot or takes its element out, which a
+    /// half updated element cannot get in the way of
+    fn with_slot<R>(&self, f: impl FnOnce(&mut Slot<Self::Value, G>) -> R) -> R;
+
+    /// Mark the lock as no longer poisoned, once its element is taken out
+    fn clear_poison(&self);
+
+    /// Get the slot without locking, since the lock is borrowed exclusively
+    fn slot_mut(&mut self) -> LockResult<&mut Slot<Self::Value, G>>;
+}
+
+impl<T, G: FixedGenerationalIndex> SlotLock<G> for Mutex<Slot<T, G>> {
+    type Value = T;
+
+    fn vacant(generation: G) -> Self {
+        Mutex::new(Slot::vacant(generation))
+    }
+
+    fn with_slot<R>(&self, f: impl FnOnce(&mut Slot<T, G>) -> R) -> R {
+        f(&mut self.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn clear_poison(&self) {
+        Mutex::clear_poison(self)
+    }
+
+    fn slot_mut(&mut self) -> LockResult<&mut Slot<T, G>> {
+        self.get_mut()
+    }
+}
+
+impl<T, G: FixedGenerationalIndex> SlotLock<G> for RwLock<Slot<T, G>> {
+    type Value = T;
+
+    fn vacant(generation: G) -> Self {
+        RwLock::new(Slot::vacant(generation))
+    }
+
+    fn with_slot<R>(&self, f: impl FnOnce(&mut Slot<T, G>) -> R) -> R {
+        f(&mut self.write().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    fn clear_poison(&self) {
+        RwLock::clear_poison(self)
+    }
+
+    fn slot_mut(&mut self) -> LockResult<&mut Slot<T, G>> {
+        self.get_mut()
+    }
+}
+
+/// The slots of an arena, each behind a lock of type `L`, along with the free
+/// slots among them
+pub(crate) struct Slots<L, G> {
+    segments: Segments<L>,
+    /// The generation slots start at, which carries the id of the arena for
+    /// generation types which have one
+    first: G,
+    /// The number of slots handed out so far
+    allocated: AtomicUsize,
+    /// The number of slots holding an element
+    len: AtomicUsize,
+    /// The free slots, spread over several lists so that threads inserting and
+    /// removing elements do not all wait for the same one
+    free_lists: Box<[Mutex<Vec<usize>>]>,
+    /// The number of slots the index type of the arena can address
+    max_capacity: usize,
+}
+
+impl<L, G> Slots<L, G> {
+    /// Get the number of slots holding an element
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    /// Get the number of slots handed out so far
+    pub(crate) fn capacity(&self) -> usize {
+        self.allocated.load(Ordering::Acquire)
+    }
+}
+
+impl<L: SlotLock<G>, G: GenerationalIndex> Slots<L, G> {
+    /// Slots for `max_capacity` elements, with `free_lists` free lists and every
+    /// segment needed to hold `n` elements allocated
+    pub(crate) fn new(n: usize, free_lists: usize, max_capacity: usize) -> Slots<L, G> {
+        let slots = Slots {
+            segments: Segments::new(),
+            first: G::arena_first_generation(),
+            allocated: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+            free_lists: (0..free_lists).map(|_| Mutex::new(Vec::new())).collect(),
+            max_capacity,
+        };
+        let first = slots.first;
+        slots
+            .segments
+            .reserve(n.min(max_capacity), || L::vacant(first));
+        slots
+    }
+
+    /// Get the number of slots the index type of the arena can address
+    pub(crate) fn max_capacity(&self) -> usize {
+        self.max_capacity
+    }
+
+    /// Put `value` in a free slot, returning the slot and its generation, or give
+    /// `value` back if the index type cannot address any more slots
+    pub(crate) fn insert(&self, value: L::Value) -> Result<(usize, G), L::Value> {
+        let slot = match self.pop_free().or_else(|| self.allocate()) {
+            Some(slot) => slot,
+            None => return Err(value),
+        };
+        // The slot belongs to this thread now, so it is filled without holding any
+        // free list
+        let generation = self.get(slot).with_slot(|entry| {
+            debug_assert!(entry.value.is_none());
+            entry.value = Some(value);
+            entry.generation
+        });
+        self.len.fetch_add(1, Ordering::AcqRel);
+        Ok((slot, generation))
+    }
+
+    /// Take the element out of slot `slot`, if its generation is `generation`,
+    /// even if a thread panicked while holding a guard to it
+    pub(crate) fn remove(&self, slot: usize, generation: G) -> Option<L::Value> {
+        if slot >= self.capacity() {
+            return None;
+        }
+        let entry_lock = self.get(slot);
+        let (value, reusable) = entry_lock.with_slot(|entry| {
+            entry.check(slot, generation).ok()?;
+            let value = entry.value.take()?;
+            Some((value, entry.generation.checked_increment_generation()))
+        })?;
+        // The half updated element is gone, so the next one starts afresh
+        entry_lock.clear_poison();
+        self.len.fetch_sub(1, Ordering::AcqRel);
+        // A slot whose generation is exhausted is retired, so that its last index
+        // never refers to another element
+        if reusable {
+            let shard = thread_hint() % self.free_lists.len();
+            lock(&self.free_lists[shard]).push(slot);
+        }
+        Some(value)
+    }
+
+    /// Get the lock of slot `slot` for an index with generation `generation`, or
+    /// the reason the index cannot refer to an element of the arena
+    pub(crate) fn lookup(&self, slot: usize, generation: &G) -> Result<&L, AccessError<G>> {
+        AccessError::check_arena(&self.first, generation)?;
+        let capacity = self.capacity();
+        if slot >= capacity {
+            return Err(AccessError::OutOfBounds { slot, capacity });
+        }
+        Ok(self.get(slot))
+    }
+
+    /// Get an exclusive reference to the element in slot `slot`, if its
+    /// generation is `generation` and its lock is not poisoned, without locking
+    pub(crate) fn get_mut(&mut self, slot: usize, generation: G) -> Option<&mut L::Value> {
+        if slot >= self.capacity() {
+            return None;
+        }
+        let entry = self.segments.get_mut(slot)?.slot_mut().ok()?;
+        match entry.value {
+            Some(ref mut value) if entry.generation == generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the lock of slot `slot`, which must have been handed out
+    fn get(&self, slot: usize) -> &L {
+        let first = self.first;
+        self.segments.get(slot, || L::vacant(first))
+    }
+
+    /// Pop a free slot, trying the free list of the current thread first
+    fn pop_free(&self) -> Option<usize> {
+        let shards = self.free_lists.len();
+        let hint = thread_hint();
+        // Do not wait for the free lists other threads are busy with
+        for i in 0..shards {
+            let shard = (hint + i) % shards;
+            let free = if i == 0 {
+                Some(lock(&self.free_lists[shard]))
+            } else {
+                self.free_lists[shard].try_lock().ok()
+            };
+            if let Some(slot) = free.and_then(|mut free| free.pop()) {
+                return Some(slot);
+            }
+        }
+        None
+    }
+
+    /// Hand out a slot which was never used, or wait on every free list if the
+    /// index space is exhausted
+    fn allocate(&self) -> Option<usize> {
+        let max = self.max_capacity;
+        match self
+            .allocated
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                if n < max {
+                    Some(n + 1)
+                } else {
+                    None
+                }
+            }) {
+            Ok(slot) => Some(slot),
+            Err(_) => self
+                .free_lists
+                .iter()
+                .filter_map(|free| lock(free).pop())
+                .next(),
+        }
+    }
+}
+
+/// Lock the free list `free`, ignoring poisoning: no code which could panic runs
+/// while a free list is locked, besides pushing and popping, which leave it
+/// consistent
+fn lock(free: &Mutex<Vec<usize>>) -> MutexGuard<'_, Vec<usize>> {
+    free.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Get a number identifying the current thread, which spreads threads over the
+/// free lists
+fn thread_hint() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static HINT: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    HINT.with(|hint| *hint)
+}
+
+/// Segmented storage for slots of type `L`, which never move once allocated
+pub(crate) struct Segments<L> {
+    segments: Box<[OnceLock<Box<[L]>>]>,
+}
+
+impl<L> Segments<L> {
+    /// Storage with no segment allocated yet
+    pub(crate) fn new() -> Segments<L> {
+        Segments {
+            segments: (0..SEGMENTS).map(|_| OnceLock::new()).collect(),
+        }
+    }
+
+    /// Allocate every segment needed to hold `n` slots
+    pub(crate) fn reserve(&self, n: usize, init: impl Fn() -> L) {
+        if n > 0 {
+            let (last, _) = locate(n - 1);
+            for segment in 0..=last {
+                self.segment(segment, &init);
+            }
+        }
+    }
+
+    /// Get slot `slot`, allocating its segment with `init` if needed
+    pub(crate) fn get(&self, slot: usize, init: impl Fn() -> L) -> &L {
+        let (segment, offset) = locate(slot);
+        &self.segment(segment, init)[offset]
+    }
+
+    /// Get slot `slot`, if its segment was allocated
+    pub(crate) fn get_mut(&mut self, slot: usize) -> Option<&mut L> {
+        let (segment, offset) = locate(slot);
+        self.segments[segment]
+            .get_mut()
+            .map(|segment| &mut segment[offset])
+    }
+
+    /// Get segment `segment`, initializing it if needed
+    fn segment(&self, segment: usize, init: impl Fn() -> L) -> &[L] {
+        self.segments[segment]
+            .get_or_init(|| (0..FIRST_SEGMENT << segment).map(|_| init()).collect())
+    }
+}
+
+/// Get the segment and the offset in it of slot `slot`
+fn locate(slot: usize) -> (usize, usize) {
+    let position = slot / FIRST_SEGMENT + 1;
+    let segment = (usize::BITS - 1 - position.leading_zeros()) as usize;
+    let start = FIRST_SEGMENT * ((1 << segment) - 1);
+    (segment, slot - start)
+}
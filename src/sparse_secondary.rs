@@ -0,0 +1,453 @@
+//! A sparse, hash-backed map from the indices of an `Arena` to values stored
+//! outside of it.
+
+use super::{ArenaIndex, FixedGenerationalIndex, Index};
+use core::fmt;
+use core::iter::{Extend, FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::mem;
+use core::ops;
+use std::collections::hash_map::{self, HashMap};
+
+/// A map from the indices of an `Arena` to values, stored in a `HashMap` keyed
+/// by slot.
+///
+/// Like a `SecondaryMap`, every value is stored along with the generation of
+/// the index it was inserted at, so a stale index does not see the value of
+/// the element which has since taken its slot. Memory use grows with the
+/// number of values rather than with the highest slot used as a key, at the
+/// cost of hashing on every access, and iteration is in no particular order.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{SparseSecondaryMap, StandardArena};
+///
+/// let mut arena = StandardArena::new();
+/// let indices: Vec<_> = (0..1000).map(|i| arena.insert(i)).collect();
+///
+/// let mut selected = SparseSecondaryMap::new();
+/// selected.insert(indices[999], "last");
+/// assert_eq!(selected.len(), 1);
+/// assert_eq!(selected[indices[999]], "last");
+///
+/// arena.remove(indices[999]);
+/// let idx = arena.insert(1000);
+/// assert_eq!(selected.get(idx), None);
+/// ```
+pub struct SparseSecondaryMap<T, V, I = usize, G = usize> {
+    pub(crate) slots: HashMap<usize, (G, V)>,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> SparseSecondaryMap<T, V, I, G> {
+    /// Constructs a new, empty `SparseSecondaryMap`.
+    pub fn new() -> SparseSecondaryMap<T, V, I, G> {
+        SparseSecondaryMap::with_capacity(0)
+    }
+
+    /// Constructs a new, empty `SparseSecondaryMap` able to hold `n` values
+    /// without further allocation.
+    pub fn with_capacity(n: usize) -> SparseSecondaryMap<T, V, I, G> {
+        SparseSecondaryMap {
+            slots: HashMap::with_capacity(n),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the number of values this map can hold without further
+    /// allocation.
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
+    }
+
+    /// Get the number of values in this map.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Is this map empty?
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Remove every value from this map, keeping its allocation.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Is there a value for the index `i` in this map?
+    pub fn contains_key(&self, i: Index<T, I, G>) -> bool {
+        self.get(i).is_some()
+    }
+
+    /// Get a shared reference to the value for the index `i`, if any.
+    ///
+    /// Returns `None` if there is no value for the slot of `i`, or the value
+    /// was inserted at another generation.
+    pub fn get(&self, i: Index<T, I, G>) -> Option<&V> {
+        match self.slots.get(&i.index.to_idx()) {
+            Some(&(generation, ref value)) if generation == i.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get an exclusive reference to the value for the index `i`, if any.
+    ///
+    /// Returns `None` if there is no value for the slot of `i`, or the value
+    /// was inserted at another generation.
+    pub fn get_mut(&mut self, i: Index<T, I, G>) -> Option<&mut V> {
+        match self.slots.get_mut(&i.index.to_idx()) {
+            Some(&mut (generation, ref mut value)) if generation == i.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Insert `value` for the index `i`, returning the value it replaces, if
+    /// it was inserted for the same index.
+    ///
+    /// A value for another generation of the same slot is dropped, as the
+    /// element it belonged to has been removed. Generations may wrap around,
+    /// so the map cannot tell a stale index from a live one: only insert
+    /// values for indices which are in the arena.
+    pub fn insert(&mut self, i: Index<T, I, G>, value: V) -> Option<V> {
+        match self.entry(i) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Remove the value for the index `i` from this map, if any.
+    pub fn remove(&mut self, i: Index<T, I, G>) -> Option<V> {
+        match self.entry(i) {
+            Entry::Occupied(entry) => Some(entry.remove()),
+            Entry::Vacant(_) => None,
+        }
+    }
+
+    /// Get the entry for the index `i`, to insert or update its value in
+    /// place.
+    ///
+    /// The entry is vacant if the slot of `i` holds no value, or a value for
+    /// another generation, which inserting into the entry drops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{SparseSecondaryMap, StandardArena};
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let mut visits = SparseSecondaryMap::new();
+    /// let idx = arena.insert("page");
+    ///
+    /// for _ in 0..3 {
+    ///     *visits.entry(idx).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(visits[idx], 3);
+    /// ```
+    pub fn entry(&mut self, i: Index<T, I, G>) -> Entry<'_, T, V, I, G> {
+        match self.slots.entry(i.index.to_idx()) {
+            hash_map::Entry::Occupied(inner) if inner.get().0 == i.generation => {
+                Entry::Occupied(OccupiedEntry { inner, index: i })
+            }
+            inner => Entry::Vacant(VacantEntry { inner, index: i }),
+        }
+    }
+
+    /// Only keep the values for which `predicate` returns `true`.
+    pub fn retain<F: FnMut(Index<T, I, G>, &mut V) -> bool>(&mut self, mut predicate: F) {
+        self.slots
+            .retain(|&slot, &mut (generation, ref mut value)| {
+                predicate(Index::new(I::from_idx(slot), generation), value)
+            })
+    }
+
+    /// Iterate over shared references to the values in this map, along with
+    /// their indices, in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T, V, I, G> {
+        Iter {
+            inner: self.slots.iter(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Iterate over exclusive references to the values in this map, along
+    /// with their indices, in arbitrary order.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, V, I, G> {
+        IterMut {
+            inner: self.slots.iter_mut(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> Default for SparseSecondaryMap<T, V, I, G> {
+    fn default() -> Self {
+        SparseSecondaryMap::new()
+    }
+}
+
+impl<T, V: Clone, I, G: Clone> Clone for SparseSecondaryMap<T, V, I, G> {
+    fn clone(&self) -> Self {
+        SparseSecondaryMap {
+            slots: self.slots.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, V: fmt::Debug, I: ArenaIndex + fmt::Debug, G: FixedGenerationalIndex + fmt::Debug>
+    fmt::Debug for SparseSecondaryMap<T, V, I, G>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> ops::Index<Index<T, I, G>>
+    for SparseSecondaryMap<T, V, I, G>
+{
+    type Output = V;
+
+    fn index(&self, index: Index<T, I, G>) -> &Self::Output {
+        self.get(index).expect("No value for index")
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> ops::IndexMut<Index<T, I, G>>
+    for SparseSecondaryMap<T, V, I, G>
+{
+    fn index_mut(&mut self, index: Index<T, I, G>) -> &mut Self::Output {
+        self.get_mut(index).expect("No value for index")
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> Extend<(Index<T, I, G>, V)>
+    for SparseSecondaryMap<T, V, I, G>
+{
+    fn extend<It: IntoIterator<Item = (Index<T, I, G>, V)>>(&mut self, iter: It) {
+        for (i, value) in iter {
+            self.insert(i, value);
+        }
+    }
+}
+
+impl<T, V, I: ArenaIndex, G: FixedGenerationalIndex> FromIterator<(Index<T, I, G>, V)>
+    for SparseSecondaryMap<T, V, I, G>
+{
+    fn from_iter<It: IntoIterator<Item = (Index<T, I, G>, V)>>(iter: It) -> Self {
+        let mut map = SparseSecondaryMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator
+    for &'a SparseSecondaryMap<T, V, I, G>
+{
+    type Item = (Index<T, I, G>, &'a V);
+    type IntoIter = Iter<'a, T, V, I, G>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> IntoIterator
+    for &'a mut SparseSecondaryMap<T, V, I, G>
+{
+    type Item = (Index<T, I, G>, &'a mut V);
+    type IntoIter = IterMut<'a, T, V, I, G>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An entry of a `SparseSecondaryMap`, obtained from
+/// `SparseSecondaryMap::entry`.
+#[derive(Debug)]
+pub enum Entry<'a, T: 'a, V: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    /// The map holds a value for the index
+    Occupied(OccupiedEntry<'a, T, V, I, G>),
+    /// The map holds no value for the index
+    Vacant(VacantEntry<'a, T, V, I, G>),
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> Entry<'a, T, V, I, G> {
+    /// Get the index of this entry
+    pub fn index(&self) -> Index<T, I, G> {
+        match *self {
+            Entry::Occupied(ref entry) => entry.index,
+            Entry::Vacant(ref entry) => entry.index,
+        }
+    }
+
+    /// Insert `value` if this entry is vacant, and get a reference to the
+    /// value of this entry
+    pub fn or_insert(self, value: V) -> &'a mut V {
+        self.or_insert_with(|| value)
+    }
+
+    /// Insert the value returned by `create` if this entry is vacant, and get
+    /// a reference to the value of this entry
+    pub fn or_insert_with<F: FnOnce() -> V>(self, create: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(create()),
+        }
+    }
+
+    /// Call `modify` on the value of this entry if it is occupied
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, modify: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            modify(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, T, V: Default, I: ArenaIndex, G: FixedGenerationalIndex> Entry<'a, T, V, I, G> {
+    /// Insert the default value if this entry is vacant, and get a reference
+    /// to the value of this entry
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An entry of a `SparseSecondaryMap` which holds a value for its index.
+#[derive(Debug)]
+pub struct OccupiedEntry<'a, T: 'a, V: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    inner: hash_map::OccupiedEntry<'a, usize, (G, V)>,
+    index: Index<T, I, G>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> OccupiedEntry<'a, T, V, I, G> {
+    /// Get the index of this entry
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Get a shared reference to the value of this entry
+    pub fn get(&self) -> &V {
+        &self.inner.get().1
+    }
+
+    /// Get an exclusive reference to the value of this entry
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.inner.get_mut().1
+    }
+
+    /// Turn this entry into an exclusive reference to its value, borrowing
+    /// from the map
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.inner.into_mut().1
+    }
+
+    /// Replace the value of this entry, returning the old value
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the value of this entry from the map
+    pub fn remove(self) -> V {
+        self.inner.remove().1
+    }
+}
+
+/// An entry of a `SparseSecondaryMap` which holds no value for its index.
+#[derive(Debug)]
+pub struct VacantEntry<'a, T: 'a, V: 'a, I: 'a + ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    /// The entry of the slot, which is occupied if it holds a value for
+    /// another generation
+    inner: hash_map::Entry<'a, usize, (G, V)>,
+    index: Index<T, I, G>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> VacantEntry<'a, T, V, I, G> {
+    /// Get the index of this entry
+    pub fn index(&self) -> Index<T, I, G> {
+        self.index
+    }
+
+    /// Insert `value` into this entry, dropping any value for another
+    /// generation of its slot, and get a reference to it
+    pub fn insert(self, value: V) -> &'a mut V {
+        let generation = self.index.generation;
+        let slot = match self.inner {
+            hash_map::Entry::Occupied(mut inner) => {
+                inner.insert((generation, value));
+                inner.into_mut()
+            }
+            hash_map::Entry::Vacant(inner) => inner.insert((generation, value)),
+        };
+        &mut slot.1
+    }
+}
+
+/// An iterator over shared references to the values of a
+/// `SparseSecondaryMap`, obtained from `SparseSecondaryMap::iter`.
+#[derive(Debug)]
+pub struct Iter<'a, T, V: 'a, I: ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    inner: hash_map::Iter<'a, usize, (G, V)>,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for Iter<'a, T, V, I, G> {
+    type Item = (Index<T, I, G>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(&slot, &(generation, ref value))| {
+            (Index::new(I::from_idx(slot), generation), value)
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator
+    for Iter<'a, T, V, I, G>
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for Iter<'a, T, V, I, G> {}
+
+/// An iterator over exclusive references to the values of a
+/// `SparseSecondaryMap`, obtained from `SparseSecondaryMap::iter_mut`.
+#[derive(Debug)]
+pub struct IterMut<'a, T, V: 'a, I: ArenaIndex, G: 'a + FixedGenerationalIndex> {
+    inner: hash_map::IterMut<'a, usize, (G, V)>,
+    _phantom: PhantomData<fn() -> (T, I)>,
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> Iterator for IterMut<'a, T, V, I, G> {
+    type Item = (Index<T, I, G>, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(&slot, &mut (generation, ref mut value))| {
+                (Index::new(I::from_idx(slot), generation), value)
+            })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> ExactSizeIterator
+    for IterMut<'a, T, V, I, G>
+{
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'a, T, V, I: ArenaIndex, G: FixedGenerationalIndex> FusedIterator for IterMut<'a, T, V, I, G> {}
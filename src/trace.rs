@@ -0,0 +1,342 @@
+use super::{
+    Arena, ArenaIndex, Entry, FixedGenerationalIndex, GenerationStrategy, GenerationalIndex, Index,
+    Vec,
+};
+use core::cell::{Cell, RefCell};
+use core::fmt;
+use core::marker::PhantomData;
+use core::num::Wrapping;
+
+cfg_if! {
+    if #[cfg(feature = "std")] {
+        use std::boxed::Box;
+        use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+        use std::string::String;
+    } else {
+        use alloc::boxed::Box;
+        use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+        use alloc::string::String;
+    }
+}
+
+/// A type whose values may hold indices into an arena of `T`, which
+/// `Arena::collect_garbage` follows to find the elements still in use.
+///
+/// `Index<T, I, G>` reports itself, and containers report the indices their
+/// contents hold. Types which hold no indices, like numbers and strings, report
+/// nothing for any arena. With the `derive` feature, `Trace` can be derived for
+/// structs and enums whose fields implement it.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::{NonzeroGeneration, StandardIndex, Trace, Tracer};
+///
+/// struct Node {
+///     label: String,
+///     edges: Vec<StandardIndex<Node>>,
+/// }
+///
+/// impl Trace<Node, usize, NonzeroGeneration<usize>> for Node {
+///     fn trace(&self, tracer: &mut Tracer<Node, usize, NonzeroGeneration<usize>>) {
+///         self.edges.trace(tracer);
+///     }
+/// }
+/// ```
+pub trait Trace<T, I = usize, G = usize> {
+    /// Report every index into an arena of `T` this value holds to `tracer`.
+    fn trace(&self, tracer: &mut Tracer<T, I, G>);
+}
+
+/// Collects the indices reported by `Trace::trace` during
+/// `Arena::collect_garbage`.
+pub struct Tracer<T, I = usize, G = usize> {
+    /// The generation of the element in each slot of the arena, until it is
+    /// reached
+    unreached: Vec<Option<G>>,
+    /// The indices reached whose element was not traced yet
+    pending: Vec<Index<T, I, G>>,
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Tracer<T, I, G> {
+    /// Mark the element at `index` as reachable, so that it is kept, and the
+    /// indices it holds are traced in turn.
+    ///
+    /// Indices which are not in the arena are ignored, so a stale index never
+    /// stands in for the element now in its slot.
+    pub fn mark(&mut self, index: Index<T, I, G>) {
+        let slot = index.index.to_idx();
+        match self.unreached.get(slot) {
+            Some(&Some(generation)) if generation == index.generation => {
+                self.unreached[slot] = None;
+                self.pending.push(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<T, I, G> fmt::Debug for Tracer<T, I, G>
+where
+    I: ArenaIndex + fmt::Debug,
+    G: FixedGenerationalIndex + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Tracer")
+            .field("unreached", &self.unreached)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+impl<T, I, G, S> Arena<T, I, G, S>
+where
+    T: Trace<T, I, G>,
+    I: ArenaIndex,
+    G: GenerationalIndex,
+    S: GenerationStrategy,
+{
+    /// Remove every element which cannot be reached from `roots`, by following the
+    /// indices the elements hold, returning the indices of the removed elements in
+    /// slot order.
+    ///
+    /// Elements are removed with `remove`, so their indices become stale. Stale
+    /// roots, and stale indices held by elements, are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use typed_generational_arena::{NonzeroGeneration, StandardArena, StandardIndex, Trace, Tracer};
+    ///
+    /// struct Node(Option<StandardIndex<Node>>);
+    ///
+    /// impl Trace<Node, usize, NonzeroGeneration<usize>> for Node {
+    ///     fn trace(&self, tracer: &mut Tracer<Node, usize, NonzeroGeneration<usize>>) {
+    ///         self.0.trace(tracer);
+    ///     }
+    /// }
+    ///
+    /// let mut arena = StandardArena::new();
+    /// let tail = arena.insert(Node(None));
+    /// let head = arena.insert(Node(Some(tail)));
+    /// // A cycle which nothing points to
+    /// let a = arena.insert(Node(None));
+    /// let b = arena.insert(Node(Some(a)));
+    /// arena[a].0 = Some(b);
+    ///
+    /// assert_eq!(arena.collect_garbage(vec![head]), vec![a, b]);
+    /// assert!(arena.contains(head) && arena.contains(tail));
+    /// assert_eq!(arena.collect_garbage(None), vec![tail, head]);
+    /// ```
+    pub fn collect_garbage<R>(&mut self, roots: R) -> Vec<Index<T, I, G>>
+    where
+        R: IntoIterator<Item = Index<T, I, G>>,
+    {
+        let mut tracer = Tracer {
+            unreached: self
+                .items
+                .iter()
+                .map(|entry| match entry {
+                    Entry::Occupied { generation, .. } => Some(*generation),
+                    _ => None,
+                })
+                .collect(),
+            pending: Vec::new(),
+        };
+        for root in roots {
+            tracer.mark(root);
+        }
+        while let Some(index) = tracer.pending.pop() {
+            if let Some(value) = self.get(index) {
+                value.trace(&mut tracer);
+            }
+        }
+
+        let garbage: Vec<_> = self
+            .iter()
+            .map(|(index, _)| index)
+            .filter(|index| tracer.unreached[index.index.to_idx()].is_some())
+            .collect();
+        for &index in &garbage {
+            self.remove(index);
+        }
+        garbage
+    }
+}
+
+impl<T, I: ArenaIndex, G: FixedGenerationalIndex> Trace<T, I, G> for Index<T, I, G> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        tracer.mark(*self)
+    }
+}
+
+macro_rules! impl_trace_leaf {
+    ($($leaf:ty),*) => {
+        $(
+            impl<T, I, G> Trace<T, I, G> for $leaf {
+                #[inline(always)]
+                fn trace(&self, _tracer: &mut Tracer<T, I, G>) {}
+            }
+        )*
+    };
+}
+
+impl_trace_leaf!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    str,
+    String
+);
+
+impl<T, I, G, X> Trace<T, I, G> for PhantomData<X> {
+    #[inline(always)]
+    fn trace(&self, _tracer: &mut Tracer<T, I, G>) {}
+}
+
+impl<T, I, G, X: Trace<T, I, G>> Trace<T, I, G> for Wrapping<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        self.0.trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G> + ?Sized> Trace<T, I, G> for &X {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        (**self).trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G> + ?Sized> Trace<T, I, G> for Box<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        (**self).trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G> + Copy> Trace<T, I, G> for Cell<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        self.get().trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G> + ?Sized> Trace<T, I, G> for RefCell<X> {
+    /// # Panics
+    ///
+    /// Panics if the value is borrowed mutably.
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        self.borrow().trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>> Trace<T, I, G> for Option<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        if let Some(value) = self {
+            value.trace(tracer)
+        }
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>, E: Trace<T, I, G>> Trace<T, I, G> for Result<X, E> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        match self {
+            Ok(value) => value.trace(tracer),
+            Err(error) => error.trace(tracer),
+        }
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>> Trace<T, I, G> for [X] {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        for value in self {
+            value.trace(tracer)
+        }
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>, const N: usize> Trace<T, I, G> for [X; N] {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        self[..].trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>> Trace<T, I, G> for Vec<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        self[..].trace(tracer)
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>> Trace<T, I, G> for VecDeque<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        for value in self {
+            value.trace(tracer)
+        }
+    }
+}
+
+impl<T, I, G, X: Trace<T, I, G>> Trace<T, I, G> for BTreeSet<X> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        for value in self {
+            value.trace(tracer)
+        }
+    }
+}
+
+impl<T, I, G, K: Trace<T, I, G>, V: Trace<T, I, G>> Trace<T, I, G> for BTreeMap<K, V> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        for (key, value) in self {
+            key.trace(tracer);
+            value.trace(tracer);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, I, G, X: Trace<T, I, G>, H> Trace<T, I, G> for HashSet<X, H> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        for value in self {
+            value.trace(tracer)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, I, G, K: Trace<T, I, G>, V: Trace<T, I, G>, H> Trace<T, I, G> for HashMap<K, V, H> {
+    fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+        for (key, value) in self {
+            key.trace(tracer);
+            value.trace(tracer);
+        }
+    }
+}
+
+macro_rules! impl_trace_tuple {
+    ($($name:ident),+) => {
+        impl<T, I, G, $($name: Trace<T, I, G>),+> Trace<T, I, G> for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn trace(&self, tracer: &mut Tracer<T, I, G>) {
+                let ($(ref $name,)+) = *self;
+                $($name.trace(tracer);)+
+            }
+        }
+    };
+}
+
+impl_trace_tuple!(A);
+impl_trace_tuple!(A, B);
+impl_trace_tuple!(A, B, C);
+impl_trace_tuple!(A, B, C, D);
+impl_trace_tuple!(A, B, C, D, E);
+impl_trace_tuple!(A, B, C, D, E, F);
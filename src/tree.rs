@@ -0,0 +1,503 @@
+//! A tree whose nodes are the elements of an `Arena`, linked to their parent,
+//! children and siblings by index.
+
+use super::{AccessError, Arena, ArenaIndex, GenerationalIndex, Index, TreeError, Vec};
+use core::iter::FusedIterator;
+use core::ops;
+
+/// The identifier of a node of an `ArenaTree` holding values of type `T`: a
+/// plain `Index`, so that a node which was removed is never confused with a
+/// later node in the same slot.
+pub type NodeId<T, I = usize, G = usize> = Index<T, I, G>;
+
+/// A node of the tree: its value, and the links to its neighbours
+#[derive(Clone, Debug)]
+struct Node<T, I, G> {
+    value: T,
+    parent: Option<NodeId<T, I, G>>,
+    first_child: Option<NodeId<T, I, G>>,
+    last_child: Option<NodeId<T, I, G>>,
+    prev_sibling: Option<NodeId<T, I, G>>,
+    next_sibling: Option<NodeId<T, I, G>>,
+}
+
+/// A forest of trees whose nodes are stored in an `Arena`.
+///
+/// Every node holds a value, and is either a root or the child of another node.
+/// The children of a node are ordered. Nodes are identified by `NodeId`s, which
+/// are checked against the generation of their slot like any other index, so
+/// that operations on a removed node fail rather than affect another node.
+///
+/// # Examples
+///
+/// ```
+/// use typed_generational_arena::tree::ArenaTree;
+///
+/// let mut tree = ArenaTree::<&str>::new();
+/// let root = tree.insert("root");
+/// let a = tree.insert("a");
+/// let b = tree.insert("b");
+/// let c = tree.insert("c");
+/// tree.append_child(root, a).unwrap();
+/// tree.append_child(root, c).unwrap();
+/// tree.insert_before(c, b).unwrap();
+///
+/// let children: Vec<_> = tree.children(root).map(|id| tree[id]).collect();
+/// assert_eq!(children, ["a", "b", "c"]);
+/// assert_eq!(tree.parent(b), Some(root));
+///
+/// assert_eq!(tree.remove_subtree(root), ["root", "a", "b", "c"]);
+/// assert!(tree.is_empty());
+/// assert_eq!(tree.get(a), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ArenaTree<T, I = usize, G = usize> {
+    nodes: Arena<Node<T, I, G>, I, G>,
+}
+
+/// Get the index of the node with the identifier `id` in the arena of nodes
+#[inline]
+fn key<T, I: ArenaIndex, G: GenerationalIndex>(id: NodeId<T, I, G>) -> Index<Node<T, I, G>, I, G> {
+    Index::new(id.index, id.generation)
+}
+
+/// Are `a` and `b` the same node?
+#[inline]
+fn same<T, I: ArenaIndex, G: GenerationalIndex>(a: NodeId<T, I, G>, b: NodeId<T, I, G>) -> bool {
+    a.index.to_idx() == b.index.to_idx() && a.generation == b.generation
+}
+
+/// Get the identifier of the node at index `i` in the arena of nodes
+#[inline]
+fn id<T, I: ArenaIndex, G: GenerationalIndex>(i: Index<Node<T, I, G>, I, G>) -> NodeId<T, I, G> {
+    Index::new(i.index, i.generation)
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> ArenaTree<T, I, G> {
+    /// Constructs a new, empty `ArenaTree`.
+    pub fn new() -> ArenaTree<T, I, G> {
+        ArenaTree {
+            nodes: Arena::new(),
+        }
+    }
+
+    /// Constructs a new, empty `ArenaTree` able to hold `n` nodes without
+    /// further allocation.
+    pub fn with_capacity(n: usize) -> ArenaTree<T, I, G> {
+        ArenaTree {
+            nodes: Arena::with_capacity(n),
+        }
+    }
+
+    /// Get the number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Is the tree empty?
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Get the number of nodes the tree can hold without further allocation.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Insert `value` into the tree as a new root, returning its identifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the arena of nodes is full and its index type cannot address
+    /// any more slots, like `Arena::insert`.
+    pub fn insert(&mut self, value: T) -> NodeId<T, I, G> {
+        id(self.nodes.insert(Node {
+            value,
+            parent: None,
+            first_child: None,
+            last_child: None,
+            prev_sibling: None,
+            next_sibling: None,
+        }))
+    }
+
+    /// Is the node `id` in the tree?
+    pub fn contains(&self, id: NodeId<T, I, G>) -> bool {
+        self.nodes.contains(key(id))
+    }
+
+    /// Get a shared reference to the value of the node `id`, or `None` if it is
+    /// not in the tree.
+    pub fn get(&self, id: NodeId<T, I, G>) -> Option<&T> {
+        self.nodes.get(key(id)).map(|node| &node.value)
+    }
+
+    /// Get an exclusive reference to the value of the node `id`, or `None` if it
+    /// is not in the tree.
+    pub fn get_mut(&mut self, id: NodeId<T, I, G>) -> Option<&mut T> {
+        self.nodes.get_mut(key(id)).map(|node| &mut node.value)
+    }
+
+    /// Get the parent of the node `id`, or `None` if it is a root or not in the
+    /// tree.
+    pub fn parent(&self, id: NodeId<T, I, G>) -> Option<NodeId<T, I, G>> {
+        self.nodes.get(key(id))?.parent
+    }
+
+    /// Get the first child of the node `id`, or `None` if it has no children or
+    /// is not in the tree.
+    pub fn first_child(&self, id: NodeId<T, I, G>) -> Option<NodeId<T, I, G>> {
+        self.nodes.get(key(id))?.first_child
+    }
+
+    /// Get the last child of the node `id`, or `None` if it has no children or
+    /// is not in the tree.
+    pub fn last_child(&self, id: NodeId<T, I, G>) -> Option<NodeId<T, I, G>> {
+        self.nodes.get(key(id))?.last_child
+    }
+
+    /// Get the sibling before the node `id`, or `None` if it is the first child
+    /// of its parent, a root, or not in the tree.
+    pub fn prev_sibling(&self, id: NodeId<T, I, G>) -> Option<NodeId<T, I, G>> {
+        self.nodes.get(key(id))?.prev_sibling
+    }
+
+    /// Get the sibling after the node `id`, or `None` if it is the last child of
+    /// its parent, a root, or not in the tree.
+    pub fn next_sibling(&self, id: NodeId<T, I, G>) -> Option<NodeId<T, I, G>> {
+        self.nodes.get(key(id))?.next_sibling
+    }
+
+    /// Make the node `child` the last child of the node `parent`, detaching it
+    /// from its current parent first, along with its descendants.
+    ///
+    /// Fails without changing the tree if either node is not in the tree, or if
+    /// `child` is `parent` or one of its ancestors.
+    pub fn append_child(
+        &mut self,
+        parent: NodeId<T, I, G>,
+        child: NodeId<T, I, G>,
+    ) -> Result<(), TreeError<G>> {
+        self.check_move(child, parent)?;
+        self.unlink(child);
+        let last = self.node(parent).last_child;
+        {
+            let node = self.node_mut(child);
+            node.parent = Some(parent);
+            node.prev_sibling = last;
+        }
+        match last {
+            Some(last) => self.node_mut(last).next_sibling = Some(child),
+            None => self.node_mut(parent).first_child = Some(child),
+        }
+        self.node_mut(parent).last_child = Some(child);
+        Ok(())
+    }
+
+    /// Make the node `node` the sibling right before the node `sibling`,
+    /// detaching it from its current parent first, along with its descendants.
+    ///
+    /// Fails without changing the tree if either node is not in the tree, if
+    /// `sibling` is a root, or if `node` is `sibling` or one of its ancestors.
+    pub fn insert_before(
+        &mut self,
+        sibling: NodeId<T, I, G>,
+        node: NodeId<T, I, G>,
+    ) -> Result<(), TreeError<G>> {
+        self.check_move(node, sibling)?;
+        let parent = match self.node(sibling).parent {
+            Some(parent) => parent,
+            None => return Err(TreeError::Root),
+        };
+        self.unlink(node);
+        let prev = self.node(sibling).prev_sibling;
+        {
+            let node = self.node_mut(node);
+            node.parent = Some(parent);
+            node.prev_sibling = prev;
+            node.next_sibling = Some(sibling);
+        }
+        match prev {
+            Some(prev) => self.node_mut(prev).next_sibling = Some(node),
+            None => self.node_mut(parent).first_child = Some(node),
+        }
+        self.node_mut(sibling).prev_sibling = Some(node);
+        Ok(())
+    }
+
+    /// Detach the node `id` from its parent, along with its descendants, making
+    /// it a root.
+    pub fn detach(&mut self, id: NodeId<T, I, G>) -> Result<(), AccessError<G>> {
+        self.nodes.try_get(key(id))?;
+        self.unlink(id);
+        Ok(())
+    }
+
+    /// Remove the node `id` and all of its descendants from the tree, returning
+    /// their values in preorder, which is empty if the node is not in the tree.
+    pub fn remove_subtree(&mut self, id: NodeId<T, I, G>) -> Vec<T> {
+        if !self.contains(id) {
+            return Vec::new();
+        }
+        self.unlink(id);
+        let removed: Vec<_> = self.preorder(id).collect();
+        removed
+            .into_iter()
+            .filter_map(|id| self.nodes.remove(key(id)))
+            .map(|node| node.value)
+            .collect()
+    }
+
+    /// Iterate over the ancestors of the node `id`, from its parent up to its
+    /// root.
+    pub fn ancestors(&self, id: NodeId<T, I, G>) -> Ancestors<'_, T, I, G> {
+        Ancestors {
+            tree: self,
+            next: self.parent(id),
+        }
+    }
+
+    /// Iterate over the children of the node `id`, in order.
+    pub fn children(&self, id: NodeId<T, I, G>) -> Children<'_, T, I, G> {
+        Children {
+            tree: self,
+            next: self.first_child(id),
+        }
+    }
+
+    /// Iterate over the descendants of the node `id` in preorder, not including
+    /// the node itself.
+    pub fn descendants(&self, id: NodeId<T, I, G>) -> Descendants<'_, T, I, G> {
+        let mut preorder = self.preorder(id);
+        preorder.next();
+        Descendants { preorder }
+    }
+
+    /// Iterate over the node `id` and its descendants in preorder: every node
+    /// comes before its children, which come in order.
+    pub fn preorder(&self, id: NodeId<T, I, G>) -> Preorder<'_, T, I, G> {
+        Preorder {
+            tree: self,
+            root: id,
+            next: if self.contains(id) { Some(id) } else { None },
+        }
+    }
+
+    /// Iterate over the node `id` and its descendants in postorder: every node
+    /// comes after its children, which come in order.
+    pub fn postorder(&self, id: NodeId<T, I, G>) -> Postorder<'_, T, I, G> {
+        Postorder {
+            tree: self,
+            root: id,
+            next: if self.contains(id) {
+                Some(self.first_leaf(id))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Check that `node` can be moved next to or below `target`
+    fn check_move(
+        &self,
+        node: NodeId<T, I, G>,
+        target: NodeId<T, I, G>,
+    ) -> Result<(), TreeError<G>> {
+        self.nodes.try_get(key(node))?;
+        self.nodes.try_get(key(target))?;
+        if same(node, target) || self.ancestors(target).any(|ancestor| same(ancestor, node)) {
+            return Err(TreeError::Cycle);
+        }
+        Ok(())
+    }
+
+    /// Unlink the node `id` from its parent and siblings
+    fn unlink(&mut self, id: NodeId<T, I, G>) {
+        let (parent, prev, next) = {
+            let node = self.node_mut(id);
+            (
+                node.parent.take(),
+                node.prev_sibling.take(),
+                node.next_sibling.take(),
+            )
+        };
+        match prev {
+            Some(prev) => self.node_mut(prev).next_sibling = next,
+            None => {
+                if let Some(parent) = parent {
+                    self.node_mut(parent).first_child = next;
+                }
+            }
+        }
+        match next {
+            Some(next) => self.node_mut(next).prev_sibling = prev,
+            None => {
+                if let Some(parent) = parent {
+                    self.node_mut(parent).last_child = prev;
+                }
+            }
+        }
+    }
+
+    /// Get the first node of the subtree of `id` in postorder, by following first
+    /// children
+    fn first_leaf(&self, mut id: NodeId<T, I, G>) -> NodeId<T, I, G> {
+        while let Some(child) = self.node(id).first_child {
+            id = child;
+        }
+        id
+    }
+
+    /// Get a node which is linked to from the tree, and so is in it
+    fn node(&self, id: NodeId<T, I, G>) -> &Node<T, I, G> {
+        match self.nodes.get(key(id)) {
+            Some(node) => node,
+            None => unreachable!("nodes linked from the tree are in it"),
+        }
+    }
+
+    /// Get a node which is linked to from the tree, and so is in it, mutably
+    fn node_mut(&mut self, id: NodeId<T, I, G>) -> &mut Node<T, I, G> {
+        match self.nodes.get_mut(key(id)) {
+            Some(node) => node,
+            None => unreachable!("nodes linked from the tree are in it"),
+        }
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> Default for ArenaTree<T, I, G> {
+    fn default() -> ArenaTree<T, I, G> {
+        ArenaTree::new()
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> ops::Index<NodeId<T, I, G>> for ArenaTree<T, I, G> {
+    type Output = T;
+
+    fn index(&self, id: NodeId<T, I, G>) -> &T {
+        &self.nodes[key(id)].value
+    }
+}
+
+impl<T, I: ArenaIndex, G: GenerationalIndex> ops::IndexMut<NodeId<T, I, G>> for ArenaTree<T, I, G> {
+    fn index_mut(&mut self, id: NodeId<T, I, G>) -> &mut T {
+        &mut self.nodes[key(id)].value
+    }
+}
+
+/// An iterator over the ancestors of a node of an `ArenaTree`, from its parent
+/// up, created by `ArenaTree::ancestors`.
+#[derive(Clone, Debug)]
+pub struct Ancestors<'a, T: 'a, I: 'a, G: 'a> {
+    tree: &'a ArenaTree<T, I, G>,
+    next: Option<NodeId<T, I, G>>,
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> Iterator for Ancestors<'a, T, I, G> {
+    type Item = NodeId<T, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = self.tree.node(current).parent;
+        Some(current)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Ancestors<'a, T, I, G> {}
+
+/// An iterator over the children of a node of an `ArenaTree`, created by
+/// `ArenaTree::children`.
+#[derive(Clone, Debug)]
+pub struct Children<'a, T: 'a, I: 'a, G: 'a> {
+    tree: &'a ArenaTree<T, I, G>,
+    next: Option<NodeId<T, I, G>>,
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> Iterator for Children<'a, T, I, G> {
+    type Item = NodeId<T, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = self.tree.node(current).next_sibling;
+        Some(current)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Children<'a, T, I, G> {}
+
+/// An iterator over a node of an `ArenaTree` and its descendants in preorder,
+/// created by `ArenaTree::preorder`.
+#[derive(Clone, Debug)]
+pub struct Preorder<'a, T: 'a, I: 'a, G: 'a> {
+    tree: &'a ArenaTree<T, I, G>,
+    root: NodeId<T, I, G>,
+    next: Option<NodeId<T, I, G>>,
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> Iterator for Preorder<'a, T, I, G> {
+    type Item = NodeId<T, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        // Go down to the first child, or else up to the first following sibling
+        // of the node or of one of its ancestors below the root
+        self.next = self.tree.node(current).first_child;
+        let mut node = current;
+        while self.next.is_none() && !same(node, self.root) {
+            let links = self.tree.node(node);
+            self.next = links.next_sibling;
+            node = links.parent.expect("a node below the root has a parent");
+        }
+        Some(current)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Preorder<'a, T, I, G> {}
+
+/// An iterator over the descendants of a node of an `ArenaTree` in preorder,
+/// created by `ArenaTree::descendants`.
+#[derive(Clone, Debug)]
+pub struct Descendants<'a, T: 'a, I: 'a, G: 'a> {
+    preorder: Preorder<'a, T, I, G>,
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> Iterator for Descendants<'a, T, I, G> {
+    type Item = NodeId<T, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.preorder.next()
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Descendants<'a, T, I, G> {}
+
+/// An iterator over a node of an `ArenaTree` and its descendants in postorder,
+/// created by `ArenaTree::postorder`.
+#[derive(Clone, Debug)]
+pub struct Postorder<'a, T: 'a, I: 'a, G: 'a> {
+    tree: &'a ArenaTree<T, I, G>,
+    root: NodeId<T, I, G>,
+    next: Option<NodeId<T, I, G>>,
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> Iterator for Postorder<'a, T, I, G> {
+    type Item = NodeId<T, I, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        // The root comes last, and every other node is followed by the subtree of
+        // its next sibling, or else by its parent
+        self.next = if same(current, self.root) {
+            None
+        } else {
+            let links = self.tree.node(current);
+            match links.next_sibling {
+                Some(sibling) => Some(self.tree.first_leaf(sibling)),
+                None => links.parent,
+            }
+        };
+        Some(current)
+    }
+}
+
+impl<'a, T, I: ArenaIndex, G: GenerationalIndex> FusedIterator for Postorder<'a, T, I, G> {}
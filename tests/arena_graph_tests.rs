@@ -0,0 +1,154 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{AccessError, ArenaGraph, Index, NonzeroGeneration};
+
+type G = NonzeroGeneration<usize>;
+type Graph = ArenaGraph<char, u32, usize, G>;
+type Node = Index<char, usize, G>;
+
+/// Insert a node per character of `names`
+fn nodes(graph: &mut Graph, names: &str) -> Vec<Node> {
+    names.chars().map(|name| graph.insert_node(name)).collect()
+}
+
+fn names<It: Iterator<Item = Node>>(graph: &Graph, nodes: It) -> String {
+    nodes.map(|node| *graph.node(node).unwrap()).collect()
+}
+
+#[test]
+fn adjacency_in_both_directions() {
+    let mut graph = Graph::new();
+    let n = nodes(&mut graph, "abc");
+    let ab = graph.insert_edge(n[0], n[1], 1).unwrap();
+    let ac = graph.insert_edge(n[0], n[2], 2).unwrap();
+    let cb = graph.insert_edge(n[2], n[1], 3).unwrap();
+    let bb = graph.insert_edge(n[1], n[1], 4).unwrap();
+
+    assert_eq!(graph.outgoing(n[0]).collect::<Vec<_>>(), [ab, ac]);
+    assert_eq!(graph.incoming(n[1]).collect::<Vec<_>>(), [ab, cb, bb]);
+    assert_eq!(names(&graph, graph.successors(n[0])), "bc");
+    assert_eq!(names(&graph, graph.predecessors(n[1])), "acb");
+    assert_eq!(names(&graph, graph.successors(n[1])), "b");
+    assert_eq!(graph.source(cb), Some(n[2]));
+    assert_eq!(graph.target(cb), Some(n[1]));
+    assert_eq!(graph.edge(ac), Some(&2));
+    *graph.edge_mut(ac).unwrap() += 10;
+    assert_eq!(graph.edge(ac), Some(&12));
+    assert_eq!(graph.edge_count(), 4);
+}
+
+#[test]
+fn removing_a_node_removes_its_edges() {
+    let mut graph = Graph::new();
+    let n = nodes(&mut graph, "abc");
+    let ab = graph.insert_edge(n[0], n[1], 1).unwrap();
+    let bc = graph.insert_edge(n[1], n[2], 2).unwrap();
+    let ca = graph.insert_edge(n[2], n[0], 3).unwrap();
+    let bb = graph.insert_edge(n[1], n[1], 4).unwrap();
+
+    assert_eq!(graph.remove_node(n[1]), Some('b'));
+    assert_eq!(graph.remove_node(n[1]), None);
+    assert_eq!(graph.node_count(), 2);
+    assert_eq!(graph.edge_count(), 1);
+    for &edge in &[ab, bc, bb] {
+        assert!(!graph.contains_edge(edge));
+    }
+    assert_eq!(graph.outgoing(n[0]).count(), 0);
+    assert_eq!(graph.incoming(n[2]).count(), 0);
+    assert_eq!(graph.outgoing(n[2]).collect::<Vec<_>>(), [ca]);
+    assert_eq!(graph.outgoing(n[1]).count(), 0);
+
+    assert_eq!(graph.remove_edge(ca), Some(3));
+    assert_eq!(graph.remove_edge(ca), None);
+    assert_eq!(graph.incoming(n[0]).count(), 0);
+}
+
+#[test]
+fn indices_are_stable_and_generation_checked() {
+    let mut graph = Graph::new();
+    let n = nodes(&mut graph, "abcd");
+    let cd = graph.insert_edge(n[2], n[3], 7).unwrap();
+    graph.remove_node(n[0]);
+    graph.remove_node(n[1]);
+    // The remaining indices are unaffected by the removals
+    assert_eq!(graph.node(n[2]), Some(&'c'));
+    assert_eq!(graph.source(cd), Some(n[2]));
+
+    let e = graph.insert_node('e');
+    assert_eq!(e.to_idx(), n[1].to_idx());
+    assert_eq!(graph.node(n[1]), None);
+    assert_eq!(graph.successors(n[1]).count(), 0);
+    match graph.insert_edge(n[1], n[2], 0) {
+        Err(AccessError::Stale { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match graph.insert_edge(n[2], n[0], 0) {
+        Err(AccessError::Stale { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(graph.edge_count(), 1);
+}
+
+#[test]
+fn breadth_and_depth_first() {
+    let mut graph = Graph::new();
+    let n = nodes(&mut graph, "abcdefx");
+    for &(source, target) in &[(0, 1), (0, 2), (1, 3), (2, 4), (3, 5), (5, 0), (4, 3)] {
+        graph.insert_edge(n[source], n[target], 0).unwrap();
+    }
+    assert_eq!(names(&graph, graph.bfs(n[0])), "abcdef");
+    assert_eq!(names(&graph, graph.dfs(n[0])), "abdfce");
+    assert_eq!(names(&graph, graph.dfs(n[4])), "edfabc");
+    assert_eq!(names(&graph, graph.bfs(n[6])), "x");
+    graph.remove_node(n[6]);
+    assert_eq!(graph.bfs(n[6]).count(), 0);
+    assert_eq!(graph.dfs(n[6]).count(), 0);
+}
+
+#[test]
+fn topological_sort() {
+    let mut graph = Graph::new();
+    let n = nodes(&mut graph, "abcde");
+    for &(source, target) in &[(3, 1), (1, 0), (4, 0), (3, 2), (2, 0)] {
+        graph.insert_edge(n[source], n[target], 0).unwrap();
+    }
+    let sorted = graph.topological_sort().unwrap();
+    assert_eq!(sorted.len(), 5);
+    let position = |node| sorted.iter().position(|&sorted| sorted == node).unwrap();
+    for &node in &n {
+        for target in graph.successors(node) {
+            assert!(position(node) < position(target));
+        }
+    }
+
+    // The reported node is on the cycle, not just after it
+    let back = graph.insert_edge(n[2], n[3], 0).unwrap();
+    let on_cycle = graph.topological_sort().unwrap_err().node();
+    assert!(on_cycle == n[2] || on_cycle == n[3]);
+    graph.remove_edge(back);
+
+    graph.insert_edge(n[4], n[4], 0).unwrap();
+    assert_eq!(graph.topological_sort().unwrap_err().node(), n[4]);
+}
+
+#[test]
+fn shortest_paths() {
+    let mut graph = Graph::new();
+    let n = nodes(&mut graph, "abcdx");
+    let ab = graph.insert_edge(n[0], n[1], 10).unwrap();
+    graph.insert_edge(n[0], n[2], 3).unwrap();
+    graph.insert_edge(n[2], n[1], 4).unwrap();
+    graph.insert_edge(n[1], n[3], 2).unwrap();
+    graph.insert_edge(n[3], n[0], 1).unwrap();
+
+    let distances = graph.dijkstra(n[0], |_, &cost| cost);
+    assert_eq!(distances.len(), 4);
+    assert_eq!(distances[n[0]], 0);
+    assert_eq!(distances[n[1]], 7);
+    assert_eq!(distances[n[2]], 3);
+    assert_eq!(distances[n[3]], 9);
+    assert_eq!(distances.get(n[4]), None);
+
+    // The cost can depend on the edge itself
+    let distances = graph.dijkstra(n[0], |edge, &cost| if edge == ab { 1 } else { cost });
+    assert_eq!(distances[n[3]], 3);
+}
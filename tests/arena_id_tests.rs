@@ -0,0 +1,111 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{
+    AccessError, Arena, ConcurrentArena, ExhaustionPolicy, IdGeneration, LockError, LockingArena,
+    NonzeroGeneration, SmallIdArena, StandardIdArena, StandardIdSlotArena, TinyIdArena,
+};
+
+#[test]
+fn index_from_another_arena_is_rejected() {
+    let mut a = StandardIdArena::new();
+    let mut b = StandardIdArena::new();
+    let in_a = a.insert(1);
+    let in_b = b.insert(2);
+    // Both indices point at slot 0 with the same generation
+    assert_eq!(in_a.to_idx(), in_b.to_idx());
+    assert_ne!(in_a, in_b);
+
+    assert_eq!(a.get(in_b), None);
+    assert_eq!(a.get_mut(in_b), None);
+    assert!(!a.contains(in_b));
+    assert_eq!(a.remove(in_b), None);
+    assert_eq!(a.len(), 1);
+    assert_eq!(
+        a.try_get(in_b),
+        Err(AccessError::WrongArena {
+            expected: in_a.gen().arena() as u64,
+            found: in_b.gen().arena() as u64,
+        })
+    );
+    assert_eq!(a[in_a], 1);
+    assert_eq!(b[in_b], 2);
+}
+
+#[test]
+fn wrong_arena_is_reported_before_bounds() {
+    let mut a = SmallIdArena::new();
+    let mut b = SmallIdArena::new();
+    a.insert(());
+    let far = (0..10).map(|_| b.insert(())).last().unwrap();
+    match a.try_get(far) {
+        Err(AccessError::WrongArena { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(a.try_remove(far), Err(a.try_get(far).unwrap_err()));
+}
+
+#[test]
+fn every_arena_gets_a_fresh_id() {
+    let ids: Vec<_> = (0..100)
+        .map(|_| TinyIdArena::<()>::new().insert(()).gen().arena())
+        .collect();
+    assert!(ids.iter().all(|&id| id != 0));
+    for (n, id) in ids.iter().enumerate() {
+        assert!(!ids[n + 1..].contains(id));
+    }
+}
+
+#[test]
+fn clone_keeps_the_id() {
+    let mut arena = StandardIdArena::new();
+    let idx = arena.insert("a");
+    let clone = arena.clone();
+    assert_eq!(clone.get(idx), Some(&"a"));
+}
+
+#[test]
+fn id_survives_removal_and_wrapping() {
+    let mut arena = StandardIdSlotArena::new();
+    let first = arena.insert(0);
+    arena.remove(first);
+    let second = arena.insert(1);
+    assert_eq!(first.gen().arena(), second.gen().arena());
+    assert_eq!(arena.get(first), None);
+    match arena.try_get(first) {
+        Err(AccessError::Stale { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let mut arena = Arena::<(), u8, IdGeneration<u8, u8>>::new();
+    arena.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+    let id = arena.insert(()).gen().arena();
+    for _ in 0..600 {
+        let idx = arena.insert(());
+        assert_eq!(idx.gen().arena(), id);
+        arena.remove(idx);
+    }
+}
+
+#[test]
+fn concurrent_arenas_check_ids() {
+    type Generation = IdGeneration<NonzeroGeneration<usize>>;
+    let a = ConcurrentArena::<u32, usize, Generation>::new();
+    let b = ConcurrentArena::<u32, usize, Generation>::new();
+    let in_a = a.insert(1);
+    let in_b = b.insert(2);
+    assert!(a.get(in_b).is_none());
+    assert_eq!(a.remove(in_b), None);
+    match a.try_get(in_b) {
+        Err(LockError::Invalid(AccessError::WrongArena { .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(*a.get(in_a).unwrap(), 1);
+
+    let c = LockingArena::<u32, usize, Generation>::new();
+    let in_c = c.insert(3);
+    assert!(c.get_read(in_a).is_none());
+    match c.try_get_write(in_b) {
+        Err(LockError::Invalid(AccessError::WrongArena { .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(*c.get_read(in_c).unwrap(), 3);
+}
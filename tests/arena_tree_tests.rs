@@ -0,0 +1,141 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{AccessError, ArenaTree, NodeId, NonzeroGeneration, TreeError};
+
+type Tree = ArenaTree<&'static str, usize, NonzeroGeneration<usize>>;
+type Id = NodeId<&'static str, usize, NonzeroGeneration<usize>>;
+
+/// Build the tree
+///
+/// ```text
+/// root
+/// ├── a
+/// │   ├── a1
+/// │   └── a2
+/// └── b
+///     └── b1
+/// ```
+fn sample() -> (Tree, Vec<Id>) {
+    let mut tree = Tree::new();
+    let ids: Vec<_> = ["root", "a", "a1", "a2", "b", "b1"]
+        .iter()
+        .map(|&name| tree.insert(name))
+        .collect();
+    let (root, a, a1, a2, b, b1) = (ids[0], ids[1], ids[2], ids[3], ids[4], ids[5]);
+    tree.append_child(root, a).unwrap();
+    tree.append_child(a, a1).unwrap();
+    tree.append_child(a, a2).unwrap();
+    tree.append_child(root, b).unwrap();
+    tree.append_child(b, b1).unwrap();
+    (tree, ids)
+}
+
+fn names<It: Iterator<Item = Id>>(tree: &Tree, ids: It) -> Vec<&'static str> {
+    ids.map(|id| tree[id]).collect()
+}
+
+#[test]
+fn traversals() {
+    let (tree, ids) = sample();
+    let (root, a, a2) = (ids[0], ids[1], ids[3]);
+    assert_eq!(
+        names(&tree, tree.preorder(root)),
+        ["root", "a", "a1", "a2", "b", "b1"]
+    );
+    assert_eq!(
+        names(&tree, tree.postorder(root)),
+        ["a1", "a2", "a", "b1", "b", "root"]
+    );
+    assert_eq!(
+        names(&tree, tree.descendants(root)),
+        ["a", "a1", "a2", "b", "b1"]
+    );
+    assert_eq!(names(&tree, tree.children(root)), ["a", "b"]);
+    assert_eq!(names(&tree, tree.ancestors(a2)), ["a", "root"]);
+
+    // Traversals of a subtree stay within it
+    assert_eq!(names(&tree, tree.preorder(a)), ["a", "a1", "a2"]);
+    assert_eq!(names(&tree, tree.postorder(a)), ["a1", "a2", "a"]);
+    assert_eq!(names(&tree, tree.postorder(a2)), ["a2"]);
+    assert_eq!(tree.descendants(a2).count(), 0);
+}
+
+#[test]
+fn links() {
+    let (tree, ids) = sample();
+    let (root, a, a1, a2, b) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+    assert_eq!(tree.parent(root), None);
+    assert_eq!(tree.parent(a1), Some(a));
+    assert_eq!(tree.first_child(a), Some(a1));
+    assert_eq!(tree.last_child(a), Some(a2));
+    assert_eq!(tree.next_sibling(a), Some(b));
+    assert_eq!(tree.prev_sibling(b), Some(a));
+    assert_eq!(tree.prev_sibling(a), None);
+    assert_eq!(tree.first_child(a1), None);
+}
+
+#[test]
+fn moving_nodes() {
+    let (mut tree, ids) = sample();
+    let (root, a, a1, a2, b, b1) = (ids[0], ids[1], ids[2], ids[3], ids[4], ids[5]);
+
+    // Moving a node takes its descendants along
+    tree.append_child(b1, a).unwrap();
+    assert_eq!(
+        names(&tree, tree.preorder(root)),
+        ["root", "b", "b1", "a", "a1", "a2"]
+    );
+    tree.insert_before(a1, a2).unwrap();
+    assert_eq!(names(&tree, tree.children(a)), ["a2", "a1"]);
+    tree.insert_before(b, a).unwrap();
+    assert_eq!(names(&tree, tree.children(root)), ["a", "b"]);
+    assert_eq!(tree.first_child(b1), None);
+
+    tree.detach(a).unwrap();
+    assert_eq!(tree.parent(a), None);
+    assert_eq!(names(&tree, tree.preorder(root)), ["root", "b", "b1"]);
+    assert_eq!(names(&tree, tree.preorder(a)), ["a", "a2", "a1"]);
+    assert_eq!(tree.len(), 6);
+}
+
+#[test]
+fn cycles_and_roots_are_rejected() {
+    let (mut tree, ids) = sample();
+    let (root, a, a1, b) = (ids[0], ids[1], ids[2], ids[4]);
+    assert_eq!(tree.append_child(a1, root), Err(TreeError::Cycle));
+    assert_eq!(tree.append_child(a, a), Err(TreeError::Cycle));
+    assert_eq!(tree.insert_before(a1, a), Err(TreeError::Cycle));
+    assert_eq!(tree.insert_before(root, b), Err(TreeError::Root));
+    // The tree is unchanged
+    assert_eq!(
+        names(&tree, tree.preorder(root)),
+        ["root", "a", "a1", "a2", "b", "b1"]
+    );
+}
+
+#[test]
+fn removed_nodes_are_generation_checked() {
+    let (mut tree, ids) = sample();
+    let (root, a, a1, a2, b) = (ids[0], ids[1], ids[2], ids[3], ids[4]);
+    assert_eq!(tree.remove_subtree(a), ["a", "a1", "a2"]);
+    assert_eq!(tree.len(), 3);
+    assert_eq!(names(&tree, tree.children(root)), ["b"]);
+    assert_eq!(tree.prev_sibling(b), None);
+
+    // A new node in the slot of a removed one is not confused with it
+    let new = tree.insert("new");
+    assert_eq!(new.to_idx(), a2.to_idx());
+    assert!(!tree.contains(a2));
+    assert_eq!(tree.get(a2), None);
+    assert_eq!(tree.parent(a1), None);
+    assert_eq!(tree.preorder(a).count(), 0);
+    assert!(tree.remove_subtree(a).is_empty());
+    match tree.append_child(new, a1) {
+        Err(TreeError::Invalid(AccessError::Stale { .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match tree.detach(a2) {
+        Err(AccessError::Stale { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(tree.len(), 4);
+}
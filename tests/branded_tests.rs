@@ -0,0 +1,84 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{AccessError, StandardArena, StandardSlotArena};
+
+#[test]
+fn validated_tokens_access_their_elements() {
+    let mut arena = StandardArena::new();
+    let a = arena.insert("a".to_string());
+    let b = arena.insert("b".to_string());
+    arena.scope(|mut arena| {
+        let va = arena.validate(a).unwrap();
+        let vb = arena.validate(b).unwrap();
+        assert_eq!(arena.len(), 2);
+        assert_eq!(va.to_idx(), a.to_idx());
+        assert_eq!(arena.index(va), a);
+        arena.get_mut(va).push('!');
+        arena[vb].push('?');
+        assert_eq!(arena.get(va), "a!");
+        assert_eq!(arena[vb], "b?");
+    });
+    assert_eq!(arena[a], "a!");
+}
+
+#[test]
+fn invalid_indices_are_not_validated() {
+    let mut arena = StandardSlotArena::new();
+    let gone = arena.insert(0);
+    arena.remove(gone);
+    let reused = arena.insert(1);
+    let mut other = StandardSlotArena::new();
+    let far = (0..10).map(|i| other.insert(i)).last().unwrap();
+    arena.scope(|arena| {
+        assert!(arena.validate(gone).is_none());
+        match arena.try_validate(gone) {
+            Err(AccessError::Stale { .. }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(
+            arena.try_validate(far).unwrap_err(),
+            AccessError::OutOfBounds {
+                slot: 9,
+                capacity: 4
+            }
+        );
+        assert!(arena.validate(reused).is_some());
+    });
+}
+
+#[test]
+fn tokens_stay_valid_across_insertions() {
+    let mut arena = StandardArena::with_capacity(1);
+    let first = arena.insert(0);
+    let indices = arena.scope(|mut arena| {
+        let valid = arena.validate(first).unwrap();
+        // Growing the arena moves its slots, but tokens only hold the slot
+        let inserted: Vec<_> = (1..100).map(|i| arena.insert(i)).collect();
+        arena[valid] = 1000;
+        for &v in &inserted {
+            arena[v] += 1;
+        }
+        inserted.iter().map(|&v| arena.index(v)).collect::<Vec<_>>()
+    });
+    assert_eq!(arena[first], 1000);
+    assert_eq!(arena.len(), 100);
+    for (i, &idx) in indices.iter().enumerate() {
+        assert_eq!(arena[idx], i + 2);
+    }
+}
+
+#[test]
+fn get2_mut_borrows_distinct_elements() {
+    let mut arena = StandardArena::new();
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    arena.scope(|mut arena| {
+        let (va, vb) = (arena.validate(a).unwrap(), arena.validate(b).unwrap());
+        assert!(arena.get2_mut(va, va).is_none());
+        {
+            let (x, y) = arena.get2_mut(vb, va).unwrap();
+            assert_eq!((*x, *y), (2, 1));
+            std::mem::swap(x, y);
+        }
+        assert_eq!((arena[va], arena[vb]), (2, 1));
+    });
+}
@@ -0,0 +1,187 @@
+extern crate typed_generational_arena;
+use std::collections::HashSet;
+use std::sync::Barrier;
+use std::thread;
+use typed_generational_arena::{
+    AccessError, CapacityError, ConcurrentArena, LockError, NonzeroGeneration,
+};
+
+type Arena<T> = ConcurrentArena<T, usize, NonzeroGeneration<usize>>;
+
+#[test]
+fn can_get_live_value() {
+    let arena = Arena::new();
+    let i = arena.insert(42);
+    assert_eq!(*arena.get(i).unwrap(), 42);
+    assert!(arena.contains(i));
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn removed_index_is_stale() {
+    let arena = Arena::new();
+    let i = arena.insert(42);
+    assert_eq!(arena.remove(i), Some(42));
+    assert_eq!(arena.remove(i), None);
+    assert!(!arena.contains(i));
+    assert_eq!(
+        arena.try_get(i).unwrap_err(),
+        LockError::Invalid(AccessError::Vacant { slot: 0 })
+    );
+
+    let j = arena.insert(43);
+    assert_eq!(i.to_idx(), j.to_idx());
+    assert!(arena.get(i).is_none());
+    match arena.try_get(i) {
+        Err(LockError::Invalid(AccessError::Stale { slot: 0, .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(arena.remove(i), None);
+    assert_eq!(*arena.get(j).unwrap(), 43);
+    assert!(!arena.is_empty());
+}
+
+#[test]
+fn out_of_bounds_index_is_rejected() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    for i in 0..3 {
+        other.insert(i);
+    }
+    let far = other.insert(3);
+    arena.insert(0);
+    assert_eq!(
+        arena.try_get(far).unwrap_err(),
+        LockError::Invalid(AccessError::OutOfBounds {
+            slot: 3,
+            capacity: 1
+        })
+    );
+    assert_eq!(arena.remove(far), None);
+}
+
+#[test]
+fn panicking_while_holding_a_guard_poisons_the_element() {
+    let mut arena = Arena::new();
+    let i = arena.insert(vec![1, 2]);
+    let j = arena.insert(vec![3]);
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            let mut guard = arena.get(i).unwrap();
+            guard.push(3);
+            panic!("half way through an update");
+        })
+        .join()
+    });
+    assert!(result.is_err());
+
+    assert_eq!(arena.try_get(i).unwrap_err(), LockError::Poisoned);
+    assert!(arena.get(i).is_none());
+    assert!(arena.get_mut(i).is_none());
+    assert!(arena.contains(i));
+    assert_eq!(*arena.get(j).unwrap(), [3]);
+
+    // Removing the element discards it, and the slot is reused afresh
+    assert_eq!(arena.remove(i), Some(vec![1, 2, 3]));
+    let k = arena.insert(vec![4]);
+    assert_eq!(k.to_idx(), i.to_idx());
+    assert_eq!(*arena.try_get(k).unwrap(), [4]);
+    match arena.try_get(i) {
+        Err(LockError::Invalid(AccessError::Stale { .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    };
+}
+
+#[test]
+fn elements_span_several_segments() {
+    let mut arena = Arena::with_capacity(10);
+    let indices: Vec<_> = (0..1000).map(|i| arena.insert(i)).collect();
+    assert_eq!(arena.capacity(), 1000);
+    for (i, &idx) in indices.iter().enumerate() {
+        assert_eq!(idx.to_idx(), i);
+        assert_eq!(*arena.get(idx).unwrap(), i);
+    }
+    for &idx in &indices {
+        *arena.get_mut(idx).unwrap() *= 2;
+    }
+    assert!(indices
+        .iter()
+        .all(|&idx| *arena.get(idx).unwrap() == idx.to_idx() * 2));
+}
+
+#[test]
+fn removed_slots_are_reused() {
+    let arena = ConcurrentArena::<u32, u8, u8>::with_free_lists(0, 3);
+    let indices: Vec<_> = (0..256).map(|i| arena.insert(i)).collect();
+    assert!(arena.try_insert(256).is_err());
+    for &idx in indices.iter().step_by(2) {
+        arena.remove(idx);
+    }
+    let reused: HashSet<_> = (0..128).map(|i| arena.insert(i).to_idx()).collect();
+    assert_eq!(
+        reused,
+        indices.iter().step_by(2).map(|i| i.to_idx()).collect()
+    );
+    assert_eq!(
+        arena.try_insert(0),
+        Err((0, CapacityError::IndexSpaceExhausted { max_capacity: 256 }))
+    );
+}
+
+#[test]
+fn slot_with_exhausted_generation_is_retired() {
+    let arena = ConcurrentArena::<(), u8, u8>::with_free_lists(0, 1);
+    let mut idx = arena.insert(());
+    for _ in 0..255 {
+        arena.remove(idx);
+        let next = arena.insert(());
+        assert_eq!(next.to_idx(), 0);
+        idx = next;
+    }
+    assert_eq!(idx.gen(), 255);
+    arena.remove(idx);
+    assert_eq!(arena.insert(()).to_idx(), 1);
+    assert!(!arena.contains(idx));
+}
+
+#[test]
+fn threads_insert_and_remove_concurrently() {
+    let threads = 8;
+    let per_thread = 2000;
+    let arena = Arena::new();
+    let barrier = Barrier::new(threads);
+    let kept: Vec<_> = thread::scope(|s| {
+        let workers: Vec<_> = (0..threads)
+            .map(|t| {
+                let (arena, barrier) = (&arena, &barrier);
+                s.spawn(move || {
+                    barrier.wait();
+                    let mut kept = Vec::new();
+                    for i in 0..per_thread {
+                        let value = t * per_thread + i;
+                        let idx = arena.insert(value);
+                        *arena.get(idx).unwrap() += 1;
+                        if i % 3 == 0 {
+                            assert_eq!(arena.remove(idx), Some(value + 1));
+                            assert!(arena.get(idx).is_none());
+                        } else {
+                            kept.push((idx, value + 1));
+                        }
+                    }
+                    kept
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    assert_eq!(arena.len(), kept.len());
+    let slots: HashSet<_> = kept.iter().map(|(idx, _)| idx.to_idx()).collect();
+    assert_eq!(slots.len(), kept.len());
+    for (idx, value) in kept {
+        assert_eq!(*arena.get(idx).unwrap(), value);
+    }
+}
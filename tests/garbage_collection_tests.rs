@@ -0,0 +1,153 @@
+extern crate typed_generational_arena;
+use std::cell::Cell;
+use std::num::Wrapping;
+use std::rc::Rc;
+use typed_generational_arena::{
+    IdGeneration, NanoArena, NanoIndex, NonzeroGeneration, StandardArena, StandardIdArena,
+    StandardIdIndex, StandardIndex, Trace, Tracer,
+};
+
+type G = NonzeroGeneration<usize>;
+
+struct Node {
+    edges: Vec<StandardIndex<Node>>,
+    weight: Option<(u32, String)>,
+}
+
+impl Trace<Node, usize, G> for Node {
+    fn trace(&self, tracer: &mut Tracer<Node, usize, G>) {
+        self.edges.trace(tracer);
+        self.weight.trace(tracer);
+    }
+}
+
+fn node(edges: Vec<StandardIndex<Node>>) -> Node {
+    Node {
+        edges,
+        weight: None,
+    }
+}
+
+#[test]
+fn unreachable_elements_are_removed() {
+    let mut arena = StandardArena::new();
+    let leaf = arena.insert(node(vec![]));
+    let garbage = arena.insert(node(vec![leaf]));
+    let root = arena.insert(node(vec![leaf, leaf]));
+    let other_root = arena.insert(node(vec![]));
+
+    assert_eq!(arena.collect_garbage(vec![root, other_root]), vec![garbage]);
+    assert_eq!(arena.len(), 3);
+    assert!(!arena.contains(garbage));
+    // Freed slots are reused like any other removed slot
+    let new = arena.insert(node(vec![]));
+    assert_eq!(new.to_idx(), garbage.to_idx());
+}
+
+#[test]
+fn cycles_are_collected_unless_reachable() {
+    let mut arena = StandardArena::new();
+    let a = arena.insert(node(vec![]));
+    let b = arena.insert(node(vec![a]));
+    let c = arena.insert(node(vec![b]));
+    arena[a].edges.push(c);
+
+    assert!(arena.collect_garbage(vec![b]).is_empty());
+    assert_eq!(arena.len(), 3);
+    assert_eq!(arena.collect_garbage(vec![]), vec![a, b, c]);
+    assert!(arena.is_empty());
+}
+
+#[test]
+fn stale_indices_are_ignored() {
+    let mut arena = StandardArena::new();
+    let removed = arena.insert(node(vec![]));
+    arena.remove(removed);
+    let reused = arena.insert(node(vec![]));
+    assert_eq!(reused.to_idx(), removed.to_idx());
+    let holder = arena.insert(node(vec![removed]));
+
+    // Neither a stale root nor a stale edge keeps the new element alive
+    assert_eq!(arena.collect_garbage(vec![removed, holder]), vec![reused]);
+    assert!(arena.contains(holder));
+}
+
+#[test]
+fn stale_indices_are_ignored_once_generations_wrap() {
+    struct Leaf;
+
+    impl Trace<Leaf, u8, Wrapping<u8>> for Leaf {
+        fn trace(&self, _tracer: &mut Tracer<Leaf, u8, Wrapping<u8>>) {}
+    }
+
+    let mut arena = NanoArena::new();
+    let at_generation = |arena: &mut NanoArena<Leaf>, generation: u8| -> NanoIndex<Leaf> {
+        loop {
+            let index = arena.insert(Leaf);
+            if index.gen() == Wrapping(generation) {
+                return index;
+            }
+            arena.remove(index);
+        }
+    };
+    let stale = at_generation(&mut arena, 250);
+    arena.remove(stale);
+    // The live generation wrapped around, so it compares lower than the stale one
+    let live = at_generation(&mut arena, 4);
+    assert_eq!(live.to_idx(), stale.to_idx());
+
+    assert!(arena.collect_garbage(vec![stale, live]).is_empty());
+    assert!(arena.contains(live));
+    assert_eq!(arena.collect_garbage(vec![stale]), vec![live]);
+}
+
+#[test]
+fn collected_elements_are_dropped() {
+    struct Tracked(Rc<Cell<usize>>, Option<StandardIndex<Tracked>>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    impl Trace<Tracked, usize, G> for Tracked {
+        fn trace(&self, tracer: &mut Tracer<Tracked, usize, G>) {
+            self.1.trace(tracer);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let mut arena = StandardArena::new();
+    let mut head = None;
+    for _ in 0..10 {
+        head = Some(arena.insert(Tracked(drops.clone(), head)));
+    }
+    let tail = arena.iter().next().unwrap().0;
+    arena[tail].1 = head;
+
+    assert!(arena.collect_garbage(head).is_empty());
+    assert_eq!(drops.get(), 0);
+    assert_eq!(arena.collect_garbage(None).len(), 10);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn indices_from_another_arena_are_ignored() {
+    struct Cell(Vec<StandardIdIndex<Cell>>);
+
+    impl Trace<Cell, usize, IdGeneration<G>> for Cell {
+        fn trace(&self, tracer: &mut Tracer<Cell, usize, IdGeneration<G>>) {
+            self.0.trace(tracer);
+        }
+    }
+
+    let mut first = StandardIdArena::new();
+    let mut second = StandardIdArena::new();
+    let foreign = first.insert(Cell(vec![]));
+    let local = second.insert(Cell(vec![]));
+    assert_eq!(foreign.to_idx(), local.to_idx());
+
+    assert_eq!(second.collect_garbage(vec![foreign]), vec![local]);
+    assert!(first.contains(foreign));
+}
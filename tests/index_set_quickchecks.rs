@@ -0,0 +1,33 @@
+extern crate typed_generational_arena;
+#[macro_use]
+extern crate quickcheck;
+
+use std::collections::BTreeSet;
+use typed_generational_arena::{IndexSet, StandardArena};
+
+quickcheck! {
+    fn set_operations_match_btree_set(capacity: u8, left: Vec<u8>, right: Vec<u8>) -> bool {
+        let mut arena = StandardArena::new();
+        let indices: Vec<_> = (0..=capacity as usize).map(|i| arena.insert(i)).collect();
+        let model = |slots: &[u8]| -> BTreeSet<usize> {
+            slots.iter().map(|&s| s as usize % indices.len()).collect()
+        };
+        let set = |slots: &BTreeSet<usize>| -> IndexSet<_, _, _> {
+            slots.iter().map(|&s| indices[s]).collect()
+        };
+        let slots = |set: &IndexSet<_, _, _>| -> Vec<usize> {
+            set.iter().map(|i| i.to_idx()).collect()
+        };
+        let (left, right) = (model(&left), model(&right));
+        let (left_set, right_set) = (set(&left), set(&right));
+
+        slots(&left_set) == left.iter().cloned().collect::<Vec<_>>()
+            && slots(&left_set.union(&right_set)) == left.union(&right).cloned().collect::<Vec<_>>()
+            && slots(&left_set.intersection(&right_set))
+                == left.intersection(&right).cloned().collect::<Vec<_>>()
+            && slots(&left_set.difference(&right_set))
+                == left.difference(&right).cloned().collect::<Vec<_>>()
+            && arena.iter_set(&left_set).map(|(_, &v)| v).collect::<Vec<_>>()
+                == left.iter().cloned().collect::<Vec<_>>()
+    }
+}
@@ -0,0 +1,151 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{IndexSet, NanoArena, StandardArena, StandardSlotArena, TinyArena};
+
+#[test]
+fn index_set_holds_inserted_indices() {
+    let mut arena = StandardArena::new();
+    let a = arena.insert('a');
+    let b = arena.insert('b');
+    let mut set = IndexSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(b));
+    assert!(set.insert(a));
+    assert!(!set.insert(a));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(a) && set.contains(b));
+    assert!(set.remove(a));
+    assert!(!set.remove(a));
+    assert!(!set.contains(a));
+    assert_eq!(set.len(), 1);
+    set.clear();
+    assert!(set.is_empty());
+    assert!(!set.contains(b));
+}
+
+#[test]
+fn index_set_misses_stale_indices() {
+    let mut arena = StandardSlotArena::new();
+    let old = arena.insert(());
+    let mut set = IndexSet::new();
+    set.insert(old);
+    arena.remove(old);
+    let new = arena.insert(());
+    assert_eq!(old.to_idx(), new.to_idx());
+
+    assert!(!set.contains(new));
+    // The newer index replaces the stale one, which cannot come back
+    assert!(set.insert(new));
+    assert_eq!(set.len(), 1);
+    assert!(!set.contains(old));
+    assert!(!set.remove(old));
+    assert!(set.contains(new));
+}
+
+#[test]
+fn index_set_takes_live_indices_once_generations_wrap() {
+    let mut arena = NanoArena::new();
+    let mut set = IndexSet::new();
+    let mut idx = arena.insert(());
+    for _ in 0..300 {
+        let old = set.iter().next();
+        assert!(set.insert(idx));
+        assert!(set.contains(idx));
+
+        let single: IndexSet<_, _, _> = vec![idx].into_iter().collect();
+        let previous: IndexSet<_, _, _> = old.into_iter().collect();
+        assert_eq!(previous.union(&single), single);
+
+        arena.remove(idx);
+        idx = arena.insert(());
+    }
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn index_set_iterates_in_slot_order_across_words() {
+    let mut arena = TinyArena::new();
+    let indices: Vec<_> = (0..200).map(|i| arena.insert(i)).collect();
+    let chosen: Vec<_> = indices.iter().cloned().filter(|i| i.to_idx() % 7 == 3).collect();
+    let set: IndexSet<_, _, _> = chosen.iter().rev().cloned().collect();
+    let iter = set.iter();
+    assert_eq!(iter.len(), chosen.len());
+    assert_eq!(iter.collect::<Vec<_>>(), chosen);
+    assert_eq!((&set).into_iter().count(), chosen.len());
+}
+
+#[test]
+fn index_set_operations() {
+    let mut arena = StandardArena::new();
+    let indices: Vec<_> = (0..130).map(|i| arena.insert(i)).collect();
+    let twos: IndexSet<_, _, _> = indices.iter().cloned().step_by(2).collect();
+    let threes: IndexSet<_, _, _> = indices.iter().cloned().step_by(3).collect();
+
+    let slots = |set: &IndexSet<_, _, _>| set.iter().map(|i| i.to_idx()).collect::<Vec<_>>();
+    assert_eq!(
+        slots(&twos.union(&threes)),
+        (0..130).filter(|i| i % 2 == 0 || i % 3 == 0).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        slots(&twos.intersection(&threes)),
+        (0..130).filter(|i| i % 6 == 0).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        slots(&twos.difference(&threes)),
+        (0..130).filter(|i| i % 2 == 0 && i % 3 != 0).collect::<Vec<_>>()
+    );
+    assert_eq!(twos.union(&threes).len(), 87);
+    assert_eq!(twos.union(&IndexSet::new()), twos);
+    assert!(twos.intersection(&IndexSet::new()).is_empty());
+}
+
+#[test]
+fn index_set_operations_compare_generations() {
+    let mut arena = StandardArena::new();
+    let old = arena.insert(());
+    arena.remove(old);
+    let new = arena.insert(());
+    let olds: IndexSet<_, _, _> = vec![old].into_iter().collect();
+    let news: IndexSet<_, _, _> = vec![new].into_iter().collect();
+
+    let union = olds.union(&news);
+    assert_eq!(union.iter().collect::<Vec<_>>(), vec![new]);
+    assert_eq!(news.union(&olds).iter().collect::<Vec<_>>(), vec![old]);
+    assert!(olds.intersection(&news).is_empty());
+    assert_eq!(olds.difference(&news), olds);
+    assert!(news.difference(&news).is_empty());
+}
+
+#[test]
+fn iter_set_visits_live_elements_in_set() {
+    let mut arena = StandardArena::new();
+    let indices: Vec<_> = (0..100).map(|i| arena.insert(i)).collect();
+    let set: IndexSet<_, _, _> = indices.iter().cloned().filter(|i| i.to_idx() % 10 == 0).collect();
+    arena.remove(indices[50]);
+    let reused = arena.insert(500);
+    assert_eq!(reused.to_idx(), 50);
+    // Indices past the end of the arena are skipped too
+    let mut bigger = StandardArena::new();
+    let beyond: Vec<_> = (0..200).map(|i| bigger.insert(i)).collect();
+    let mut with_beyond = set.clone();
+    with_beyond.insert(beyond[150]);
+
+    let expected: Vec<_> = (0..10).map(|i| i * 10).filter(|&i| i != 50).collect();
+    for set in &[&set, &with_beyond] {
+        let seen: Vec<_> = arena.iter_set(set).map(|(idx, value)| (idx, *value)).collect();
+        let expected: Vec<_> = expected.iter().map(|&i| (indices[i], i)).collect();
+        assert_eq!(seen, expected);
+    }
+
+    for (idx, value) in arena.iter_mut_set(&with_beyond) {
+        assert_eq!(*value, idx.to_idx());
+        *value += 1000;
+    }
+    for (idx, value) in arena.iter() {
+        if set.contains(idx) {
+            assert_eq!(*value, idx.to_idx() + 1000);
+        } else {
+            assert!(*value < 1000 || idx == reused);
+        }
+    }
+    assert_eq!(arena[reused], 500);
+}
@@ -0,0 +1,189 @@
+extern crate typed_generational_arena;
+use std::sync::Barrier;
+use std::thread;
+use typed_generational_arena::{AccessError, LockError, LockingArena, NonzeroGeneration};
+
+type Arena<T> = LockingArena<T, usize, NonzeroGeneration<usize>>;
+
+#[test]
+fn can_read_and_write_live_value() {
+    let arena = Arena::new();
+    let i = arena.insert(42);
+    *arena.get_write(i).unwrap() += 1;
+    assert_eq!(*arena.get_read(i).unwrap(), 43);
+    assert!(arena.contains(i));
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn readers_share_an_element_and_writers_exclude_them() {
+    let arena = Arena::new();
+    let i = arena.insert(42);
+    let j = arena.insert(7);
+    {
+        let first = arena.try_get_read(i).unwrap();
+        let second = arena.try_get_read(i).unwrap();
+        assert_eq!(*first + *second, 84);
+        assert_eq!(arena.try_get_write(i).unwrap_err(), LockError::WouldBlock);
+        // Other elements are not locked
+        *arena.try_get_write(j).unwrap() += 1;
+    }
+    {
+        let mut writer = arena.try_get_write(i).unwrap();
+        *writer = 0;
+        assert_eq!(arena.try_get_read(i).unwrap_err(), LockError::WouldBlock);
+        assert_eq!(arena.try_get_write(i).unwrap_err(), LockError::WouldBlock);
+        assert_eq!(*arena.try_get_read(j).unwrap(), 8);
+    }
+    assert_eq!(*arena.try_get_read(i).unwrap(), 0);
+}
+
+#[test]
+fn removed_index_is_stale() {
+    let arena = Arena::new();
+    let i = arena.insert(42);
+    assert_eq!(arena.remove(i), Some(42));
+    assert_eq!(arena.remove(i), None);
+    assert!(!arena.contains(i));
+    assert_eq!(
+        arena.try_get_read(i).unwrap_err(),
+        LockError::Invalid(AccessError::Vacant { slot: 0 })
+    );
+
+    let j = arena.insert(43);
+    assert_eq!(i.to_idx(), j.to_idx());
+    assert!(arena.get_read(i).is_none());
+    assert!(arena.get_write(i).is_none());
+    match arena.try_get_write(i) {
+        Err(LockError::Invalid(AccessError::Stale { slot: 0, .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(arena.remove(i), None);
+    assert_eq!(*arena.get_read(j).unwrap(), 43);
+}
+
+#[test]
+fn out_of_bounds_index_is_rejected() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    for i in 0..3 {
+        other.insert(i);
+    }
+    let far = other.insert(3);
+    arena.insert(0);
+    assert_eq!(
+        arena.try_get_read(far).unwrap_err(),
+        LockError::Invalid(AccessError::OutOfBounds {
+            slot: 3,
+            capacity: 1
+        })
+    );
+    assert_eq!(arena.remove(far), None);
+}
+
+#[test]
+fn panicking_while_holding_a_write_guard_poisons_the_element() {
+    let mut arena = Arena::new();
+    let i = arena.insert(vec![1, 2]);
+    let j = arena.insert(vec![3]);
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            let _reader = arena.get_read(j).unwrap();
+            panic!("while reading");
+        })
+        .join()
+    });
+    assert!(result.is_err());
+    // Readers cannot leave the element half updated
+    assert_eq!(*arena.try_get_write(j).unwrap(), [3]);
+
+    let result = thread::scope(|s| {
+        s.spawn(|| {
+            let mut writer = arena.get_write(i).unwrap();
+            writer.push(3);
+            panic!("half way through an update");
+        })
+        .join()
+    });
+    assert!(result.is_err());
+    assert_eq!(arena.try_get_read(i).unwrap_err(), LockError::Poisoned);
+    assert_eq!(arena.try_get_write(i).unwrap_err(), LockError::Poisoned);
+    assert!(arena.get_read(i).is_none());
+    assert!(arena.get_write(i).is_none());
+    assert!(arena.get_mut(i).is_none());
+    assert!(arena.contains(i));
+
+    // Removing the element discards it, and the slot is reused afresh
+    assert_eq!(arena.remove(i), Some(vec![1, 2, 3]));
+    let k = arena.insert(vec![4]);
+    assert_eq!(k.to_idx(), i.to_idx());
+    assert_eq!(*arena.try_get_read(k).unwrap(), [4]);
+    match arena.try_get_write(i) {
+        Err(LockError::Invalid(AccessError::Stale { .. })) => {}
+        other => panic!("unexpected {:?}", other),
+    };
+}
+
+#[test]
+fn elements_span_several_segments() {
+    let mut arena = Arena::with_capacity(10);
+    let indices: Vec<_> = (0..1000).map(|i| arena.insert(i)).collect();
+    assert_eq!(arena.capacity(), 1000);
+    for (i, &idx) in indices.iter().enumerate() {
+        assert_eq!(idx.to_idx(), i);
+        assert_eq!(*arena.get_read(idx).unwrap(), i);
+    }
+    for &idx in &indices {
+        *arena.get_mut(idx).unwrap() *= 2;
+    }
+    assert!(indices
+        .iter()
+        .all(|&idx| *arena.get_read(idx).unwrap() == idx.to_idx() * 2));
+}
+
+#[test]
+fn slot_with_exhausted_generation_is_retired() {
+    let arena = LockingArena::<(), u8, u8>::new();
+    let mut idx = arena.insert(());
+    for _ in 0..255 {
+        arena.remove(idx);
+        let next = arena.insert(());
+        assert_eq!(next.to_idx(), 0);
+        idx = next;
+    }
+    assert_eq!(idx.gen(), 255);
+    arena.remove(idx);
+    assert_eq!(arena.insert(()).to_idx(), 1);
+    assert!(!arena.contains(idx));
+}
+
+#[test]
+fn threads_mutate_shared_elements_concurrently() {
+    let threads = 8;
+    let rounds = 1000;
+    let arena = Arena::new();
+    let counters: Vec<_> = (0..4).map(|_| arena.insert(0)).collect();
+    let barrier = Barrier::new(threads);
+    thread::scope(|s| {
+        for t in 0..threads {
+            let (arena, counters, barrier) = (&arena, &counters, &barrier);
+            s.spawn(move || {
+                barrier.wait();
+                for i in 0..rounds {
+                    *arena.get_write(counters[(t + i) % counters.len()]).unwrap() += 1;
+                    // Churn the structure while the counters are being written
+                    let idx = arena.insert(usize::MAX);
+                    assert_eq!(*arena.get_read(idx).unwrap(), usize::MAX);
+                    assert_eq!(arena.remove(idx), Some(usize::MAX));
+                }
+            });
+        }
+    });
+
+    assert_eq!(arena.len(), counters.len());
+    let total: usize = counters
+        .iter()
+        .map(|&idx| *arena.get_read(idx).unwrap())
+        .sum();
+    assert_eq!(total, threads * rounds);
+}
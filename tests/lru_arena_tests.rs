@@ -0,0 +1,105 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{LruArena, NonzeroGeneration};
+
+type Cache = LruArena<String, u32, usize, NonzeroGeneration<usize>>;
+
+fn keys(cache: &Cache) -> Vec<&str> {
+    cache.iter().map(|(_, key, _)| key.as_str()).collect()
+}
+
+#[test]
+fn least_recently_used_entries_are_evicted() {
+    let mut cache = Cache::new(3);
+    for (i, key) in ["a", "b", "c"].iter().enumerate() {
+        let (_, evicted) = cache.insert(key.to_string(), i as u32);
+        assert_eq!(evicted, None);
+    }
+    assert_eq!(keys(&cache), ["c", "b", "a"]);
+
+    assert_eq!(cache.get("a"), Some(&0));
+    *cache.get_mut("b").unwrap() += 10;
+    assert_eq!(keys(&cache), ["b", "a", "c"]);
+
+    let (_, evicted) = cache.insert("d".to_string(), 3);
+    assert_eq!(evicted, Some(("c".to_string(), 2)));
+    let (_, evicted) = cache.insert("e".to_string(), 4);
+    assert_eq!(evicted, Some(("a".to_string(), 0)));
+    assert_eq!(keys(&cache), ["e", "d", "b"]);
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.capacity(), 3);
+    assert!(!cache.contains_key("a"));
+    assert_eq!(cache.peek("b"), Some(&11));
+}
+
+#[test]
+fn peeking_does_not_mark_entries_as_used() {
+    let mut cache = Cache::new(2);
+    let (a, _) = cache.insert("a".to_string(), 1);
+    cache.insert("b".to_string(), 2);
+    assert_eq!(cache.peek("a"), Some(&1));
+    assert_eq!(cache.value(a), Some(&1));
+    *cache.value_mut(a).unwrap() = 5;
+    assert_eq!(cache.handle("a"), Some(a));
+    assert_eq!(cache.lru(), Some(a));
+
+    let (_, evicted) = cache.insert("c".to_string(), 3);
+    assert_eq!(evicted, Some(("a".to_string(), 5)));
+}
+
+#[test]
+fn handles_go_stale_on_eviction() {
+    let mut cache = Cache::new(1);
+    let (a, _) = cache.insert("a".to_string(), 1);
+    assert_eq!(cache.key(a).map(String::as_str), Some("a"));
+    let (b, evicted) = cache.insert("b".to_string(), 2);
+    assert_eq!(evicted, Some(("a".to_string(), 1)));
+
+    // The new entry takes the slot of the evicted one
+    assert_eq!(a.to_idx(), b.to_idx());
+    assert!(!cache.contains(a));
+    assert_eq!(cache.value(a), None);
+    assert_eq!(cache.key(a), None);
+    assert!(!cache.touch(a));
+    assert_eq!(cache.remove_handle(a), None);
+    assert_eq!(cache.value(b), Some(&2));
+}
+
+#[test]
+fn reinserting_a_key_replaces_its_entry() {
+    let mut cache = Cache::new(2);
+    let (a, _) = cache.insert("a".to_string(), 1);
+    cache.insert("b".to_string(), 2);
+    let (new_a, replaced) = cache.insert("a".to_string(), 3);
+    assert_eq!(replaced, Some(("a".to_string(), 1)));
+    assert!(!cache.contains(a));
+    assert_eq!(cache.value(new_a), Some(&3));
+    assert_eq!(keys(&cache), ["a", "b"]);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn touching_and_removing() {
+    let mut cache = Cache::new(3);
+    let (a, _) = cache.insert("a".to_string(), 1);
+    let (b, _) = cache.insert("b".to_string(), 2);
+    cache.insert("c".to_string(), 3);
+    assert!(cache.touch(a));
+    assert_eq!(keys(&cache), ["a", "c", "b"]);
+    let reversed: Vec<_> = cache.iter().rev().map(|(handle, _, _)| handle).collect();
+    assert_eq!(reversed[0], b);
+
+    assert_eq!(cache.remove("c"), Some(3));
+    assert_eq!(cache.remove("c"), None);
+    assert_eq!(cache.pop_lru(), Some(("b".to_string(), 2)));
+    assert_eq!(keys(&cache), ["a"]);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.pop_lru(), None);
+    assert!(!cache.touch(a));
+}
+
+#[test]
+#[should_panic(expected = "LRU capacity must be at least 1")]
+fn zero_capacity_is_rejected() {
+    Cache::new(0);
+}
@@ -1,4 +1,6 @@
 extern crate typed_generational_arena;
+use typed_generational_arena::{AccessError, CapacityError, FixedGenerationalIndex, GetManyError, ReusePolicy};
+use typed_generational_arena::ExhaustionPolicy;
 use typed_generational_arena::NanoArena as Arena;
 use std::collections::BTreeSet;
 
@@ -39,7 +41,7 @@ fn try_insert_when_full() {
 #[test]
 fn insert_many_and_cause_doubling() {
     let mut arena = Arena::new();
-    // We can't hold more than `std::u8::MAX` items, so this was reduced from 1000 to 255.
+    // We can't hold more than `u8::MAX` items, so this was reduced from 1000 to 255.
     let indices: Vec<_> = (0..255).map(|i| arena.insert(i * i)).collect();
     for (i, idx) in indices.iter().cloned().enumerate() {
         assert_eq!(arena.remove(idx).unwrap(), i * i);
@@ -109,7 +111,7 @@ fn index_deleted_item() {
     let mut arena = Arena::new();
     let idx = arena.insert(42);
     arena.remove(idx);
-    arena[idx];
+    let _ = arena[idx];
 }
 
 #[test]
@@ -182,3 +184,421 @@ fn clear() {
     assert_eq!(arena.capacity(), 4);
     assert_eq!(arena.len(), 0);
 }
+
+#[test]
+fn insert_with_sees_own_index() {
+    let mut arena = Arena::with_capacity(1);
+    let idx1 = arena.insert_with(|idx| (idx.to_idx(), 1));
+    let idx2 = arena.insert_with(|idx| (idx.to_idx(), 2));
+    assert_eq!(arena[idx1], (idx1.to_idx(), 1));
+    assert_eq!(arena[idx2], (idx2.to_idx(), 2));
+}
+
+#[test]
+fn try_insert_with_when_full() {
+    let mut arena = Arena::with_capacity(1);
+    arena.try_insert_with(|_| 42).ok().unwrap();
+    assert!(arena.try_insert_with(|_| 42).is_err());
+}
+
+#[test]
+fn vacant_entry_index_matches_insertion() {
+    let mut arena = Arena::with_capacity(1);
+    let old = arena.insert(0);
+    arena.remove(old);
+    let entry = arena.vacant_entry();
+    let idx = entry.index();
+    assert!(idx != old);
+    assert_eq!(entry.insert(42), idx);
+    assert_eq!(arena[idx], 42);
+    assert!(!arena.contains(old));
+}
+
+#[test]
+fn dropped_vacant_entry_inserts_nothing() {
+    let mut arena = Arena::<usize>::with_capacity(1);
+    let idx = arena.vacant_entry().index();
+    assert!(!arena.contains(idx));
+    assert_eq!(arena.len(), 0);
+    assert_eq!(arena.insert(42), idx);
+}
+
+#[test]
+fn occupied_entry() {
+    let mut arena = Arena::new();
+    let idx = arena.insert(42);
+    {
+        let mut entry = arena.entry(idx).unwrap();
+        assert_eq!(entry.index(), idx);
+        assert_eq!(*entry.get(), 42);
+        *entry.get_mut() += 1;
+        assert_eq!(entry.replace(44), 43);
+        *entry.into_mut() += 1;
+    }
+    assert_eq!(arena[idx], 45);
+    assert_eq!(arena.entry(idx).unwrap().remove(), 45);
+    assert!(arena.entry(idx).is_none());
+    assert!(!arena.contains(idx));
+}
+
+#[test]
+fn retire_slots_once_shared_generation_is_exhausted() {
+    let mut arena = Arena::with_capacity(2);
+    arena.set_exhaustion_policy(ExhaustionPolicy::Retire);
+    let old = arena.insert(0);
+    let stale: Vec<_> = (0..255)
+        .map(|i| {
+            let idx = arena.insert(i);
+            arena.remove(idx);
+            idx
+        })
+        .collect();
+    assert_eq!(arena.retired_slots(), 0);
+    // The shared generation is now exhausted. Removing `old` frees a slot
+    // which can be reused once at the last generation.
+    arena.remove(old);
+    assert_eq!(arena.retired_slots(), 0);
+    let last = arena.insert(1);
+    assert_eq!(arena.remove(last), Some(1));
+    assert_eq!(arena.retired_slots(), 1);
+    assert!(stale.iter().all(|idx| !arena.contains(*idx)));
+    assert!(!arena.contains(old));
+}
+
+#[test]
+fn wrap_shared_generation() {
+    let mut arena = Arena::with_capacity(1);
+    arena.set_exhaustion_policy(ExhaustionPolicy::Wrap);
+    let first = arena.insert(0);
+    arena.remove(first);
+    for i in 0..255 {
+        let idx = arena.insert(i);
+        arena.remove(idx);
+    }
+    assert_eq!(arena.retired_slots(), 0);
+    assert_eq!(arena.insert(0), first);
+}
+
+#[test]
+#[should_panic]
+fn panic_on_exhausted_shared_generation() {
+    let mut arena = Arena::with_capacity(1);
+    arena.set_exhaustion_policy(ExhaustionPolicy::Panic);
+    for i in 0..256 {
+        let idx = arena.insert(i);
+        arena.remove(idx);
+    }
+}
+
+#[test]
+fn insert_after_drain() {
+    let mut arena = Arena::with_capacity(2);
+    let idx = arena.insert(42);
+    arena.insert(43);
+    assert_eq!(arena.drain().count(), 2);
+    assert_eq!(arena.capacity(), 2);
+    let new_idx = arena.insert(44);
+    assert!(!arena.contains(idx));
+    assert_eq!(arena[new_idx], 44);
+}
+
+#[test]
+fn clear_invalidates_indices() {
+    let mut arena = Arena::with_capacity(2);
+    let idx = arena.insert(42);
+    arena.clear();
+    let new_idx = arena.insert(43);
+    assert_eq!(idx.to_idx(), new_idx.to_idx());
+    assert!(!arena.contains(idx));
+    assert_eq!(arena.remove(idx), None);
+    assert_eq!(arena[new_idx], 43);
+}
+
+#[test]
+fn try_get_reports_why_lookups_fail() {
+    let mut arena = Arena::with_capacity(1);
+    let a = arena.insert(1);
+    assert_eq!(arena.try_get(a), Ok(&1));
+    assert_eq!(arena.try_remove(a), Ok(1));
+    // The slot is free, at a newer generation than `a`
+    match arena.try_get(a) {
+        Err(AccessError::Stale { slot: 0, .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match arena.try_remove(a) {
+        Err(AccessError::Stale { slot: 0, .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let b = arena.insert(2);
+    match arena.try_get_mut(a) {
+        Err(AccessError::Stale {
+            slot: 0,
+            current,
+            requested,
+        }) => assert!(requested.generation_lt(&current)),
+        other => panic!("unexpected {:?}", other),
+    }
+
+    let mut other = Arena::with_capacity(2);
+    other.insert(3);
+    let far = other.insert(4);
+    assert_eq!(
+        arena.try_get(far),
+        Err(AccessError::OutOfBounds {
+            slot: 1,
+            capacity: 1
+        })
+    );
+    // `b` is newer than the first element of `other`
+    match other.try_get(b) {
+        Err(AccessError::Newer {
+            slot: 0,
+            current,
+            requested,
+        }) => assert!(current.generation_lt(&requested)),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "is stale")]
+fn index_stale_item_reports_staleness() {
+    let mut arena = Arena::new();
+    let idx = arena.insert(42);
+    arena.remove(idx);
+    arena.insert(43);
+    let _ = arena[idx];
+}
+
+#[test]
+fn get_many_mut_returns_disjoint_references() {
+    let mut arena = Arena::new();
+    let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+    {
+        let [a, b, c] = arena.get_many_mut([indices[4], indices[0], indices[2]]).unwrap();
+        assert_eq!((*a, *b, *c), (4, 0, 2));
+        std::mem::swap(a, b);
+        *c += 10;
+    }
+    assert_eq!(arena[indices[0]], 4);
+    assert_eq!(arena[indices[4]], 0);
+    assert_eq!(arena[indices[2]], 12);
+
+    let values = arena.get_many_mut_slice(&indices[1..4]).unwrap();
+    assert_eq!(values.iter().map(|v| **v).collect::<Vec<_>>(), vec![1, 12, 3]);
+}
+
+#[test]
+fn get_many_mut_reports_duplicate_and_invalid_indices() {
+    let mut arena = Arena::new();
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    assert_eq!(
+        arena.get_many_mut([a, b, a]),
+        Err(GetManyError::Duplicate {
+            first: 0,
+            second: 2
+        })
+    );
+    arena.remove(b);
+    match arena.get_many_mut_slice(&[a, b]) {
+        Err(GetManyError::Invalid {
+            position: 1,
+            error: AccessError::Stale { slot: 1, .. },
+        }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(arena.get_many_mut([a]), Ok([&mut 1]));
+}
+
+#[test]
+fn growth_stops_at_max_capacity() {
+    let mut arena = Arena::with_capacity(100);
+    assert_eq!(arena.max_capacity(), 256);
+    for i in 0..256 {
+        arena.insert(i);
+    }
+    assert_eq!(arena.capacity(), 256);
+    assert_eq!(
+        arena.try_insert_grow(256),
+        Err((
+            256,
+            CapacityError::IndexSpaceExhausted { max_capacity: 256 }
+        ))
+    );
+    assert_eq!(
+        arena.try_reserve(1),
+        Err(CapacityError::IndexSpaceExhausted { max_capacity: 256 })
+    );
+    arena.reserve(10);
+    assert_eq!(arena.capacity(), 256);
+}
+
+#[test]
+fn reserve_is_clamped_to_max_capacity() {
+    let mut arena = Arena::<u8>::with_capacity(1000);
+    assert_eq!(arena.capacity(), 256);
+    let idx = arena.insert(1);
+    arena.remove(idx);
+    assert_eq!(arena.try_reserve(0), Ok(()));
+}
+
+#[test]
+#[should_panic(expected = "cannot address more than 256 slots")]
+fn insert_past_max_capacity_panics() {
+    let mut arena = Arena::new();
+    for i in 0..257 {
+        arena.insert(i);
+    }
+}
+
+#[test]
+fn lifo_reuses_most_recently_freed_slot() {
+    let mut arena = Arena::with_capacity(4);
+    assert_eq!(arena.reuse_policy(), ReusePolicy::Lifo);
+    let indices: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+    arena.remove(indices[1]);
+    arena.remove(indices[3]);
+    assert_eq!(arena.insert(4).to_idx(), 3);
+    assert_eq!(arena.insert(5).to_idx(), 1);
+}
+
+#[test]
+fn fifo_reuses_least_recently_freed_slot() {
+    let mut arena = Arena::with_reuse_policy(ReusePolicy::Fifo);
+    let indices: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+    arena.remove(indices[1]);
+    arena.remove(indices[3]);
+    arena.remove(indices[0]);
+    assert_eq!(arena.insert(4).to_idx(), 1);
+    assert_eq!(arena.insert(5).to_idx(), 3);
+    assert_eq!(arena.insert(6).to_idx(), 0);
+    // Full, so growing appends fresh slots
+    assert_eq!(arena.insert(7).to_idx(), 4);
+    assert!(!arena.contains(indices[0]));
+}
+
+#[test]
+fn lowest_first_reuses_lowest_free_slot() {
+    let mut arena = Arena::with_reuse_policy(ReusePolicy::LowestFirst);
+    let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+    arena.remove(indices[4]);
+    arena.remove(indices[0]);
+    arena.remove(indices[2]);
+    assert_eq!(arena.insert(6).to_idx(), 0);
+    assert_eq!(arena.insert(7).to_idx(), 2);
+    assert_eq!(arena.insert(8).to_idx(), 4);
+    arena.clear();
+    assert_eq!(arena.insert(9).to_idx(), 0);
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn switching_reuse_policy_keeps_free_slots() {
+    let mut arena = Arena::with_capacity(4);
+    let indices: Vec<_> = (0..4).map(|i| arena.insert(i)).collect();
+    arena.remove(indices[2]);
+    arena.remove(indices[0]);
+    arena.set_reuse_policy(ReusePolicy::LowestFirst);
+    arena.set_reuse_policy(ReusePolicy::Fifo);
+    assert_eq!(arena.insert(4).to_idx(), 0);
+    assert_eq!(arena.insert(5).to_idx(), 2);
+    assert!(arena.try_insert(6).is_err());
+}
+
+#[test]
+fn compact_moves_elements_to_the_front() {
+    let mut arena = Arena::with_capacity(6);
+    let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+    arena.remove(indices[0]);
+    arena.remove(indices[2]);
+    arena.remove(indices[5]);
+
+    let remap = arena.compact();
+    assert_eq!(arena.capacity(), 3);
+    assert_eq!(arena.len(), 3);
+    assert_eq!(remap.len(), 2);
+    assert_eq!(arena[remap.remap(indices[1])], 1);
+    assert_eq!(arena[remap.remap(indices[3])], 3);
+    assert_eq!(arena[remap.remap(indices[4])], 4);
+    assert_eq!(remap.get(indices[1]), None);
+    for (old, new) in remap.iter() {
+        assert!(!arena.contains(*old));
+        assert!(arena.contains(*new));
+    }
+
+    // Stale indices stay invalid when the dropped slots are regrown
+    for i in 6..12 {
+        arena.insert(i);
+    }
+    for (old, _) in remap.iter() {
+        assert!(!arena.contains(*old));
+    }
+    assert!(!arena.contains(indices[0]));
+    assert!(!arena.contains(indices[2]));
+    assert!(!arena.contains(indices[5]));
+    assert_eq!(arena.len(), 9);
+}
+
+#[test]
+fn compact_with_reports_moves() {
+    let mut arena = Arena::with_capacity(4);
+    let a = arena.insert('a');
+    let b = arena.insert('b');
+    arena.remove(a);
+
+    let mut moves = vec![];
+    arena.compact_with(|old, new| moves.push((old, new)));
+    assert_eq!(moves.len(), 1);
+    assert_eq!(moves[0].0, b);
+    assert_eq!(moves[0].1.to_idx(), 0);
+    assert_eq!(arena[moves[0].1], 'b');
+    assert_eq!(arena.capacity(), 1);
+
+    // Nothing left to move
+    arena.compact_with(|_, _| panic!("nothing should move"));
+    assert_eq!(arena.capacity(), 1);
+}
+
+#[test]
+fn shrink_to_fit_keeps_live_indices_and_invalidates_stale_ones() {
+    let mut arena = Arena::with_capacity(8);
+    let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+    for &idx in &indices[2..] {
+        arena.remove(idx);
+    }
+    arena.remove(indices[0]);
+
+    arena.shrink_to_fit();
+    assert_eq!(arena.capacity(), 2);
+    assert_eq!(arena.len(), 1);
+    assert_eq!(arena[indices[1]], 1);
+
+    // Regrow the trimmed region
+    let new_indices: Vec<_> = (0..6).map(|i| arena.insert(10 + i)).collect();
+    assert_eq!(new_indices[0].to_idx(), 0);
+    for &idx in indices.iter().filter(|&&idx| idx != indices[1]) {
+        assert!(!arena.contains(idx));
+    }
+    for (i, &idx) in new_indices.iter().enumerate() {
+        assert_eq!(arena[idx], 10 + i);
+    }
+}
+
+#[test]
+fn shrink_to_keeps_min_capacity() {
+    let mut arena = Arena::with_capacity(16);
+    let idx = arena.insert(1);
+    arena.shrink_to(4);
+    assert_eq!(arena.capacity(), 4);
+    arena.shrink_to(8);
+    assert_eq!(arena.capacity(), 4);
+    arena.remove(idx);
+    arena.shrink_to(0);
+    assert_eq!(arena.capacity(), 0);
+    assert!(arena.try_insert(2).is_err());
+    let new_idx = arena.insert(2);
+    assert!(!arena.contains(idx));
+    assert_eq!(arena[new_idx], 2);
+}
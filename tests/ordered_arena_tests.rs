@@ -0,0 +1,107 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::{AccessError, Index, NonzeroGeneration, OrderedArena};
+
+type Arena = OrderedArena<u32, usize, NonzeroGeneration<usize>>;
+type Idx = Index<u32, usize, NonzeroGeneration<usize>>;
+
+fn values(arena: &Arena) -> Vec<u32> {
+    arena.iter().map(|(_, &value)| value).collect()
+}
+
+#[test]
+fn iterates_in_insertion_order_despite_slot_reuse() {
+    let mut arena = Arena::new();
+    let indices: Vec<_> = (0..5).map(|i| arena.push_back(i)).collect();
+    arena.remove(indices[1]);
+    arena.remove(indices[3]);
+    // These reuse the freed slots, but go at the back of the list
+    let five = arena.push_back(5);
+    let six = arena.push_back(6);
+    assert_eq!(values(&arena), [0, 2, 4, 5, 6]);
+    assert!(five.to_idx() < indices[4].to_idx());
+    assert!(six.to_idx() < indices[4].to_idx());
+
+    let reversed: Vec<_> = arena.iter().rev().map(|(_, &value)| value).collect();
+    assert_eq!(reversed, [6, 5, 4, 2, 0]);
+    let items: Vec<Idx> = arena.iter().map(|(index, _)| index).collect();
+    assert_eq!(items, [indices[0], indices[2], indices[4], five, six]);
+}
+
+#[test]
+fn ends_and_neighbours() {
+    let mut arena = Arena::new();
+    assert_eq!(arena.front(), None);
+    let b = arena.push_front(2);
+    let a = arena.push_front(1);
+    let c = arena.insert_after(b, 3).unwrap();
+    assert_eq!(arena.front(), Some(a));
+    assert_eq!(arena.back(), Some(c));
+    assert_eq!(arena.next(a), Some(b));
+    assert_eq!(arena.prev(c), Some(b));
+    assert_eq!(arena.prev(a), None);
+    assert_eq!(arena.next(c), None);
+
+    arena.remove(c);
+    assert_eq!(arena.back(), Some(b));
+    assert_eq!(arena.next(b), None);
+    assert_eq!(arena.next(c), None);
+    arena.remove(a);
+    assert_eq!(arena.front(), Some(b));
+    arena.remove(b);
+    assert_eq!(arena.front(), None);
+    assert_eq!(arena.back(), None);
+    assert_eq!(arena.iter().count(), 0);
+}
+
+#[test]
+fn user_defined_order() {
+    let mut arena = Arena::new();
+    let indices: Vec<_> = (0..4).map(|i| arena.push_back(i)).collect();
+    arena.move_before(indices[3], indices[0]).unwrap();
+    assert_eq!(values(&arena), [3, 0, 1, 2]);
+    arena.move_before(indices[0], indices[2]).unwrap();
+    assert_eq!(values(&arena), [3, 1, 0, 2]);
+    arena.move_before(indices[1], indices[1]).unwrap();
+    assert_eq!(values(&arena), [3, 1, 0, 2]);
+    arena.insert_after(indices[2], 4).unwrap();
+    assert_eq!(values(&arena), [3, 1, 0, 2, 4]);
+    assert_eq!(arena.iter().len(), 5);
+}
+
+#[test]
+fn stale_indices_are_rejected() {
+    let mut arena = Arena::new();
+    let a = arena.push_back(1);
+    let b = arena.push_back(2);
+    arena.remove(a);
+    let c = arena.push_back(3);
+    assert_eq!(c.to_idx(), a.to_idx());
+
+    assert_eq!(arena.get(a), None);
+    assert_eq!(arena.next(a), None);
+    assert_eq!(arena.remove(a), None);
+    match arena.insert_after(a, 4) {
+        Err(AccessError::Stale { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match arena.move_before(b, a) {
+        Err(AccessError::Stale { .. }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(values(&arena), [2, 3]);
+    arena[c] += 10;
+    assert_eq!(arena[c], 13);
+}
+
+#[test]
+fn clear_resets_the_list() {
+    let mut arena = Arena::new();
+    let a = arena.push_back(1);
+    arena.push_back(2);
+    arena.clear();
+    assert!(arena.is_empty());
+    assert!(!arena.contains(a));
+    assert_eq!(arena.front(), None);
+    arena.push_back(3);
+    assert_eq!(values(&arena), [3]);
+}
@@ -124,3 +124,23 @@ fn clear() {
     assert_eq!(slab.capacity(), 4);
     assert_eq!(slab.len(), 0);
 }
+
+#[test]
+fn insert_after_drain() {
+    let mut slab = Slab::with_capacity(2);
+    slab.insert(42);
+    slab.insert(43);
+    assert_eq!(slab.drain().count(), 2);
+    assert_eq!(slab.capacity(), 2);
+    let idx = slab.insert(44);
+    assert_eq!(slab[idx], 44);
+    assert_eq!(slab.len(), 1);
+}
+
+#[test]
+fn max_capacity_excludes_zero() {
+    let slab = typed_generational_arena::SmallPtrSlab::<()>::new();
+    assert_eq!(slab.max_capacity(), u32::MAX as usize);
+    let slab = Slab::<()>::new();
+    assert_eq!(slab.max_capacity(), usize::MAX);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "rayon")]
+
+extern crate rayon;
+extern crate typed_generational_arena;
+
+use rayon::prelude::*;
+use typed_generational_arena::{
+    Arena, ArenaIndex, GenerationStrategy, GenerationalIndex, StandardArena, StandardSlotArena,
+    TinyArena,
+};
+
+/// Build an arena with holes, by inserting `0..n` and removing every third
+/// element
+fn arena_with_holes<I, G, S>(n: usize) -> Arena<usize, I, G, S>
+where
+    I: ArenaIndex,
+    G: GenerationalIndex,
+    S: GenerationStrategy,
+{
+    let mut arena = Arena::new();
+    let indices: Vec<_> = (0..n).map(|i| arena.insert(i)).collect();
+    for idx in indices.into_iter().step_by(3) {
+        arena.remove(idx);
+    }
+    arena
+}
+
+#[test]
+fn par_iter_matches_iter() {
+    let arena: StandardArena<usize> = arena_with_holes(10_000);
+    let mut parallel: Vec<_> = arena.par_iter().collect();
+    parallel.sort_by_key(|(idx, _)| idx.to_idx());
+    let sequential: Vec<_> = arena.iter().collect();
+    assert_eq!(parallel, sequential);
+    assert_eq!((&arena).into_par_iter().count(), arena.len());
+}
+
+#[test]
+fn par_iter_mut_matches_iter_mut() {
+    let mut arena: StandardSlotArena<usize> = arena_with_holes(10_000);
+    arena.par_iter_mut().for_each(|(idx, value)| {
+        assert_eq!(*value, idx.to_idx());
+        *value *= 2;
+    });
+    (&mut arena).into_par_iter().for_each(|(_, value)| *value += 1);
+    assert!(arena.iter().all(|(idx, value)| *value == idx.to_idx() * 2 + 1));
+    assert_eq!(arena.len(), 6_666);
+}
+
+#[test]
+fn par_values_mut_visits_every_element() {
+    let mut arena: TinyArena<usize> = arena_with_holes(1_000);
+    arena.par_values_mut().for_each(|value| *value += 1);
+    assert!(arena.iter().all(|(idx, value)| *value == idx.to_idx() + 1));
+    assert_eq!(arena.par_values_mut().count(), arena.len());
+}
+
+#[test]
+fn par_retain_matches_retain() {
+    let mut parallel: StandardArena<usize> = arena_with_holes(10_000);
+    let mut sequential = parallel.clone();
+    parallel.par_retain(|_, value| value % 5 != 0);
+    sequential.retain(|_, value| value % 5 != 0);
+    assert_eq!(
+        parallel.iter().collect::<Vec<_>>(),
+        sequential.iter().collect::<Vec<_>>()
+    );
+    // Both arenas hand out the same indices afterwards
+    for i in 0..5_000 {
+        assert_eq!(parallel.insert(i), sequential.insert(i));
+    }
+}
+
+#[test]
+fn par_extend_and_collect_insert_in_order() {
+    let mut parallel: StandardArena<usize> = arena_with_holes(100);
+    let mut sequential = parallel.clone();
+    parallel.par_extend((0..10_000).into_par_iter());
+    sequential.extend(0..10_000);
+    assert_eq!(
+        parallel.iter().collect::<Vec<_>>(),
+        sequential.iter().collect::<Vec<_>>()
+    );
+
+    let collected: StandardArena<usize> = (0..10_000).into_par_iter().collect();
+    assert!(collected.iter().all(|(idx, value)| *value == idx.to_idx()));
+    assert_eq!(collected.len(), 10_000);
+}
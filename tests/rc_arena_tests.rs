@@ -0,0 +1,130 @@
+extern crate typed_generational_arena;
+use std::cell::Cell;
+use std::rc::Rc;
+use typed_generational_arena::{NonzeroGeneration, RcArena, StrongHandle};
+
+type Arena<T> = RcArena<T, usize, NonzeroGeneration<usize>>;
+
+/// Counts how many times it was dropped
+struct Tracked(Rc<Cell<usize>>);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn element_is_removed_with_its_last_strong_handle() {
+    let drops = Rc::new(Cell::new(0));
+    let arena = Arena::new();
+    let first = arena.insert(Tracked(drops.clone()));
+    let weak = first.downgrade();
+    let second = first.clone();
+    assert_eq!(arena.strong_count(weak), 2);
+    assert_eq!(arena.len(), 1);
+
+    drop(first);
+    assert_eq!(drops.get(), 0);
+    assert!(arena.contains(weak));
+    assert_eq!(second.strong_count(), 1);
+
+    drop(second);
+    assert_eq!(drops.get(), 1);
+    assert!(!arena.contains(weak));
+    assert!(arena.upgrade(weak).is_none());
+    assert_eq!(arena.strong_count(weak), 0);
+    assert!(arena.is_empty());
+}
+
+#[test]
+fn weak_handles_are_generation_checked() {
+    let arena = Arena::new();
+    let old = arena.insert(1);
+    let weak = old.downgrade();
+    drop(old);
+    let new = arena.insert(2);
+    assert_eq!(new.index().to_idx(), weak.to_idx());
+    assert!(arena.upgrade(weak).is_none());
+    let upgraded = arena.upgrade(new.downgrade()).unwrap();
+    assert_eq!(upgraded, new);
+    assert_eq!(*arena.get(&upgraded), 2);
+}
+
+#[test]
+fn elements_can_be_mutated_through_strong_handles() {
+    let arena = Arena::new();
+    let handle = arena.insert(vec![1, 2]);
+    arena.get_mut(&handle).push(3);
+    assert_eq!(*arena.get(&handle), vec![1, 2, 3]);
+}
+
+#[test]
+fn removal_while_borrowed_is_deferred() {
+    let drops = Rc::new(Cell::new(0));
+    let arena = Arena::new();
+    let kept = arena.insert(Tracked(drops.clone()));
+    let released = arena.insert(Tracked(drops.clone()));
+    let weak = released.downgrade();
+    {
+        let _guard = arena.get(&kept);
+        drop(released);
+        // The element is unreachable, but only dropped once the arena is free
+        assert_eq!(drops.get(), 0);
+        assert!(arena.upgrade(weak).is_none());
+        assert_eq!(arena.len(), 1);
+    }
+    let other = arena.insert(Tracked(drops.clone()));
+    assert_eq!(drops.get(), 1);
+    assert_eq!(arena.len(), 2);
+    drop(other);
+    drop(kept);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn elements_can_hold_handles_into_their_own_arena() {
+    struct Node {
+        next: Option<StrongHandle<Node, usize, NonzeroGeneration<usize>>>,
+        _tracked: Tracked,
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let arena = Arena::new();
+    let mut head = None;
+    for _ in 0..10 {
+        head = Some(arena.insert(Node {
+            next: head.take(),
+            _tracked: Tracked(drops.clone()),
+        }));
+    }
+    assert_eq!(arena.len(), 10);
+    let second = arena.get(head.as_ref().unwrap()).next.clone().unwrap();
+
+    // Dropping the head releases it, but its successor is still held
+    drop(head);
+    assert_eq!(drops.get(), 1);
+    assert_eq!(arena.len(), 9);
+    drop(second);
+    assert_eq!(drops.get(), 10);
+    assert!(arena.is_empty());
+}
+
+#[test]
+fn handles_outlive_the_arena() {
+    let arena = Arena::new();
+    let handle = arena.insert("still here");
+    let clone = handle.clone();
+    drop(arena);
+    assert_eq!(clone.strong_count(), 2);
+}
+
+#[test]
+#[should_panic(expected = "strong handle belongs to another arena")]
+fn handles_from_another_arena_are_rejected() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    arena.insert(0);
+    let foreign = other.insert(1);
+    arena.get(&foreign);
+}
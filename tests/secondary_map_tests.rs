@@ -0,0 +1,228 @@
+extern crate typed_generational_arena;
+use typed_generational_arena::secondary;
+use typed_generational_arena::sparse_secondary;
+use typed_generational_arena::{
+    NanoArena, SecondaryMap, SparseSecondaryMap, StandardArena, StandardSlotArena, TinyArena,
+};
+
+#[test]
+fn secondary_map_holds_values_for_live_indices() {
+    let mut arena = StandardArena::new();
+    let mut map = SecondaryMap::new();
+    let a = arena.insert('a');
+    let b = arena.insert('b');
+    assert_eq!(map.insert(a, 1), None);
+    assert_eq!(map.insert(b, 2), None);
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[a], 1);
+    assert_eq!(map.get(b), Some(&2));
+    *map.get_mut(b).unwrap() += 10;
+    assert_eq!(map[b], 12);
+    assert_eq!(map.remove(a), Some(1));
+    assert_eq!(map.remove(a), None);
+    assert!(!map.contains_key(a));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn secondary_map_misses_stale_indices() {
+    let mut arena = StandardArena::new();
+    let mut map = SecondaryMap::new();
+    let old = arena.insert(());
+    map.insert(old, "old");
+    arena.remove(old);
+    let new = arena.insert(());
+    assert_eq!(old.to_idx(), new.to_idx());
+
+    // The value for the removed element is not visible to the new one, and is
+    // dropped when a value is inserted for it
+    assert_eq!(map.get(new), None);
+    assert_eq!(map.insert(new, "new"), None);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(old), None);
+
+    // A stale index cannot remove the value of the new element
+    assert_eq!(map.remove(old), None);
+    assert_eq!(map[new], "new");
+}
+
+#[test]
+fn secondary_map_misses_stale_indices_with_per_slot_generations() {
+    let mut arena = StandardSlotArena::new();
+    let mut map = SecondaryMap::new();
+    let a = arena.insert(());
+    let b = arena.insert(());
+    arena.remove(a);
+    let c = arena.insert(());
+    map.insert(b, 'b');
+    map.insert(c, 'c');
+    assert_eq!(map.get(a), None);
+    assert_eq!(map.remove(a), None);
+    assert_eq!(map[c], 'c');
+}
+
+#[test]
+fn secondary_maps_take_live_indices_once_generations_wrap() {
+    let mut arena = NanoArena::new();
+    let mut dense = SecondaryMap::new();
+    let mut sparse = SparseSecondaryMap::new();
+    let mut idx = arena.insert(());
+    for i in 0..300 {
+        assert_eq!(dense.insert(idx, i), None);
+        assert_eq!(sparse.insert(idx, i), None);
+        assert_eq!(dense[idx], i);
+        assert_eq!(sparse[idx], i);
+        arena.remove(idx);
+        idx = arena.insert(());
+    }
+    assert_eq!(dense.len(), 1);
+    assert_eq!(sparse.len(), 1);
+}
+
+#[test]
+fn secondary_map_entry() {
+    let mut arena = TinyArena::new();
+    let mut map = SecondaryMap::new();
+    let idx = arena.insert(());
+
+    match map.entry(idx) {
+        secondary::Entry::Vacant(entry) => {
+            assert_eq!(entry.index(), idx);
+            *entry.insert(1) += 1;
+        }
+        secondary::Entry::Occupied(_) => panic!("entry should be vacant"),
+    }
+    assert_eq!(map[idx], 2);
+
+    match map.entry(idx) {
+        secondary::Entry::Occupied(mut entry) => {
+            assert_eq!(*entry.get(), 2);
+            assert_eq!(entry.insert(3), 2);
+            assert_eq!(entry.remove(), 3);
+        }
+        secondary::Entry::Vacant(_) => panic!("entry should be occupied"),
+    }
+    assert!(map.is_empty());
+
+    assert_eq!(*map.entry(idx).or_default(), 0);
+    map.entry(idx).and_modify(|v| *v += 5).or_insert(100);
+    assert_eq!(map[idx], 5);
+}
+
+#[test]
+fn secondary_map_iterates_in_slot_order() {
+    let mut arena = StandardArena::new();
+    let indices: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+    let mut map = SecondaryMap::new();
+    for &idx in indices.iter().rev().step_by(2) {
+        map.insert(idx, arena[idx] * 10);
+    }
+
+    let iter = map.iter();
+    assert_eq!(iter.len(), 5);
+    let pairs: Vec<_> = iter.map(|(idx, &v)| (idx, v)).collect();
+    let expected: Vec<_> = (0..10)
+        .filter(|i| i % 2 == 1)
+        .map(|i| (indices[i], i * 10))
+        .collect();
+    assert_eq!(pairs, expected);
+
+    for (_, value) in &mut map {
+        *value += 1;
+    }
+    assert_eq!(map[indices[9]], 91);
+}
+
+#[test]
+fn secondary_map_retain() {
+    let mut arena = StandardArena::new();
+    let mut map: SecondaryMap<_, _, _, _> = (0..10).map(|i| (arena.insert(()), i)).collect();
+    map.retain(|_, value| {
+        *value *= 2;
+        *value % 4 == 0
+    });
+    assert_eq!(map.len(), 5);
+    assert!(map.iter().all(|(_, &value)| value % 4 == 0));
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+}
+
+#[test]
+fn sparse_secondary_map_holds_values_for_live_indices() {
+    let mut arena = StandardArena::new();
+    let mut map = SparseSecondaryMap::new();
+    let a = arena.insert('a');
+    let b = arena.insert('b');
+    assert_eq!(map.insert(a, 1), None);
+    assert_eq!(map.insert(b, 2), None);
+    assert_eq!(map.insert(b, 3), Some(2));
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[a], 1);
+    assert_eq!(map.remove(a), Some(1));
+    assert!(!map.contains_key(a));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn sparse_secondary_map_misses_stale_indices() {
+    let mut arena = StandardArena::new();
+    let mut map = SparseSecondaryMap::new();
+    let old = arena.insert(());
+    map.insert(old, "old");
+    arena.remove(old);
+    let new = arena.insert(());
+
+    assert_eq!(map.get(new), None);
+    assert_eq!(map.insert(new, "new"), None);
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.remove(old), None);
+    assert_eq!(map[new], "new");
+}
+
+#[test]
+fn sparse_secondary_map_entry() {
+    let mut arena = StandardArena::new();
+    let mut map = SparseSecondaryMap::new();
+    let old = arena.insert(());
+    map.insert(old, 7);
+    arena.remove(old);
+    let idx = arena.insert(());
+
+    // The slot holds a value for an older generation, so the entry is vacant
+    match map.entry(idx) {
+        sparse_secondary::Entry::Vacant(entry) => assert_eq!(*entry.insert(1), 1),
+        sparse_secondary::Entry::Occupied(_) => panic!("entry should be vacant"),
+    }
+    assert_eq!(map.len(), 1);
+
+    match map.entry(idx) {
+        sparse_secondary::Entry::Occupied(entry) => assert_eq!(entry.remove(), 1),
+        sparse_secondary::Entry::Vacant(_) => panic!("entry should be occupied"),
+    }
+    assert!(map.is_empty());
+
+    *map.entry(idx).or_insert(10) += 1;
+    map.entry(idx).and_modify(|v| *v *= 2);
+    assert_eq!(map[idx], 22);
+}
+
+#[test]
+fn sparse_secondary_map_iter_and_retain() {
+    let mut arena = StandardArena::new();
+    let indices: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+    let mut map: SparseSecondaryMap<_, _, _, _> =
+        indices.iter().map(|&idx| (idx, arena[idx])).collect();
+
+    let mut pairs: Vec<_> = map.iter().map(|(idx, &v)| (idx, v)).collect();
+    pairs.sort();
+    assert_eq!(pairs, indices.iter().cloned().zip(0..10).collect::<Vec<_>>());
+
+    map.retain(|idx, value| {
+        *value += 1;
+        idx.to_idx() < 3
+    });
+    let mut values: Vec<_> = map.iter_mut().map(|(_, value)| *value).collect();
+    values.sort();
+    assert_eq!(values, vec![1, 2, 3]);
+}
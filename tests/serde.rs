@@ -5,9 +5,16 @@ extern crate serde;
 extern crate bincode;
 extern crate serde_test;
 
-use typed_generational_arena::{U64Arena as Arena, U64Index as Index};
+use typed_generational_arena::{
+    ArenaIndex, DisableRemoval, ExhaustionPolicy, GenerationStrategy, GenerationalIndex,
+    IgnoreGeneration, NanoArena, PicoArena, PtrSlab, ReusePolicy, SecondaryMap, SmallArena,
+    SmallPtrSlab, SmallSlab, SmallSlotArena, SparseSecondaryMap, StandardArena, StandardIdArena,
+    StandardIdSlotArena, StandardSlab, StandardSlotArena, TinyArena, TinySlotArena, TinyWrapArena,
+    TinyWrapSlotArena, U64Arena as Arena, U64Index as Index,
+};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use serde_test::{assert_ser_tokens, Token};
+use serde_test::{assert_ser_tokens, Configure, Token};
 use std::fmt::Debug;
 
 #[test]
@@ -110,42 +117,602 @@ fn sparse_deserialized_arena_can_use_whole_elements_in_free_list() {
     assert_eq!(arena.capacity(), capacity);
 }
 
+#[test]
+fn stale_index_stays_stale_after_round_trip() {
+    let mut arena = Arena::new();
+    let a = arena.insert("apple");
+    assert_eq!(arena.remove(a), Some("apple"));
+    let b = arena.insert("banana");
+    let c = arena.insert("cherry");
+    // `c` had the greatest generation in the arena
+    assert_eq!(arena.remove(c), Some("cherry"));
+
+    let bytes = bincode::serialize(&arena).expect("arena must be serialized");
+    let de_arena =
+        bincode::deserialize::<Arena<&str>>(&bytes).expect("arena must be deserialized");
+    for arena in &mut [arena, de_arena] {
+        let d = arena.insert("durian");
+        assert_ne!(d, c);
+        assert_eq!(arena.get(b), Some(&"banana"));
+        assert_eq!(arena.get(c), None);
+    }
+}
+
+#[test]
+fn stale_index_stays_stale_after_round_trip_with_per_slot_generations() {
+    let mut arena = StandardSlotArena::new();
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    assert_eq!(arena.remove(a), Some(1));
+    assert_eq!(arena.remove(b), Some(2));
+
+    let bytes = bincode::serialize(&arena).expect("arena must be serialized");
+    let mut de_arena = bincode::deserialize::<StandardSlotArena<i32>>(&bytes)
+        .expect("arena must be deserialized");
+    let c = de_arena.insert(3);
+    let d = de_arena.insert(4);
+    assert_eq!(c, arena.insert(3));
+    assert_eq!(d, arena.insert(4));
+    assert_eq!(de_arena.get(a), None);
+    assert_eq!(de_arena.get(b), None);
+}
+
+#[test]
+fn free_list_order_is_preserved_by_round_trip() {
+    let mut arena = Arena::with_capacity(6);
+    let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+    for &i in &[3, 0, 5, 1] {
+        arena.remove(indices[i]);
+    }
+
+    let bytes = bincode::serialize(&arena).expect("arena must be serialized");
+    let mut de_arena =
+        bincode::deserialize::<Arena<usize>>(&bytes).expect("arena must be deserialized");
+    assert_eq!(de_arena.len(), arena.len());
+    assert_eq!(de_arena.capacity(), arena.capacity());
+    for i in 0..4 {
+        assert_eq!(de_arena.insert(10 + i), arena.insert(10 + i));
+    }
+}
+
+#[test]
+fn policies_are_preserved_by_round_trip() {
+    for &reuse in &[ReusePolicy::Fifo, ReusePolicy::LowestFirst] {
+        let mut arena = Arena::with_reuse_policy(reuse);
+        arena.set_exhaustion_policy(ExhaustionPolicy::Retire);
+        let indices: Vec<_> = (0..6).map(|i| arena.insert(i)).collect();
+        for &i in &[3, 0, 5, 1] {
+            arena.remove(indices[i]);
+        }
+
+        let mut de_arena: Arena<usize> = round_trip(&arena);
+        assert_eq!(de_arena.reuse_policy(), reuse);
+        assert_eq!(de_arena.exhaustion_policy(), ExhaustionPolicy::Retire);
+        for i in 0..4 {
+            assert_eq!(de_arena.insert(10 + i), arena.insert(10 + i));
+        }
+    }
+}
+
 #[test]
 fn empty_arena_can_be_serialized_and_deserialized() {
     let arena = Arena::<()>::new();
     let cap = arena.capacity();
     let mut tokens = vec![Token::Seq { len: Some(cap) }];
-    tokens.extend(vec![Token::None; cap]);
+    tokens.extend((0..cap).map(|i| Token::U64(i as u64)));
+    tokens.push(Token::SeqEnd);
+    tokens.push(Token::Seq { len: Some(cap) });
+    for _ in 0..cap {
+        tokens.extend(&[
+            Token::NewtypeVariant {
+                name: "Slot",
+                variant: "Free",
+            },
+            Token::U64(0),
+        ]);
+    }
     tokens.push(Token::SeqEnd);
-    assert_tokens(&arena, &tokens);
+    assert_tokens(&arena, 0, &tokens);
 }
 
 #[test]
 fn fully_occupied_arena_can_be_serialized_and_deserialized() {
     let mut arena = Arena::with_capacity(30); // 30 is greater than default capacity(4)
-    let mut tokens = vec![Token::Seq { len: Some(30) }];
+    let mut tokens = vec![
+        Token::Seq { len: Some(0) },
+        Token::SeqEnd,
+        Token::Seq { len: Some(30) },
+    ];
     for i in 0..arena.capacity() {
         let _ = arena.insert(i * i);
         tokens.extend(&[
-            Token::Some,
-            Token::Tuple { len: 2 },
+            Token::TupleVariant {
+                name: "Slot",
+                variant: "Occupied",
+                len: 2,
+            },
             Token::U64(0),
             Token::U64((i * i) as u64),
-            Token::TupleEnd,
+            Token::TupleVariantEnd,
         ]);
     }
     tokens.push(Token::SeqEnd);
-    assert_tokens(&arena, &tokens);
+    assert_tokens(&arena, 0, &tokens);
+}
+
+#[test]
+fn sparse_arena_can_be_serialized_and_deserialized() {
+    let mut arena = Arena::with_capacity(3);
+    let a = arena.insert('a');
+    arena.insert('b');
+    let c = arena.insert('c');
+    arena.remove(c);
+    arena.remove(a);
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::U64(0),
+        Token::U64(2),
+        Token::SeqEnd,
+        Token::Seq { len: Some(3) },
+        Token::NewtypeVariant {
+            name: "Slot",
+            variant: "Free",
+        },
+        Token::U64(2),
+        Token::TupleVariant {
+            name: "Slot",
+            variant: "Occupied",
+            len: 2,
+        },
+        Token::U64(0),
+        Token::Char('b'),
+        Token::TupleVariantEnd,
+        Token::NewtypeVariant {
+            name: "Slot",
+            variant: "Free",
+        },
+        Token::U64(1),
+        Token::SeqEnd,
+    ];
+    assert_tokens(&arena, 2, &tokens);
+}
+
+#[test]
+fn legacy_format_can_be_deserialized() {
+    let mut arena = Arena::with_capacity(3);
+    let a = arena.insert('a');
+    arena.insert('b');
+    arena.remove(a);
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::None,
+        Token::Some,
+        Token::Tuple { len: 2 },
+        Token::U64(0),
+        Token::Char('b'),
+        Token::TupleEnd,
+        Token::SeqEnd,
+    ];
+    serde_test::assert_de_tokens(&ArenaCompare(arena).readable(), &compare_tokens(&tokens));
+}
+
+#[test]
+fn legacy_arena_does_not_reuse_indices_of_removed_elements() {
+    // The legacy form of an arena whose element 'a' at slot 0 and generation
+    // 0 was removed
+    let legacy: Vec<Option<(u64, char)>> = vec![None, Some((0, 'b'))];
+    let bytes = bincode::serialize(&legacy).expect("legacy arena must be serialized");
+    let mut arena =
+        bincode::deserialize::<Arena<char>>(&bytes).expect("arena must be deserialized");
+    let stale = Index::new(0, 0);
+    let c = arena.insert('c');
+    assert_eq!(c.to_idx(), 0);
+    assert_ne!(c, stale);
+    assert_eq!(arena.get(stale), None);
+}
+
+#[test]
+fn unknown_format_version_is_rejected() {
+    let tokens = [
+        Token::Struct {
+            name: "Arena",
+            len: 6,
+        },
+        Token::Str("version"),
+        Token::U32(2),
+    ];
+    serde_test::assert_de_tokens_error::<serde_test::Readable<Arena<()>>>(
+        &tokens,
+        "invalid value: integer `2`, expected a supported arena format version",
+    );
+}
+
+#[test]
+fn free_list_not_matching_free_slots_is_rejected() {
+    let mut tokens = vec![
+        Token::Struct {
+            name: "Arena",
+            len: 6,
+        },
+        Token::Str("version"),
+        Token::U32(1),
+        Token::Str("generation"),
+        Token::U64(0),
+        Token::Str("free_list"),
+        // Slot 0 is occupied
+        Token::Seq { len: Some(1) },
+        Token::U64(0),
+        Token::SeqEnd,
+        Token::Str("slots"),
+        Token::Seq { len: Some(2) },
+        Token::TupleVariant {
+            name: "Slot",
+            variant: "Occupied",
+            len: 2,
+        },
+        Token::U64(0),
+        Token::Unit,
+        Token::TupleVariantEnd,
+        Token::NewtypeVariant {
+            name: "Slot",
+            variant: "Free",
+        },
+        Token::U64(0),
+        Token::SeqEnd,
+    ];
+    tokens.extend(default_policy_tokens(true));
+    tokens.push(Token::StructEnd);
+    serde_test::assert_de_tokens_error::<serde_test::Readable<Arena<()>>>(
+        &tokens,
+        "free list does not match the free slots of the arena",
+    );
+}
+
+/// Tokens of a versioned arena with the slots `slots`, and no free list
+fn versioned_arena_tokens(slots: Vec<Token>, len: usize) -> Vec<Token> {
+    let mut tokens = vec![
+        Token::Struct {
+            name: "Arena",
+            len: 6,
+        },
+        Token::Str("version"),
+        Token::U32(1),
+        Token::Str("generation"),
+        Token::U8(0),
+        Token::Str("free_list"),
+        Token::Seq { len: Some(0) },
+        Token::SeqEnd,
+        Token::Str("slots"),
+        Token::Seq { len: Some(len) },
+    ];
+    tokens.extend(slots);
+    tokens.push(Token::SeqEnd);
+    tokens.extend(default_policy_tokens(true));
+    tokens.push(Token::StructEnd);
+    tokens
+}
+
+#[test]
+fn more_free_slots_than_the_index_type_can_address_are_rejected() {
+    let mut slots = Vec::new();
+    for _ in 0..300 {
+        slots.extend_from_slice(&[
+            Token::NewtypeVariant {
+                name: "Slot",
+                variant: "Free",
+            },
+            Token::U8(0),
+        ]);
+    }
+    serde_test::assert_de_tokens_error::<serde_test::Readable<NanoArena<u32>>>(
+        &versioned_arena_tokens(slots, 300),
+        "invalid length 300, expected no more slots than the index type can address",
+    );
+}
+
+#[test]
+fn more_occupied_slots_than_the_index_type_can_address_are_rejected() {
+    let mut slots = Vec::new();
+    for i in 0..300 {
+        slots.extend_from_slice(&[
+            Token::TupleVariant {
+                name: "Slot",
+                variant: "Occupied",
+                len: 2,
+            },
+            Token::U8(0),
+            Token::U32(i),
+            Token::TupleVariantEnd,
+        ]);
+    }
+    serde_test::assert_de_tokens_error::<serde_test::Readable<NanoArena<u32>>>(
+        &versioned_arena_tokens(slots, 300),
+        "invalid length 300, expected no more slots than the index type can address",
+    );
+}
+
+#[test]
+fn legacy_arena_with_more_slots_than_the_index_type_can_address_is_rejected() {
+    let mut tokens = vec![Token::Seq { len: Some(300) }];
+    // Deserialization stops at the first slot past the last addressable one
+    for i in 0..257 {
+        tokens.extend_from_slice(&[
+            Token::Some,
+            Token::Tuple { len: 2 },
+            Token::U8(0),
+            Token::U32(i),
+            Token::TupleEnd,
+        ]);
+    }
+    serde_test::assert_de_tokens_error::<serde_test::Readable<NanoArena<u32>>>(
+        &tokens,
+        "invalid length 257, expected no more slots than the index type can address",
+    );
+}
+
+#[test]
+fn slab_is_serialized_as_sequence_of_values() {
+    let mut slab = StandardSlab::with_capacity(4);
+    slab.insert('a');
+    slab.insert('b');
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::Char('a'),
+        Token::Char('b'),
+        Token::SeqEnd,
+    ];
+    assert_ser_tokens(&slab, &tokens);
+}
+
+#[test]
+fn u64_arena_round_trip() {
+    assert_round_trip(Arena::new());
+}
+
+#[test]
+fn standard_arena_round_trip() {
+    assert_round_trip(StandardArena::new());
+}
+
+#[test]
+fn small_arena_round_trip() {
+    assert_round_trip(SmallArena::new());
+}
+
+#[test]
+fn tiny_arena_round_trip() {
+    assert_round_trip(TinyArena::new());
+}
+
+#[test]
+fn tiny_wrap_arena_round_trip() {
+    assert_round_trip(TinyWrapArena::new());
+}
+
+#[test]
+fn nano_arena_round_trip() {
+    assert_round_trip(NanoArena::new());
+}
+
+#[test]
+fn pico_arena_round_trip() {
+    assert_round_trip(PicoArena::new());
+}
+
+#[test]
+fn standard_slot_arena_round_trip() {
+    assert_round_trip(StandardSlotArena::new());
+}
+
+#[test]
+fn small_slot_arena_round_trip() {
+    assert_round_trip(SmallSlotArena::new());
+}
+
+#[test]
+fn tiny_slot_arena_round_trip() {
+    assert_round_trip(TinySlotArena::new());
+}
+
+#[test]
+fn tiny_wrap_slot_arena_round_trip() {
+    assert_round_trip(TinyWrapSlotArena::new());
+}
+
+#[test]
+fn standard_id_arena_round_trip() {
+    assert_round_trip(StandardIdArena::new());
+}
+
+#[test]
+fn standard_id_slot_arena_round_trip() {
+    assert_round_trip(StandardIdSlotArena::new());
+}
+
+#[test]
+fn deserialized_arena_keeps_its_id() {
+    let mut arena = StandardIdArena::new();
+    let idx = arena.insert(42);
+    let mut other = StandardIdArena::new();
+    let other_idx = other.insert(43);
+
+    let de_arena = round_trip(&arena);
+    assert_eq!(de_arena.get(idx), Some(&42));
+    assert_eq!(de_arena.get(other_idx), None);
+}
+
+#[test]
+fn ignore_generation_arena_round_trip() {
+    assert_round_trip(typed_generational_arena::Arena::<_, usize, IgnoreGeneration>::new());
+}
+
+#[test]
+fn standard_slab_round_trip() {
+    assert_slab_round_trip(StandardSlab::new());
+}
+
+#[test]
+fn small_slab_round_trip() {
+    assert_slab_round_trip(SmallSlab::new());
+}
+
+#[test]
+fn ptr_slab_round_trip() {
+    assert_slab_round_trip(PtrSlab::new());
+}
+
+#[test]
+fn small_ptr_slab_round_trip() {
+    assert_slab_round_trip(SmallPtrSlab::new());
+}
+
+#[test]
+fn secondary_map_is_serialized_as_sequence_of_slots_generations_and_values() {
+    let mut arena = Arena::new();
+    let a = arena.insert(());
+    let b = arena.insert(());
+    arena.remove(a);
+    let c = arena.insert(());
+    let mut map = SecondaryMap::<(), _, _, _>::new();
+    map.insert(c, 'c');
+    map.insert(b, 'b');
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::Tuple { len: 3 },
+        Token::U64(0),
+        Token::U64(1),
+        Token::Char('c'),
+        Token::TupleEnd,
+        Token::Tuple { len: 3 },
+        Token::U64(1),
+        Token::U64(0),
+        Token::Char('b'),
+        Token::TupleEnd,
+        Token::SeqEnd,
+    ];
+    assert_ser_tokens(&map, &tokens);
+}
+
+#[test]
+fn secondary_map_with_duplicate_slot_is_rejected() {
+    let tokens = [
+        Token::Seq { len: Some(2) },
+        Token::Tuple { len: 3 },
+        Token::U64(0),
+        Token::U64(0),
+        Token::Char('a'),
+        Token::TupleEnd,
+        Token::Tuple { len: 3 },
+        Token::U64(0),
+        Token::U64(1),
+        Token::Char('b'),
+        Token::TupleEnd,
+        Token::SeqEnd,
+    ];
+    serde_test::assert_de_tokens_error::<SecondaryMap<(), char, usize, u64>>(
+        &tokens,
+        "duplicate slot 0 in secondary map",
+    );
+    serde_test::assert_de_tokens_error::<SparseSecondaryMap<(), char, usize, u64>>(
+        &tokens,
+        "duplicate slot 0 in secondary map",
+    );
+}
+
+#[test]
+fn secondary_maps_round_trip() {
+    let mut arena = StandardArena::new();
+    let indices: Vec<_> = (0..10).map(|i| arena.insert(i)).collect();
+    for &idx in &indices[..5] {
+        arena.remove(idx);
+    }
+    let reused: Vec<_> = (0..5).map(|i| arena.insert(i)).collect();
+    let mut dense = SecondaryMap::new();
+    let mut sparse = SparseSecondaryMap::new();
+    for &idx in indices[5..].iter().chain(&reused) {
+        dense.insert(idx, arena[idx] * 2);
+        sparse.insert(idx, arena[idx] * 2);
+    }
+
+    let de_dense = round_trip(&dense);
+    let de_sparse = round_trip(&sparse);
+    // Both kinds of map share a format
+    let dense_from_sparse: SecondaryMap<_, _, _, _> =
+        bincode::deserialize(&bincode::serialize(&sparse).unwrap()).unwrap();
+    for map in &[&de_dense, &dense_from_sparse] {
+        assert_eq!(map.len(), 10);
+        for &idx in &indices[..5] {
+            assert_eq!(map.get(idx), None);
+        }
+        for &idx in indices[5..].iter().chain(&reused) {
+            assert_eq!(map[idx], arena[idx] * 2);
+        }
+    }
+    assert_eq!(de_sparse.len(), 10);
+    for &idx in &indices[..5] {
+        assert_eq!(de_sparse.get(idx), None);
+    }
+    for &idx in indices[5..].iter().chain(&reused) {
+        assert_eq!(de_sparse[idx], arena[idx] * 2);
+    }
+}
+
+fn round_trip<A: Serialize + DeserializeOwned>(value: &A) -> A {
+    let bytes = bincode::serialize(value).expect("value must be serialized");
+    bincode::deserialize(&bytes).expect("value must be deserialized")
+}
+
+/// Check a round-trip through `bincode` preserves the values, indices and
+/// future indices of an arena with some removed elements
+fn assert_round_trip<I, G, S>(mut arena: typed_generational_arena::Arena<u32, I, G, S>)
+where
+    I: ArenaIndex + Serialize + DeserializeOwned,
+    G: GenerationalIndex + Serialize + DeserializeOwned,
+    S: GenerationStrategy,
+{
+    let indices: Vec<_> = (0..8).map(|i| arena.insert(i)).collect();
+    arena.remove(indices[5]);
+    arena.remove(indices[2]);
+
+    let mut de_arena = round_trip(&arena);
+    assert_eq!(de_arena.len(), arena.len());
+    for &idx in &indices {
+        assert_eq!(de_arena.get(idx), arena.get(idx));
+    }
+    for i in 10..14 {
+        let idx = arena.insert(i);
+        de_arena.insert(i);
+        assert_eq!(de_arena.get(idx), Some(&i));
+    }
+}
+
+/// Check a round-trip through `bincode` preserves the values and future
+/// indices of a slab
+fn assert_slab_round_trip<I, S>(mut slab: typed_generational_arena::Arena<u32, I, DisableRemoval, S>)
+where
+    I: ArenaIndex + Serialize + DeserializeOwned,
+    S: GenerationStrategy,
+{
+    let indices: Vec<_> = (0..8).map(|i| slab.insert(i)).collect();
+
+    let mut de_slab = round_trip(&slab);
+    assert_eq!(de_slab.len(), slab.len());
+    for &idx in &indices {
+        assert_eq!(de_slab.get(idx), slab.get(idx));
+    }
+    let idx = slab.insert(8);
+    de_slab.insert(8);
+    assert_eq!(de_slab.get(idx), Some(&8));
 }
 
 /// Arena wrapper struct for comparing two arenas
 ///
 /// `serde_test::assert_tokens` requires the value implements `PartialEq`,
 /// but `Arena` does not implement it.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ArenaCompare<T>(Arena<T>);
 
-impl<'a, T> PartialEq for ArenaCompare<T>
+impl<T> PartialEq for ArenaCompare<T>
 where
     T: PartialEq,
 {
@@ -154,22 +721,82 @@ where
     }
 }
 
-fn assert_de_tokens<T>(value: &Arena<T>, tokens: &[Token])
+/// Wrap the tokens of the free list and slots of an arena with generation
+/// `generation` into the tokens of both its human-readable and compact forms
+fn versioned_tokens(generation: u64, tokens: &[Token]) -> (Vec<Token>, Vec<Token>) {
+    let mut readable = vec![
+        Token::Struct {
+            name: "Arena",
+            len: 6,
+        },
+        Token::Str("version"),
+        Token::U32(1),
+        Token::Str("generation"),
+        Token::U64(generation),
+        Token::Str("free_list"),
+    ];
+    let free_list_end = tokens
+        .iter()
+        .position(|token| *token == Token::SeqEnd)
+        .expect("tokens must start with the free list");
+    readable.extend(&tokens[..=free_list_end]);
+    readable.push(Token::Str("slots"));
+    readable.extend(&tokens[free_list_end + 1..]);
+    readable.extend(default_policy_tokens(true));
+    readable.push(Token::StructEnd);
+
+    let mut compact = vec![
+        Token::Tuple { len: 7 },
+        Token::U64(u64::MAX),
+        Token::U32(1),
+        Token::U64(generation),
+    ];
+    compact.extend(tokens);
+    compact.extend(default_policy_tokens(false));
+    compact.push(Token::TupleEnd);
+    (readable, compact)
+}
+
+/// Tokens of the default reuse and exhaustion policies, with their field
+/// names in the human-readable form
+fn default_policy_tokens(readable: bool) -> Vec<Token> {
+    let reuse = Token::UnitVariant {
+        name: "ReusePolicy",
+        variant: "Lifo",
+    };
+    let exhaustion = Token::UnitVariant {
+        name: "ExhaustionPolicy",
+        variant: "Increment",
+    };
+    if readable {
+        vec![
+            Token::Str("reuse_policy"),
+            reuse,
+            Token::Str("exhaustion_policy"),
+            exhaustion,
+        ]
+    } else {
+        vec![reuse, exhaustion]
+    }
+}
+
+fn assert_tokens<T>(value: &Arena<T>, generation: u64, tokens: &[Token])
 where
     T: Serialize + for<'de> Deserialize<'de> + PartialEq + Clone + Debug,
 {
+    let (readable, compact) = versioned_tokens(generation, tokens);
+    assert_ser_tokens(&value.readable(), &readable);
+    assert_ser_tokens(&value.compact(), &compact);
+
     let comp = ArenaCompare(value.clone());
+    serde_test::assert_de_tokens(&comp.clone().readable(), &compare_tokens(&readable));
+    serde_test::assert_de_tokens(&comp.compact(), &compare_tokens(&compact));
+}
+
+fn compare_tokens(tokens: &[Token]) -> Vec<Token> {
     let mut comp_tokens = vec![Token::NewtypeStruct {
         name: "ArenaCompare",
     }];
     comp_tokens.extend(tokens);
-    serde_test::assert_de_tokens(&comp, &comp_tokens);
-}
-
-fn assert_tokens<T>(value: &Arena<T>, tokens: &[Token])
-where
-    T: Serialize + for<'de> Deserialize<'de> + PartialEq + Clone + Debug,
-{
-    assert_ser_tokens(value, tokens);
-    assert_de_tokens(value, tokens);
+    comp_tokens
 }
@@ -108,3 +108,58 @@ quickcheck! {
         arena.into_iter().collect::<BTreeSet<_>>() == elems
     }
 }
+
+quickcheck! {
+    fn clear_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        arena.clear();
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
+
+quickcheck! {
+    fn drain_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>, taken: usize) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        // Dropping a partially consumed `Drain` must still remove everything.
+        let live = arena.len();
+        let drained: Vec<_> = arena.drain().take(taken % (live + 1)).collect();
+        for (idx, value) in drained {
+            match indices.iter().position(|&i| i == idx) {
+                Some(i) if elems[i] == value => {}
+                _ => return false,
+            }
+        }
+        if !arena.is_empty() {
+            return false;
+        }
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
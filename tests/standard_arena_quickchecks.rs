@@ -4,6 +4,7 @@ extern crate quickcheck;
 
 use std::collections::BTreeSet;
 use std::iter::FromIterator;
+use typed_generational_arena::ReusePolicy;
 use typed_generational_arena::StandardArena as Arena;
 
 quickcheck! {
@@ -108,3 +109,177 @@ quickcheck! {
         arena.into_iter().collect::<BTreeSet<_>>() == elems
     }
 }
+
+quickcheck! {
+    fn clear_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        arena.clear();
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
+
+quickcheck! {
+    fn drain_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>, taken: usize) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        // Dropping a partially consumed `Drain` must still remove everything.
+        let live = arena.len();
+        let drained: Vec<_> = arena.drain().take(taken % (live + 1)).collect();
+        for (idx, value) in drained {
+            match indices.iter().position(|&i| i == idx) {
+                Some(i) if elems[i] == value => {}
+                _ => return false,
+            }
+        }
+        if !arena.is_empty() {
+            return false;
+        }
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
+
+/// Check an arena which never grows reuses free slots in the order given by
+/// `take_free`, which takes the next slot out of a model of the free slots
+/// where freed slots are pushed at the back
+fn reuses_free_slots_in_order<F>(policy: ReusePolicy, ops: Vec<(bool, usize)>, take_free: F)
+where
+    F: Fn(&mut Vec<usize>) -> usize,
+{
+    let capacity = 8;
+    let mut arena = Arena::with_capacity(capacity);
+    arena.set_reuse_policy(policy);
+    // The initial free list is in ascending order
+    let mut free: Vec<usize> = (0..capacity).collect();
+    if policy == ReusePolicy::Lifo {
+        free.reverse();
+    }
+    let mut live = vec![];
+    for (remove, i) in ops {
+        if remove && !live.is_empty() {
+            let idx = live.swap_remove(i % live.len());
+            arena.remove(idx).unwrap();
+            free.push(idx.to_idx());
+        } else if !free.is_empty() {
+            let idx = arena.insert(i);
+            assert_eq!(idx.to_idx(), take_free(&mut free));
+            live.push(idx);
+        } else {
+            assert!(arena.try_insert(i).is_err());
+        }
+    }
+}
+
+quickcheck! {
+    fn lifo_reuses_free_slots_in_order(ops: Vec<(bool, usize)>) -> () {
+        reuses_free_slots_in_order(ReusePolicy::Lifo, ops, |free| free.pop().unwrap())
+    }
+}
+
+quickcheck! {
+    fn fifo_reuses_free_slots_in_order(ops: Vec<(bool, usize)>) -> () {
+        reuses_free_slots_in_order(ReusePolicy::Fifo, ops, |free| free.remove(0))
+    }
+}
+
+quickcheck! {
+    fn lowest_first_reuses_free_slots_in_order(ops: Vec<(bool, usize)>) -> () {
+        reuses_free_slots_in_order(ReusePolicy::LowestFirst, ops, |free| {
+            let lowest = (0..free.len()).min_by_key(|&i| free[i]).unwrap();
+            free.remove(lowest)
+        })
+    }
+}
+
+quickcheck! {
+    fn compact_keeps_elements_and_invalidates_stale_indices(ops: Vec<(bool, usize)>) -> () {
+        let mut arena = Arena::new();
+        let mut live = vec![];
+        let mut dead = vec![];
+        for (remove, i) in ops {
+            if remove && !live.is_empty() {
+                let (idx, _) = live.swap_remove(i % live.len());
+                arena.remove(idx).unwrap();
+                dead.push(idx);
+            } else {
+                live.push((arena.insert(i), i));
+            }
+        }
+
+        let remap = arena.compact();
+        assert_eq!(arena.capacity(), live.len());
+        for (old, _) in remap.iter() {
+            dead.push(*old);
+        }
+        for (idx, value) in live.iter_mut() {
+            *idx = remap.remap(*idx);
+            assert_eq!(arena[*idx], *value);
+        }
+        for i in 0..dead.len() {
+            live.push((arena.insert(i), i));
+        }
+        for idx in dead {
+            assert!(!arena.contains(idx));
+        }
+        for (idx, value) in live {
+            assert_eq!(arena[idx], value);
+        }
+    }
+}
+
+quickcheck! {
+    fn shrink_to_fit_keeps_elements_and_invalidates_stale_indices(ops: Vec<(bool, usize)>) -> () {
+        let mut arena = Arena::new();
+        let mut live = vec![];
+        let mut dead = vec![];
+        for (remove, i) in ops {
+            if remove && !live.is_empty() {
+                let (idx, _) = live.swap_remove(i % live.len());
+                arena.remove(idx).unwrap();
+                dead.push(idx);
+            } else {
+                live.push((arena.insert(i), i));
+            }
+        }
+
+        arena.shrink_to_fit();
+        for (idx, value) in live.iter() {
+            assert_eq!(arena[*idx], *value);
+        }
+        for i in 0..dead.len() {
+            live.push((arena.insert(i), i));
+        }
+        for idx in dead {
+            assert!(!arena.contains(idx));
+        }
+        for (idx, value) in live {
+            assert_eq!(arena[idx], value);
+        }
+    }
+}
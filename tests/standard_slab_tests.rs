@@ -124,3 +124,26 @@ fn clear() {
     assert_eq!(slab.capacity(), 4);
     assert_eq!(slab.len(), 0);
 }
+
+#[test]
+fn insert_after_drain() {
+    let mut slab = Slab::with_capacity(2);
+    slab.insert(42);
+    slab.insert(43);
+    assert_eq!(slab.drain().count(), 2);
+    assert_eq!(slab.capacity(), 2);
+    let idx = slab.insert(44);
+    assert_eq!(slab[idx], 44);
+    assert_eq!(slab.len(), 1);
+}
+
+#[test]
+fn shrink_to_fit_after_clear() {
+    let mut slab = Slab::with_capacity(8);
+    slab.insert(1);
+    slab.clear();
+    slab.shrink_to_fit();
+    assert_eq!(slab.capacity(), 0);
+    let idx = slab.insert(2);
+    assert_eq!(slab[idx], 2);
+}
@@ -0,0 +1,232 @@
+extern crate typed_generational_arena;
+#[macro_use]
+extern crate quickcheck;
+
+use std::collections::BTreeSet;
+use std::iter::FromIterator;
+use typed_generational_arena::StandardSlotArena as Arena;
+
+quickcheck! {
+    fn always_contains_inserted_elements(elems: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.into_iter().map(|e| arena.insert(e)).collect();
+        indices.into_iter().all(|i| arena.contains(i))
+    }
+}
+
+quickcheck! {
+    fn never_contains_deleted_elements(elems: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.into_iter().map(|e| arena.insert(e)).collect();
+        for i in indices.iter().cloned() {
+            arena.remove(i).unwrap();
+        }
+        indices.into_iter().all(|i| !arena.contains(i))
+    }
+}
+
+quickcheck! {
+    fn insert_delete_insert(elems: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for (i, idx) in indices.iter().cloned().enumerate() {
+            if arena.remove(idx).unwrap() != elems[i] {
+                return false;
+            }
+        }
+
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        new_indices.into_iter().enumerate().all(|(i, idx)| {
+            !arena.contains(indices[i]) && arena.remove(idx).unwrap() == elems[i]
+        })
+    }
+}
+
+quickcheck! {
+    fn interp(ops: Vec<(bool, usize)>) -> () {
+        let mut arena = Arena::new();
+        let mut live_indices = vec![];
+        let mut dead_indices = vec![];
+
+        for (delete, i) in ops {
+            if delete && !live_indices.is_empty() {
+                let i = i % live_indices.len();
+                let (idx, expected) = live_indices.remove(i);
+                assert_eq!(arena.remove(idx).unwrap(), expected);
+                dead_indices.push(idx);
+            } else {
+                live_indices.push((arena.insert(i), i));
+            }
+
+            // All live indices always have the expected value.
+            for (live, expected) in live_indices.iter().cloned() {
+                assert_eq!(*arena.get(live).unwrap(), expected);
+            }
+
+            // All dead indices are never contained in the arena.
+            for dead in dead_indices.iter().cloned() {
+                assert!(!arena.contains(dead));
+            }
+        }
+
+        // All the remaining values are expected.
+        let remaining: Vec<_> = arena.into_iter().collect();
+        assert_eq!(remaining.len(), live_indices.len());
+        for rem in remaining {
+            let i = live_indices.iter().position(|&(_, v)| v == rem).unwrap();
+            live_indices.remove(i);
+        }
+    }
+}
+
+quickcheck! {
+    fn iter(elems: BTreeSet<usize>) -> bool {
+        let arena = Arena::from_iter(elems.clone());
+        arena.iter().all(|(idx, value)| {
+            elems.contains(value) && arena.get(idx) == Some(value)
+        })
+    }
+}
+
+quickcheck! {
+    fn iter_mut(elems: BTreeSet<usize>) -> bool {
+        let mut arena = Arena::from_iter(elems.clone());
+        for (_, value) in &mut arena {
+            *value = value.wrapping_add(1);
+        }
+        arena.iter().all(|(idx, value)| {
+            let orig_value = value.wrapping_sub(1);
+            elems.contains(&orig_value) && arena.get(idx) == Some(value)
+        })
+    }
+}
+
+quickcheck! {
+    fn from_iter_into_iter(elems: BTreeSet<usize>) -> bool {
+        let arena = Arena::from_iter(elems.clone());
+        arena.into_iter().collect::<BTreeSet<_>>() == elems
+    }
+}
+
+quickcheck! {
+    fn clear_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        arena.clear();
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
+
+quickcheck! {
+    fn drain_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>, taken: usize) -> bool {
+        let mut arena = Arena::new();
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        // Dropping a partially consumed `Drain` must still remove everything.
+        let live = arena.len();
+        let drained: Vec<_> = arena.drain().take(taken % (live + 1)).collect();
+        for (idx, value) in drained {
+            match indices.iter().position(|&i| i == idx) {
+                Some(i) if elems[i] == value => {}
+                _ => return false,
+            }
+        }
+        if !arena.is_empty() {
+            return false;
+        }
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
+
+quickcheck! {
+    fn compact_keeps_elements_and_invalidates_stale_indices(ops: Vec<(bool, usize)>) -> () {
+        let mut arena = Arena::new();
+        let mut live = vec![];
+        let mut dead = vec![];
+        for (remove, i) in ops {
+            if remove && !live.is_empty() {
+                let (idx, _) = live.swap_remove(i % live.len());
+                arena.remove(idx).unwrap();
+                dead.push(idx);
+            } else {
+                live.push((arena.insert(i), i));
+            }
+        }
+
+        let remap = arena.compact();
+        assert_eq!(arena.capacity(), live.len());
+        for (old, _) in remap.iter() {
+            dead.push(*old);
+        }
+        for (idx, value) in live.iter_mut() {
+            *idx = remap.remap(*idx);
+            assert_eq!(arena[*idx], *value);
+        }
+        for i in 0..dead.len() {
+            live.push((arena.insert(i), i));
+        }
+        for idx in dead {
+            assert!(!arena.contains(idx));
+        }
+        for (idx, value) in live {
+            assert_eq!(arena[idx], value);
+        }
+    }
+}
+
+quickcheck! {
+    fn shrink_to_fit_keeps_elements_and_invalidates_stale_indices(ops: Vec<(bool, usize)>) -> () {
+        let mut arena = Arena::new();
+        let mut live = vec![];
+        let mut dead = vec![];
+        for (remove, i) in ops {
+            if remove && !live.is_empty() {
+                let (idx, _) = live.swap_remove(i % live.len());
+                arena.remove(idx).unwrap();
+                dead.push(idx);
+            } else {
+                live.push((arena.insert(i), i));
+            }
+        }
+
+        arena.shrink_to_fit();
+        for (idx, value) in live.iter() {
+            assert_eq!(arena[*idx], *value);
+        }
+        for i in 0..dead.len() {
+            live.push((arena.insert(i), i));
+        }
+        for idx in dead {
+            assert!(!arena.contains(idx));
+        }
+        for (idx, value) in live {
+            assert_eq!(arena[idx], value);
+        }
+    }
+}
@@ -11,7 +11,7 @@ quickcheck! {
         let mut arena = Arena::new();
         let mut elems = elems;
         // Avoid overflow
-        elems.truncate((std::u16::MAX - 1) as usize);
+        elems.truncate((u16::MAX - 1) as usize);
         let indices: Vec<_> = elems.into_iter().map(|e| arena.insert(e)).collect();
         indices.into_iter().all(|i| arena.contains(i))
     }
@@ -22,7 +22,7 @@ quickcheck! {
         let mut arena = Arena::new();
         let mut elems = elems;
         // Avoid overflow
-        elems.truncate((std::u16::MAX - 1) as usize);
+        elems.truncate((u16::MAX - 1) as usize);
         let indices: Vec<_> = elems.into_iter().map(|e| arena.insert(e)).collect();
         for i in indices.iter().cloned() {
             arena.remove(i).unwrap();
@@ -36,7 +36,7 @@ quickcheck! {
         let mut arena = Arena::new();
         let mut elems = elems;
         // Avoid overflow
-        elems.truncate((std::u16::MAX - 1) as usize);
+        elems.truncate((u16::MAX - 1) as usize);
 
         let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
         for (i, idx) in indices.iter().cloned().enumerate() {
@@ -57,7 +57,7 @@ quickcheck! {
         let mut arena = Arena::new();
         let mut ops = ops;
         // Avoid overflow
-        ops.truncate((std::u16::MAX - 1) as usize);
+        ops.truncate((u16::MAX - 1) as usize);
         let mut live_indices = vec![];
         let mut dead_indices = vec![];
 
@@ -94,7 +94,7 @@ quickcheck! {
 
 quickcheck! {
     fn iter(elems: BTreeSet<usize>) -> bool {
-        let arena = Arena::from_iter(elems.iter().take((std::u16::MAX - 1) as usize).cloned());
+        let arena = Arena::from_iter(elems.iter().take((u16::MAX - 1) as usize).cloned());
         arena.iter().all(|(idx, value)| {
             elems.contains(value) && arena.get(idx) == Some(value)
         })
@@ -103,7 +103,7 @@ quickcheck! {
 
 quickcheck! {
     fn iter_mut(elems: BTreeSet<usize>) -> bool {
-        let mut arena = Arena::from_iter(elems.iter().take((std::u16::MAX - 1) as usize).cloned());
+        let mut arena = Arena::from_iter(elems.iter().take((u16::MAX - 1) as usize).cloned());
         for (_, value) in &mut arena {
             *value = value.wrapping_add(1);
         }
@@ -116,7 +116,72 @@ quickcheck! {
 
 quickcheck! {
     fn from_iter_into_iter(elems: BTreeSet<usize>) -> bool {
-        let arena = Arena::from_iter(elems.iter().take((std::u16::MAX - 1) as usize).cloned());
+        let arena = Arena::from_iter(elems.iter().take((u16::MAX - 1) as usize).cloned());
         arena.into_iter().collect::<BTreeSet<_>>() == elems
     }
 }
+
+quickcheck! {
+    fn clear_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>) -> bool {
+        let mut arena = Arena::new();
+        let mut elems = elems;
+        let mut removed = removed;
+        // Avoid overflow, and keep generations from wrapping around
+        elems.truncate((u16::MAX / 2) as usize);
+        removed.truncate((u16::MAX / 2) as usize);
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        arena.clear();
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
+
+quickcheck! {
+    fn drain_invalidates_all_indices(elems: Vec<usize>, removed: Vec<usize>, taken: usize) -> bool {
+        let mut arena = Arena::new();
+        let mut elems = elems;
+        let mut removed = removed;
+        // Avoid overflow, and keep generations from wrapping around
+        elems.truncate((u16::MAX / 2) as usize);
+        removed.truncate((u16::MAX / 2) as usize);
+        let indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        for i in removed {
+            if !indices.is_empty() {
+                arena.remove(indices[i % indices.len()]);
+            }
+        }
+
+        // Dropping a partially consumed `Drain` must still remove everything.
+        let live = arena.len();
+        let drained: Vec<_> = arena.drain().take(taken % (live + 1)).collect();
+        for (idx, value) in drained {
+            match indices.iter().position(|&i| i == idx) {
+                Some(i) if elems[i] == value => {}
+                _ => return false,
+            }
+        }
+        if !arena.is_empty() {
+            return false;
+        }
+        let new_indices: Vec<_> = elems.iter().cloned().map(|e| arena.insert(e)).collect();
+        let stale_rejected = indices.iter().cloned().all(|i| {
+            !arena.contains(i)
+                && arena.get(i).is_none()
+                && arena.get_mut(i).is_none()
+                && arena.remove(i).is_none()
+        });
+        stale_rejected && new_indices.iter().zip(&elems).all(|(&i, e)| arena.get(i) == Some(e))
+    }
+}
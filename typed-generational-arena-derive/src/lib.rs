@@ -0,0 +1,257 @@
+/*!
+Derive macro for the `Trace` trait of
+[`typed-generational-arena`](https://crates.io/crates/typed-generational-arena).
+
+The derived implementation traces every field of a struct, or of the current
+variant of an enum, so every field type has to implement `Trace` for the arena
+being collected. Fields which hold no indices into that arena, such as indices into
+another arena, can be skipped with `#[trace(skip)]`.
+
+```rust
+#[macro_use]
+extern crate typed_generational_arena_derive;
+extern crate typed_generational_arena;
+use typed_generational_arena::{StandardArena, StandardIndex};
+
+#[derive(Trace)]
+struct Node {
+    name: String,
+    children: Vec<StandardIndex<Node>>,
+    #[trace(skip)]
+    mesh: StandardIndex<Mesh>,
+}
+
+struct Mesh;
+
+# fn main() {
+let mut meshes = StandardArena::new();
+let mesh = meshes.insert(Mesh);
+let mut nodes = StandardArena::new();
+let leaf = nodes.insert(Node { name: "leaf".into(), children: vec![], mesh });
+let root = nodes.insert(Node { name: "root".into(), children: vec![leaf], mesh });
+let orphan = nodes.insert(Node { name: "orphan".into(), children: vec![], mesh });
+
+assert_eq!(nodes.collect_garbage(vec![root]), vec![orphan]);
+# }
+```
+
+The implementation is generic over the arena being collected, `Arena<__TraceT,
+__TraceI, __TraceG>`, and bounded by every traced field type implementing `Trace`
+for it. A type which holds itself, through a `Box` for instance, would then need
+`Trace` to implement `Trace`, so its bounds have to be given instead with
+`#[trace(bound = "...")]`, in terms of those parameters:
+
+```rust
+#[macro_use]
+extern crate typed_generational_arena_derive;
+extern crate typed_generational_arena;
+use typed_generational_arena::{StandardArena, StandardIndex, Trace};
+
+#[derive(Trace)]
+#[trace(bound = "StandardIndex<Node>: Trace<__TraceT, __TraceI, __TraceG>")]
+struct Node {
+    link: Option<StandardIndex<Node>>,
+    child: Option<Box<Node>>,
+}
+
+# fn main() {
+let mut nodes = StandardArena::new();
+let linked = nodes.insert(Node { link: None, child: None });
+let child = Node { link: Some(linked), child: None };
+let root = nodes.insert(Node { link: None, child: Some(Box::new(child)) });
+
+assert!(nodes.collect_garbage(vec![root]).is_empty());
+# }
+```
+*/
+
+#![forbid(unsafe_code, missing_docs)]
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Comma;
+use syn::{
+    parse_macro_input, parse_quote, Attribute, Data, DeriveInput, Error, Field, Fields,
+    GenericParam, Ident, Index, LitStr, WherePredicate,
+};
+
+/// Derive `Trace` for a struct or an enum, by tracing each of its fields which is
+/// not marked `#[trace(skip)]`, with the bounds given by `#[trace(bound = "...")]`
+/// if any.
+#[proc_macro_derive(Trace, attributes(trace))]
+pub fn derive_trace(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2, Error> {
+    let name = &input.ident;
+    let (element, index, generation) = (
+        Ident::new("__TraceT", Span::call_site()),
+        Ident::new("__TraceI", Span::call_site()),
+        Ident::new("__TraceG", Span::call_site()),
+    );
+    let trace = quote!(::typed_generational_arena::Trace<#element, #index, #generation>);
+    let call = quote!(::typed_generational_arena::Trace::<#element, #index, #generation>::trace);
+
+    // Unless the bounds are given, every traced field type has to be traceable,
+    // which also covers the type parameters of the input
+    let bound = container_bound(&input.attrs)?;
+    let mut bounds = Vec::new();
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let fields = traced(&data.fields)?;
+            let calls = fields.iter().map(|&(position, field)| {
+                bounds.push(field.ty.clone());
+                let member = match field.ident {
+                    Some(ref ident) => quote!(#ident),
+                    None => {
+                        let index = Index::from(position);
+                        quote!(#index)
+                    }
+                };
+                quote!(#call(&self.#member, tracer);)
+            });
+            quote!(#(#calls)*)
+        }
+        Data::Enum(ref data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let fields = traced(&variant.fields)?;
+                let bindings: Vec<_> = fields
+                    .iter()
+                    .map(|&(position, _)| {
+                        Ident::new(&format!("__field{}", position), Span::call_site())
+                    })
+                    .collect();
+                let calls = bindings
+                    .iter()
+                    .map(|binding| quote!(#call(#binding, tracer);));
+                let pattern = match variant.fields {
+                    Fields::Named(_) => {
+                        let names = fields.iter().map(|&(_, field)| &field.ident);
+                        quote!(#name::#ident { #(#names: #bindings,)* .. })
+                    }
+                    Fields::Unnamed(_) => {
+                        let positions = fields.iter().map(|&(position, _)| Index::from(position));
+                        quote!(#name::#ident { #(#positions: #bindings,)* .. })
+                    }
+                    Fields::Unit => quote!(#name::#ident),
+                };
+                bounds.extend(fields.iter().map(|&(_, field)| field.ty.clone()));
+                arms.push(quote!(#pattern => { #(#calls)* }));
+            }
+            // Matching on a reference binds the fields by reference
+            quote! {
+                match self {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => {}
+                }
+            }
+        }
+        Data::Union(ref data) => {
+            return Err(Error::new(
+                data.union_token.span(),
+                "Trace cannot be derived for unions",
+            ))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    generics
+        .params
+        .push(GenericParam::Type(parse_quote!(#element)));
+    generics
+        .params
+        .push(GenericParam::Type(parse_quote!(#index)));
+    generics
+        .params
+        .push(GenericParam::Type(parse_quote!(#generation)));
+    {
+        let where_clause = generics.make_where_clause();
+        match bound {
+            Some(bound) => where_clause.predicates.extend(bound),
+            None => {
+                for ty in bounds {
+                    where_clause.predicates.push(parse_quote!(#ty: #trace));
+                }
+            }
+        }
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #trace for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn trace(&self, tracer: &mut ::typed_generational_arena::Tracer<#element, #index, #generation>) {
+                #body
+            }
+        }
+    })
+}
+
+/// Get the bounds given by `#[trace(bound = "...")]` on the input, if any
+fn container_bound(
+    attrs: &[Attribute],
+) -> Result<Option<Punctuated<WherePredicate, Comma>>, Error> {
+    let mut bound = None;
+    for attr in attrs {
+        if !attr.path().is_ident("trace") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("bound") {
+                let predicates: LitStr = meta.value()?.parse()?;
+                bound = Some(predicates.parse_with(Punctuated::parse_terminated)?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported trace attribute, expected `bound`"))
+            }
+        })?;
+    }
+    Ok(bound)
+}
+
+/// Get the fields to trace, with their positions, leaving out the skipped ones
+fn traced(fields: &Fields) -> Result<Vec<(usize, &Field)>, Error> {
+    let mut traced = Vec::new();
+    for (position, field) in fields.iter().enumerate() {
+        if !skipped(field)? {
+            traced.push((position, field));
+        }
+    }
+    Ok(traced)
+}
+
+/// Is `field` marked `#[trace(skip)]`?
+fn skipped(field: &Field) -> Result<bool, Error> {
+    let mut skip = false;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("trace") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported trace attribute, expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
@@ -0,0 +1,119 @@
+#[macro_use]
+extern crate typed_generational_arena_derive;
+extern crate typed_generational_arena;
+use std::collections::HashMap;
+use typed_generational_arena::{StandardArena, StandardIndex, Trace};
+
+#[derive(Trace)]
+enum Expr {
+    Literal(i64),
+    Negate(StandardIndex<Expr>),
+    Add {
+        lhs: StandardIndex<Expr>,
+        rhs: StandardIndex<Expr>,
+    },
+    Call(String, Vec<StandardIndex<Expr>>),
+    Unit,
+}
+
+#[test]
+fn enum_variants_trace_their_fields() {
+    let mut arena = StandardArena::new();
+    let one = arena.insert(Expr::Literal(1));
+    let two = arena.insert(Expr::Literal(2));
+    let negated = arena.insert(Expr::Negate(two));
+    let sum = arena.insert(Expr::Add {
+        lhs: one,
+        rhs: negated,
+    });
+    let unused = arena.insert(Expr::Unit);
+    let arg = arena.insert(Expr::Literal(3));
+    let call = arena.insert(Expr::Call("f".into(), vec![arg]));
+
+    assert_eq!(arena.collect_garbage(vec![sum]), vec![unused, arg, call]);
+    assert_eq!(arena.len(), 4);
+    assert_eq!(arena.collect_garbage(vec![negated]), vec![one, sum]);
+}
+
+#[derive(Trace)]
+struct Pair(StandardIndex<Pair>, #[trace(skip)] StandardIndex<Pair>);
+
+#[test]
+fn skipped_fields_are_not_traced() {
+    let mut arena = StandardArena::new();
+    let a = arena.insert_with(|a| Pair(a, a));
+    let b = arena.insert(Pair(a, a));
+    let c = arena.insert(Pair(b, a));
+    let d = arena.insert(Pair(c, c));
+    // `e` only refers to `d` through its skipped field
+    let e = arena.insert(Pair(a, d));
+
+    assert_eq!(arena.collect_garbage(vec![e]), vec![b, c, d]);
+    assert!(arena.contains(a) && arena.contains(e));
+    assert!(!arena.contains(arena[e].1));
+}
+
+#[derive(Trace)]
+struct Graph<K> {
+    edges: HashMap<K, Vec<StandardIndex<Graph<K>>>>,
+    #[trace(skip)]
+    name: K,
+}
+
+#[test]
+fn generic_types_are_bounded_by_their_fields() {
+    let mut arena = StandardArena::new();
+    let leaf = arena.insert(Graph {
+        edges: HashMap::new(),
+        name: "leaf",
+    });
+    let orphan = arena.insert(Graph {
+        edges: HashMap::new(),
+        name: "orphan",
+    });
+    let mut edges = HashMap::new();
+    edges.insert("out", vec![leaf]);
+    let root = arena.insert(Graph {
+        edges,
+        name: "root",
+    });
+
+    assert_eq!(arena.collect_garbage(vec![root]), vec![orphan]);
+    assert_eq!(arena[root].name, "root");
+}
+
+#[derive(Trace)]
+#[trace(bound = "StandardIndex<Tree>: Trace<__TraceT, __TraceI, __TraceG>")]
+struct Tree {
+    link: Option<StandardIndex<Tree>>,
+    child: Option<Box<Tree>>,
+}
+
+#[test]
+fn recursive_types_are_bounded_by_the_given_bounds() {
+    let mut arena = StandardArena::new();
+    let leaf = arena.insert(Tree {
+        link: None,
+        child: None,
+    });
+    let orphan = arena.insert(Tree {
+        link: Some(leaf),
+        child: None,
+    });
+    // `leaf` is only reachable through the link of a nested child
+    let nested = Tree {
+        link: None,
+        child: Some(Box::new(Tree {
+            link: Some(leaf),
+            child: None,
+        })),
+    };
+    let root = arena.insert(Tree {
+        link: None,
+        child: Some(Box::new(nested)),
+    });
+
+    assert_eq!(arena.collect_garbage(vec![root]), vec![orphan]);
+    assert!(arena.contains(leaf));
+    assert_eq!(arena.collect_garbage(None), vec![leaf, root]);
+}